@@ -21,7 +21,7 @@ use unicorn_graphics::UnicornGraphics;
 
 use galactic_unicorn_embassy::pins::UnicornDisplayPins;
 use galactic_unicorn_embassy::GalacticUnicorn;
-use galactic_unicorn_embassy::{HEIGHT, WIDTH};
+use galactic_unicorn_embassy::{BitstreamConfig, HEIGHT, WIDTH};
 
 #[embassy_executor::main]
 async fn main(spawner: Spawner) {
@@ -42,7 +42,14 @@ async fn main(spawner: Spawner) {
         light_sensor: p.PIN_28,
     };
 
-    let mut gu = GalacticUnicorn::new(p.PIO0, display_pins, sensor_pins, p.ADC, p.DMA_CH0);
+    let mut gu = GalacticUnicorn::new(
+        p.PIO0,
+        display_pins,
+        sensor_pins,
+        p.ADC,
+        p.DMA_CH0,
+        BitstreamConfig::default(),
+    );
 
     let mut graphics = UnicornGraphics::<WIDTH, HEIGHT>::new();
     let mut heat: [[f32; 13]; 53] = [[0.0; 13]; 53];
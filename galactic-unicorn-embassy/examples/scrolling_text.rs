@@ -7,28 +7,21 @@
 #![no_main]
 #![feature(type_alias_impl_trait)]
 
-use core::fmt::Write;
-
 use embassy_executor::Spawner;
 use embassy_rp::gpio::{Input, Pull};
-use embassy_time::Timer;
+use embassy_time::{Instant, Timer};
 
 use defmt_rtt as _;
 use panic_halt as _;
 
 use embedded_graphics::mono_font::{ascii::FONT_6X10, MonoTextStyle};
-use embedded_graphics::text::Text;
-use embedded_graphics::Drawable;
-use embedded_graphics_core::{
-    pixelcolor::{Rgb888, WebColors},
-    prelude::Point,
-};
+use embedded_graphics_core::pixelcolor::{Rgb888, WebColors};
 
-use unicorn_graphics::UnicornGraphics;
+use unicorn_graphics::{Marquee, UnicornGraphics};
 
-use galactic_unicorn_embassy::pins::{UnicornButtonPins, UnicornDisplayPins};
+use galactic_unicorn_embassy::pins::{UnicornButtonPins, UnicornDisplayPins, UnicornSensorPins};
 use galactic_unicorn_embassy::GalacticUnicorn;
-use galactic_unicorn_embassy::{HEIGHT, WIDTH};
+use galactic_unicorn_embassy::{BitstreamConfig, HEIGHT, WIDTH};
 
 #[embassy_executor::main]
 async fn main(spawner: Spawner) {
@@ -61,57 +54,48 @@ async fn main(spawner: Spawner) {
         sleep: Input::new(p.PIN_27, Pull::Up),
     };
 
-    let mut gu = GalacticUnicorn::new(p.PIO0, display_pins, sensor_pins, p.ADC, p.DMA_CH0);
+    let mut gu = GalacticUnicorn::new(
+        p.PIO0,
+        display_pins,
+        sensor_pins,
+        p.ADC,
+        p.DMA_CH0,
+        BitstreamConfig::default(),
+    );
 
     let mut graphics = UnicornGraphics::<WIDTH, HEIGHT>::new();
     gu.set_pixels(&graphics);
 
-    // keep track of scroll position
-    let mut x: f32 = -53.0;
-
     // Create a new character style
     let style = MonoTextStyle::new(&FONT_6X10, Rgb888::CSS_PURPLE);
 
-    let default_message = "Pirate. Monkey. Robot. Ninja.";
-    let mut message = heapless::String::<256>::new();
+    let mut marquee = Marquee::<WIDTH, HEIGHT>::new("Pirate. Monkey. Robot. Ninja.", style);
 
-    let mut speed: f32 = 0.15;
+    let mut last_update = Instant::now();
 
     loop {
-        message.clear();
-        write!(&mut message, "{default_message}").unwrap();
-
         if button_pins.switch_a.is_low() {
-            speed += 0.01;
+            marquee.speed += 1;
         }
 
         if button_pins.switch_b.is_low() {
-            speed -= 0.01;
-            if speed < 0.01 {
-                speed = 0.01;
-            }
+            marquee.speed = marquee.speed.saturating_sub(1).max(1);
         }
 
         if button_pins.switch_c.is_low() {
-            speed = 0.15;
+            marquee.speed = 15;
         }
 
         if button_pins.switch_d.is_low() {
-            message.clear();
-            write!(&mut message, "{speed}").unwrap();
+            marquee.reset();
         }
 
-        let width = message.len() * style.font.character_size.width as usize;
-        x += speed;
-        if x > width as f32 {
-            x = -53.0;
-        }
+        let now = Instant::now();
+        marquee.update((now - last_update).as_millis() as u32);
+        last_update = now;
 
         graphics.fill(Rgb888::new(10, 10, 10));
-
-        Text::new(&message, Point::new(0 - x as i32, 7), style)
-            .draw(&mut graphics)
-            .unwrap();
+        marquee.draw(&mut graphics).unwrap();
 
         gu.set_pixels(&graphics);
 
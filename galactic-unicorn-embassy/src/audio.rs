@@ -0,0 +1,300 @@
+use embassy_rp::{
+    bind_interrupts, clocks,
+    gpio::{Level, Output},
+    peripherals::{DMA_CH1, PIN_22, PIO1},
+    pio::{self, Direction, FifoJoin, Pio, ShiftConfig, ShiftDirection, StateMachine},
+    Peripheral, PeripheralRef,
+};
+use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, channel::Channel};
+use embassy_time::Duration;
+use fixed::types::U24F8;
+use unicorn_synth::{AudioChannel, Synth, Waveform};
+
+use crate::buttons::{ButtonEvent, ButtonEventKind, UnicornButtons};
+use crate::pins::UnicornAudioPins;
+
+/// Number of samples played per second.
+pub const SAMPLE_RATE: u32 = 22_050;
+
+/// Number of samples held in each buffer of the sample ring.
+pub const BUFFER_SIZE: usize = 256;
+
+/// Number of buffers in the sample ring.
+const BUFFER_COUNT: usize = 2;
+
+/// Number of PIO cycles per stereo sample, two per bit clock.
+const CYCLES_PER_SAMPLE: u32 = 64;
+
+/// Buffers of stereo samples, packed with the left channel in the upper half word.
+static mut SAMPLE_BUFFERS: [[u32; BUFFER_SIZE]; BUFFER_COUNT] = [[0; BUFFER_SIZE]; BUFFER_COUNT];
+
+/// Played when no samples are queued, to keep the I2S clocks running.
+static SILENCE: [u32; BUFFER_SIZE] = [0; BUFFER_SIZE];
+
+/// Indexes of buffers that are free to be filled.
+static FREE_BUFFERS: Channel<CriticalSectionRawMutex, usize, BUFFER_COUNT> = Channel::new();
+
+/// Indexes of buffers that are filled and waiting to be played.
+static FILLED_BUFFERS: Channel<CriticalSectionRawMutex, usize, BUFFER_COUNT> = Channel::new();
+
+bind_interrupts!(struct PioIrqs {
+    PIO1_IRQ_0 => pio::InterruptHandler<PIO1>;
+});
+
+pub struct UnicornAudio<'a> {
+    volume: u8,
+    muted: bool,
+    mute_pin: Output<'a, PIN_22>,
+    synth: Synth,
+}
+
+impl<'a> UnicornAudio<'a> {
+    /// Create a new audio instance, streaming samples to the speaker amplifier over I2S.
+    pub fn new(pio1: PIO1, audio_pins: UnicornAudioPins, dma: DMA_CH1) -> Self {
+        let Pio {
+            mut common,
+            sm0: mut sm,
+            ..
+        } = Pio::new(pio1, PioIrqs);
+
+        let data_pin = common.make_pio_pin(audio_pins.i2s_data);
+        let bclk_pin = common.make_pio_pin(audio_pins.i2s_bclk);
+        let lrclk_pin = common.make_pio_pin(audio_pins.i2s_lrclk);
+
+        let pio1_program = Self::build_pio_program();
+        let mut cfg = pio::Config::default();
+        cfg.use_program(
+            &common.load_program(&pio1_program),
+            &[&bclk_pin, &lrclk_pin],
+        );
+        cfg.set_out_pins(&[&data_pin]);
+        cfg.fifo_join = FifoJoin::TxOnly;
+        cfg.clock_divider = U24F8::from_bits(
+            ((clocks::clk_sys_freq() as u64 * 256) / (SAMPLE_RATE * CYCLES_PER_SAMPLE) as u64)
+                as u32,
+        );
+        cfg.shift_out = ShiftConfig {
+            auto_fill: true,
+            threshold: 32,
+            direction: ShiftDirection::Left,
+        };
+
+        let pio_pins = &[&data_pin, &bclk_pin, &lrclk_pin];
+
+        sm.set_config(&cfg);
+        sm.set_pins(Level::Low, pio_pins);
+        sm.set_pin_dirs(Direction::Out, pio_pins);
+
+        sm.set_enable(true);
+
+        for index in 0..BUFFER_COUNT {
+            FREE_BUFFERS.try_send(index).unwrap();
+        }
+
+        crate::interrupt_spawner()
+            .spawn(auto_play(sm, dma.into_ref()))
+            .unwrap();
+
+        Self {
+            volume: 127,
+            muted: false,
+            mute_pin: Output::new(audio_pins.mute, Level::High),
+            synth: Synth::new(SAMPLE_RATE),
+        }
+    }
+
+    fn build_pio_program() -> ::pio::Program<32_usize> {
+        pio_proc::pio_asm!(
+            "
+            .side_set 2
+
+            ; out pins:
+            ;
+            ; - 0: i2s data
+
+            ; sideset pins:
+            ;
+            ; - 0: i2s bit clock
+            ; - 1: i2s left right clock
+
+            ; each word pulled from the fifo holds one stereo sample:
+            ;
+            ;   llllllllllllllll rrrrrrrrrrrrrrrr  // left, right
+            ;
+            ; data changes on the falling edge of the bit clock, one bit before
+            ; the left right clock changes, as per the i2s standard
+
+            .wrap_target
+
+                set x, 14           side 0b11
+            left:
+                out pins, 1         side 0b10
+                jmp x-- left        side 0b11
+                out pins, 1         side 0b00
+                set x, 14           side 0b01
+            right:
+                out pins, 1         side 0b00
+                jmp x-- right       side 0b01
+                out pins, 1         side 0b10
+
+            .wrap
+            "
+        )
+        .program
+    }
+
+    /// Queue the signed 16 bit mono samples to be played, at the current volume.
+    /// Waits until there is space in the sample ring for all samples.
+    pub async fn play_sample(&mut self, samples: &[i16]) {
+        for chunk in samples.chunks(BUFFER_SIZE) {
+            let index = FREE_BUFFERS.receive().await;
+
+            // The buffer is not being read by the DMA until it is sent back as filled.
+            let buffer = unsafe { &mut SAMPLE_BUFFERS[index] };
+            for (i, frame) in buffer.iter_mut().enumerate() {
+                let sample = chunk.get(i).map_or(0, |&sample| self.apply_volume(sample));
+                let sample = sample as u16 as u32;
+                *frame = (sample << 16) | sample;
+            }
+
+            FILLED_BUFFERS.send(index).await;
+        }
+    }
+
+    /// Play a square wave tone of the given frequency on the first synthesizer channel.
+    /// The tone is held for the given duration, then released using the channel envelope.
+    pub async fn play_tone(&mut self, frequency: u16, duration: Duration) {
+        let channel = &mut self.synth.channels[0];
+        channel.waveforms = Waveform::SQUARE;
+        channel.frequency = frequency;
+
+        self.synth.trigger_attack(0);
+        self.play_synth(duration).await;
+
+        self.synth.trigger_release(0);
+        while self.synth.is_playing() {
+            self.play_synth_buffer(BUFFER_SIZE).await;
+        }
+    }
+
+    /// Play the output of the synthesizer for the given duration.
+    /// Channels should be configured and triggered first via [`Self::synth`].
+    pub async fn play_synth(&mut self, duration: Duration) {
+        let mut remaining = (duration.as_micros() * SAMPLE_RATE as u64 / 1_000_000) as usize;
+
+        while remaining > 0 {
+            let count = remaining.min(BUFFER_SIZE);
+            self.play_synth_buffer(count).await;
+            remaining -= count;
+        }
+    }
+
+    async fn play_synth_buffer(&mut self, count: usize) {
+        let mut samples = [0; BUFFER_SIZE];
+        self.synth.fill(&mut samples[..count]);
+        self.play_sample(&samples[..count]).await;
+    }
+
+    /// Stop all synthesizer channels immediately.
+    pub fn stop_playing(&mut self) {
+        self.synth.stop_all();
+    }
+
+    /// Get the synthesizer, to configure and trigger its channels.
+    pub fn synth(&mut self) -> &mut Synth {
+        &mut self.synth
+    }
+
+    /// Get the given channel of the synthesizer.
+    pub fn synth_channel(&mut self, channel: usize) -> &mut AudioChannel {
+        &mut self.synth.channels[channel]
+    }
+
+    /// Scale the sample by the current volume.
+    /// The volume follows a squared curve so each step sounds similar in size.
+    fn apply_volume(&self, sample: i16) -> i16 {
+        let gain = self.volume as i32 * self.volume as i32;
+        ((sample as i32 * gain) / (u8::MAX as i32 * u8::MAX as i32)) as i16
+    }
+
+    /// Get the current volume.
+    pub fn volume(&self) -> u8 {
+        self.volume
+    }
+
+    /// Increase volume by the given step.
+    pub fn increase_volume(&mut self, step: u8) {
+        self.volume = self.volume.saturating_add(step);
+    }
+
+    /// Decrease volume by the given step.
+    pub fn decrease_volume(&mut self, step: u8) {
+        self.volume = self.volume.saturating_sub(step);
+    }
+
+    /// Set the volume of the speaker to the given value.
+    pub fn set_volume(&mut self, volume: u8) {
+        self.volume = volume;
+    }
+
+    /// Adjust the volume by the given step when a volume button is pressed or repeating.
+    pub fn handle_button_event(&mut self, event: ButtonEvent, step: u8) {
+        if !matches!(
+            event.kind,
+            ButtonEventKind::Pressed | ButtonEventKind::Repeat
+        ) {
+            return;
+        }
+
+        match event.button {
+            UnicornButtons::VolumeUp => self.increase_volume(step),
+            UnicornButtons::VolumeDown => self.decrease_volume(step),
+            _ => {}
+        }
+    }
+
+    /// Mute the speaker by disabling the amplifier.
+    pub fn mute(&mut self) {
+        self.muted = true;
+        self.mute_pin.set_low();
+    }
+
+    /// Unmute the speaker by enabling the amplifier.
+    pub fn unmute(&mut self) {
+        self.muted = false;
+        self.mute_pin.set_high();
+    }
+
+    /// Toggle the speaker between muted and unmuted.
+    pub fn toggle_mute(&mut self) {
+        if self.muted {
+            self.unmute();
+        } else {
+            self.mute();
+        }
+    }
+
+    /// Check if the speaker is muted.
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
+}
+
+#[embassy_executor::task]
+async fn auto_play(
+    mut sm: StateMachine<'static, PIO1, 0>,
+    mut channel: PeripheralRef<'static, DMA_CH1>,
+) -> ! {
+    loop {
+        match FILLED_BUFFERS.try_receive() {
+            Ok(index) => {
+                let buffer = unsafe { &SAMPLE_BUFFERS[index] };
+                sm.tx().dma_push(channel.reborrow(), buffer).await;
+                FREE_BUFFERS.send(index).await;
+            }
+            Err(_) => {
+                sm.tx().dma_push(channel.reborrow(), &SILENCE).await;
+            }
+        }
+    }
+}
@@ -1,11 +1,91 @@
-pub enum UnicornButtons {
-    SwitchA,
-    SwitchB,
-    SwitchC,
-    SwitchD,
-    BrightnessUp,
-    BrightnessDown,
-    VolumeUp,
-    VolumeDown,
-    Sleep,
+use core::sync::atomic::{AtomicU16, Ordering};
+
+use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, channel::Channel};
+use embassy_time::{Duration, Instant, Ticker};
+
+use crate::pins::UnicornButtonPins;
+
+pub use unicorn_buttons::{
+    ButtonConfig, ButtonEvent, ButtonEventKind, ButtonStates, UnicornButtons,
+};
+
+/// Time between readings of the buttons.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Number of events that can be waiting to be received.
+const EVENT_QUEUE_SIZE: usize = 16;
+
+static BUTTON_EVENTS: Channel<CriticalSectionRawMutex, ButtonEvent, EVENT_QUEUE_SIZE> =
+    Channel::new();
+
+/// Debounced state of all buttons, with one bit per button index.
+static BUTTONS_HELD: AtomicU16 = AtomicU16::new(0);
+
+/// Debounced button events, read from the buttons in the background.
+pub struct UnicornButtonEvents {
+    _private: (),
+}
+
+impl UnicornButtonEvents {
+    /// Start reading the buttons in the background, sending events as they happen.
+    pub fn new(button_pins: UnicornButtonPins<'static>, config: ButtonConfig) -> Self {
+        crate::interrupt_spawner()
+            .spawn(poll_buttons(button_pins, config))
+            .unwrap();
+
+        Self { _private: () }
+    }
+
+    /// Wait for the next button event.
+    pub async fn next(&self) -> ButtonEvent {
+        BUTTON_EVENTS.receive().await
+    }
+
+    /// Get the next button event, if there is one waiting.
+    pub fn try_next(&self) -> Option<ButtonEvent> {
+        BUTTON_EVENTS.try_receive().ok()
+    }
+}
+
+/// Check if a button is pressed, as of the last reading by [`UnicornButtonEvents`].
+/// Always false if button events have not been started.
+pub fn is_button_held(button: UnicornButtons) -> bool {
+    BUTTONS_HELD.load(Ordering::Relaxed) & (1 << button.index()) != 0
+}
+
+/// Check if a button is being pressed, without debouncing.
+pub fn is_button_pressed(button_pins: &UnicornButtonPins, button: UnicornButtons) -> bool {
+    match button {
+        UnicornButtons::SwitchA => button_pins.switch_a.is_low(),
+        UnicornButtons::SwitchB => button_pins.switch_b.is_low(),
+        UnicornButtons::SwitchC => button_pins.switch_c.is_low(),
+        UnicornButtons::SwitchD => button_pins.switch_d.is_low(),
+        UnicornButtons::BrightnessUp => button_pins.brightness_up.is_low(),
+        UnicornButtons::BrightnessDown => button_pins.brightness_down.is_low(),
+        UnicornButtons::VolumeUp => button_pins.volume_up.is_low(),
+        UnicornButtons::VolumeDown => button_pins.volume_down.is_low(),
+        UnicornButtons::Sleep => button_pins.sleep.is_low(),
+    }
+}
+
+#[embassy_executor::task]
+async fn poll_buttons(button_pins: UnicornButtonPins<'static>, config: ButtonConfig) -> ! {
+    let mut states = ButtonStates::new(config);
+    let mut ticker = Ticker::every(POLL_INTERVAL);
+
+    loop {
+        let now = Instant::now().as_millis();
+        for event in states.update(now, |button| is_button_pressed(&button_pins, button)) {
+            // Drop events nobody is receiving rather than stalling the buttons.
+            let _ = BUTTON_EVENTS.try_send(event);
+        }
+
+        let held = UnicornButtons::ALL
+            .iter()
+            .filter(|button| states.is_pressed(**button))
+            .fold(0, |held, button| held | (1 << button.index()));
+        BUTTONS_HELD.store(held, Ordering::Relaxed);
+
+        ticker.next().await;
+    }
 }
@@ -1,13 +1,17 @@
 #![no_std]
 #![feature(type_alias_impl_trait)]
 
+use core::cell::Cell;
 use core::iter::Iterator;
 use core::option::Option::*;
+use core::ptr::{addr_of, addr_of_mut};
+use core::sync::atomic::{AtomicUsize, Ordering};
 
+use buttons::{ButtonEvent, ButtonEventKind, UnicornButtons};
 use cortex_m::prelude::{
     _embedded_hal_blocking_delay_DelayMs, _embedded_hal_blocking_delay_DelayUs,
 };
-use embassy_executor::InterruptExecutor;
+use embassy_executor::{InterruptExecutor, SendSpawner};
 use embassy_rp::{
     adc::{self, Adc, Async},
     bind_interrupts,
@@ -17,31 +21,42 @@ use embassy_rp::{
     pio::{self, Direction, FifoJoin, Pio, ShiftConfig, ShiftDirection, StateMachine},
     Peripheral, PeripheralRef,
 };
-use embedded_graphics_core::prelude::RgbColor;
+use embassy_sync::{
+    blocking_mutex::{raw::CriticalSectionRawMutex, Mutex},
+    signal::Signal,
+};
+use embedded_graphics_core::{
+    prelude::{Point, Size},
+    primitives::Rectangle,
+};
 use pins::{UnicornDisplayPins, UnicornSensorPins};
-use unicorn_graphics::UnicornGraphics;
+use unicorn_bitstream::{grow_area, BitstreamEncoder, DRIVER_CHIP_COUNT};
+use unicorn_brightness::{AutoBrightness, AutoBrightnessConfig};
+use unicorn_graphics::{diff_area, UnicornGraphics};
+
+pub use unicorn_bitstream::{gamma_table, BitstreamConfig, ColorCalibration, HEIGHT, WIDTH};
+pub use unicorn_display::UnicornDisplay;
 
+pub mod audio;
 pub mod buttons;
 pub mod pins;
 
-/// Width of the pimoroni galactic unicorn led matrix.
-pub const WIDTH: usize = 53;
-
-/// Height of the pimoroni galactic unicorn led matrix.
-pub const HEIGHT: usize = 11;
-
 pub const XOSC_CRYSTAL_FREQ: u32 = 12_000_000;
 
-const ROW_COUNT: usize = 11;
-const BCD_FRAME_COUNT: usize = 14;
-const BCD_FRAME_BYTES: usize = 60;
-const ROW_BYTES: usize = BCD_FRAME_COUNT * BCD_FRAME_BYTES;
-const BITSTREAM_LENGTH: usize = ROW_COUNT * ROW_BYTES;
+/// The front buffer is shown on the display while the back buffer is drawn to,
+/// so a frame is never shown part way through being drawn.
+static mut BITSTREAMS: [BitstreamEncoder; 2] = [BitstreamEncoder::new(), BitstreamEncoder::new()];
+
+/// Index of the buffer being shown on the display.
+/// Only changed by the draw task while holding the [`SWAP_PENDING`] lock.
+static FRONT_BUFFER: AtomicUsize = AtomicUsize::new(0);
 
-#[repr(C, align(4))]
-struct Bitstream([u8; BITSTREAM_LENGTH]);
+/// Set when the back buffer is ready to be shown.
+/// The draw task swaps the buffers at the end of the next refresh of the display.
+static SWAP_PENDING: Mutex<CriticalSectionRawMutex, Cell<bool>> = Mutex::new(Cell::new(false));
 
-static mut BITSTREAM: Bitstream = Bitstream([156; BITSTREAM_LENGTH]);
+/// Signalled by the draw task each time a refresh of the display completes.
+static VSYNC: Signal<CriticalSectionRawMutex, ()> = Signal::new();
 
 static INTERRUPT_EXECUTOR: InterruptExecutor = InterruptExecutor::new();
 
@@ -53,30 +68,78 @@ bind_interrupts!(struct AdcIrqs {
     ADC_IRQ_FIFO => adc::InterruptHandler;
 });
 
+static INTERRUPT_SPAWNER: Mutex<CriticalSectionRawMutex, Cell<Option<SendSpawner>>> =
+    Mutex::new(Cell::new(None));
+
 #[cortex_m_rt::interrupt]
 unsafe fn SWI_IRQ_1() {
     INTERRUPT_EXECUTOR.on_interrupt()
 }
 
+/// Get the spawner for the interrupt executor, starting it on first use.
+/// This executor runs tasks with higher priority than the normal tasks.
+fn interrupt_spawner() -> SendSpawner {
+    INTERRUPT_SPAWNER.lock(|spawner| match spawner.get() {
+        Some(spawner) => spawner,
+        None => {
+            interrupt::SWI_IRQ_1.set_priority(Priority::P2);
+            let started = INTERRUPT_EXECUTOR.start(interrupt::SWI_IRQ_1);
+            spawner.set(Some(started));
+            started
+        }
+    })
+}
+
+/// The cosmic unicorn uses the same pins and PIO program as the galactic unicorn,
+/// so is driven by the same type with the `cosmic` feature enabled.
+#[cfg(feature = "cosmic")]
+pub type CosmicUnicorn<'a> = GalacticUnicorn<'a>;
+
+/// The stellar unicorn uses the same pins and PIO program as the galactic unicorn,
+/// so is driven by the same type with the `stellar` feature enabled.
+#[cfg(feature = "stellar")]
+pub type StellarUnicorn<'a> = GalacticUnicorn<'a>;
+
 pub struct GalacticUnicorn<'a> {
     pub brightness: u8,
     light_sensor: adc::Channel<'a>,
     adc: Adc<'a, Async>,
+    auto_brightness: AutoBrightness,
+
+    /// If a swap has been queued since the back buffer was last written to.
+    swap_queued: bool,
+
+    /// Color calibration to apply to the back buffer the next time it is written to.
+    pending_calibration: Option<ColorCalibration>,
+
+    /// Brightness the pixels in the buffers were encoded at,
+    /// or `None` when every pixel needs encoding again, such as after a new calibration.
+    encoded_brightness: Option<u8>,
+
+    /// Pixels of the graphics last encoded, to find the pixels that differ in the next graphics.
+    drawn: UnicornGraphics<WIDTH, HEIGHT>,
+
+    /// Area of the back buffer written to since the buffers were last swapped,
+    /// which is all the other buffer needs copying once it becomes the back buffer.
+    written: Option<Rectangle>,
+
+    /// If the other buffer needs the calibration of the back buffer once it becomes the back buffer.
+    copy_calibration: bool,
 }
 
 impl<'a> GalacticUnicorn<'a> {
     /// Create a new galactic unicorn instance.
+    /// The config sets the color depth and refresh rate, use `BitstreamConfig::default()` for the full color depth.
     pub fn new(
         pio0: PIO0,
         display_pins: UnicornDisplayPins,
         sensor_pins: UnicornSensorPins,
         adc: ADC,
         dma: DMA_CH0,
+        config: BitstreamConfig,
     ) -> Self {
         let mut delay = embassy_time::Delay;
 
-        Self::init_bitstream();
-
         let mut column_clock_ref = PeripheralRef::new(display_pins.column_clock);
         let mut column_data_ref = PeripheralRef::new(display_pins.column_data);
         let mut column_latch_ref = PeripheralRef::new(display_pins.column_latch);
@@ -101,7 +164,8 @@ impl<'a> GalacticUnicorn<'a> {
 
         let reg1: u16 = 0b1111111111001110;
 
-        for _ in 0..9 {
+        // clock the register value to all but the last driver chip, which is latched below
+        for _ in 0..DRIVER_CHIP_COUNT - 1 {
             for i in 0..16 {
                 if reg1 & (1 << (15 - i)) != 0 {
                     column_data_pin.set_high();
@@ -198,12 +262,12 @@ impl<'a> GalacticUnicorn<'a> {
 
         sm.set_enable(true);
 
-        // Start the interupt executor. This executor runs tasks with higher priority than the normal tasks.
-        interrupt::SWI_IRQ_1.set_priority(Priority::P2);
-        let interrupt_spawner: embassy_executor::SendSpawner =
-            INTERRUPT_EXECUTOR.start(interrupt::SWI_IRQ_1);
+        // SAFETY: the draw task has not been started yet, so nothing else is using the buffers.
+        for bitstream in unsafe { &mut *addr_of_mut!(BITSTREAMS) } {
+            bitstream.set_config(config);
+        }
 
-        interrupt_spawner
+        interrupt_spawner()
             .spawn(auto_draw(sm, dma.into_ref()))
             .unwrap();
 
@@ -215,6 +279,13 @@ impl<'a> GalacticUnicorn<'a> {
             brightness: 255,
             light_sensor,
             adc,
+            auto_brightness: Self::disabled_auto_brightness(),
+            swap_queued: false,
+            pending_calibration: None,
+            encoded_brightness: None,
+            drawn: UnicornGraphics::new(),
+            written: None,
+            copy_calibration: false,
         }
     }
 
@@ -303,87 +374,123 @@ impl<'a> GalacticUnicorn<'a> {
         .program
     }
 
-    fn init_bitstream() {
-        // Iterate through rows and frames
-        for row in 0..HEIGHT {
-            for frame in 0..BCD_FRAME_COUNT {
-                // Calculate the offset in the bitstream array for the current row and frame
-                let offset = row * ROW_BYTES + (BCD_FRAME_BYTES * frame);
-
-                unsafe {
-                    // Set row pixel count and row select in the bitstream array
-                    BITSTREAM.0[offset] = (WIDTH - 1) as u8; // Row pixel count
-                    BITSTREAM.0[offset + 1] = row as u8; // Row select
-                }
-
-                // Calculate and set BCD ticks for the current frame
-                let bcd_ticks: u32 = 1 << frame;
+    /// Get the back buffer to write to.
+    ///
+    /// Any queued swap that has not happened yet is cancelled, so the buffers cannot be swapped
+    /// while the back buffer is being written to. If the swap has already happened,
+    /// the area of the new back buffer written to before the swap is brought up to date with the frame being shown.
+    fn back_buffer(&mut self) -> &mut BitstreamEncoder {
+        let cancelled = SWAP_PENDING.lock(|pending| pending.replace(false));
+        let front = FRONT_BUFFER.load(Ordering::Acquire);
+
+        // SAFETY: the draw task only reads the front buffer, and cannot swap the buffers
+        // while no swap is pending. Only this instance queues swaps.
+        let [first, second] = unsafe { &mut *addr_of_mut!(BITSTREAMS) };
+        let (front_buffer, back_buffer) = if front == 0 {
+            (first, second)
+        } else {
+            (second, first)
+        };
 
-                // Split 32-bit BCD ticks into 8-bit parts and store them in the bitstream array
-                unsafe {
-                    BITSTREAM.0[offset + 56] = ((bcd_ticks & 0xff) >> 0) as u8;
-                    BITSTREAM.0[offset + 57] = ((bcd_ticks & 0xff00) >> 8) as u8;
-                    BITSTREAM.0[offset + 58] = ((bcd_ticks & 0xff0000) >> 16) as u8;
-                    BITSTREAM.0[offset + 59] = ((bcd_ticks & 0xff000000) >> 24) as u8;
-                }
+        // only the area written to the other buffer since the last swap differs from it
+        if self.swap_queued && !cancelled {
+            if let Some(area) = self.written.take() {
+                back_buffer.copy_area_from(front_buffer, &area);
+            }
+            if core::mem::take(&mut self.copy_calibration) {
+                back_buffer.set_calibration(*front_buffer.calibration());
             }
         }
-    }
+        self.swap_queued = false;
 
-    /// Set the pixel at x, y with the color of r, g, b and the given brightness.
-    pub fn set_pixel_rgb(&mut self, x: u8, y: u8, r: u8, g: u8, b: u8, brightness: u8) {
-        let x = x as usize;
-        let y = y as usize;
-
-        if x >= WIDTH || y >= HEIGHT {
-            return;
+        // the front buffer gets the calibration once it becomes the back buffer
+        if let Some(calibration) = self.pending_calibration.take() {
+            back_buffer.set_calibration(calibration);
+            self.copy_calibration = true;
         }
 
-        // Make those coordinates sane
-        let x = WIDTH - 1 - x;
-        let y = HEIGHT - 1 - y;
+        back_buffer
+    }
 
-        let r = (r as u16 * brightness as u16) >> 8;
-        let g = (g as u16 * brightness as u16) >> 8;
-        let b = (b as u16 * brightness as u16) >> 8;
+    /// Queue the back buffer to be shown from the end of the current refresh of the display.
+    fn queue_swap(&mut self) {
+        SWAP_PENDING.lock(|pending| pending.set(true));
+        self.swap_queued = true;
+    }
 
-        let mut gamma_r = GAMMA_14BIT[r as usize];
-        let mut gamma_g = GAMMA_14BIT[g as usize];
-        let mut gamma_b = GAMMA_14BIT[b as usize];
+    /// Set the pixel at x, y with the color of r, g, b and the given brightness.
+    ///
+    /// The pixel is set in the back buffer, so is not shown until [`Self::present`] is called.
+    pub fn set_pixel_rgb(&mut self, x: u8, y: u8, r: u8, g: u8, b: u8, brightness: u8) {
+        self.back_buffer().set_pixel_rgb(x, y, r, g, b, brightness);
+        grow_area(
+            &mut self.written,
+            Rectangle::new(Point::new(x as i32, y as i32), Size::new(1, 1)),
+        );
+
+        // the pixel is not in the graphics, so the next call to `set_pixels` encodes them all again
+        self.encoded_brightness = None;
+    }
 
-        // Set the appropriate bits in the separate BCD frames
-        for frame in 0..BCD_FRAME_COUNT {
-            let offset = y * ROW_BYTES + (BCD_FRAME_BYTES * frame) + 2 + x;
+    /// Update the buffer of the display with the buffer from the unicorn graphics instance.
+    /// Only the area that differs from the graphics last passed is encoded,
+    /// so any graphics can be passed, such as the output of a compositor.
+    ///
+    /// The buffer is shown from the end of the current refresh of the display, without waiting for it.
+    /// Use [`Self::present`] instead to wait until it is shown.
+    pub fn set_pixels(&mut self, graphics: &UnicornGraphics<WIDTH, HEIGHT>) {
+        let brightness = self.brightness;
+
+        // a new brightness or calibration changes every pixel, so the whole frame is encoded again
+        let area = if self.encoded_brightness == Some(brightness) {
+            diff_area(&self.drawn, graphics)
+        } else {
+            Some(Rectangle::new(
+                Point::zero(),
+                Size::new(WIDTH as u32, HEIGHT as u32),
+            ))
+        };
+        self.encoded_brightness = Some(brightness);
+        self.drawn = *graphics;
 
-            let red_bit = gamma_r & 0b1;
-            let green_bit = gamma_g & 0b1;
-            let blue_bit = gamma_b & 0b1;
+        let back_buffer = self.back_buffer();
+        if let Some(area) = area {
+            back_buffer.set_pixels_in(graphics, brightness, &area);
+            grow_area(&mut self.written, area);
+        }
+        self.queue_swap();
+    }
 
-            unsafe {
-                BITSTREAM.0[offset] =
-                    (blue_bit << 0) as u8 | (green_bit << 1) as u8 | (red_bit << 2) as u8;
-            }
+    /// Show the back buffer on the display, waiting until the current refresh of the display
+    /// completes and the buffers have been swapped.
+    pub async fn present(&mut self) {
+        // bring the back buffer up to date first, in case an earlier swap has already happened
+        self.back_buffer();
+        self.queue_swap();
 
-            gamma_r >>= 1;
-            gamma_g >>= 1;
-            gamma_b >>= 1;
+        while SWAP_PENDING.lock(|pending| pending.get()) {
+            self.wait_for_vsync().await;
         }
     }
 
-    /// Update the entire buffer of the display with the buffer from the unicorn graphics instance.
-    pub fn set_pixels(&mut self, graphics: &UnicornGraphics<WIDTH, HEIGHT>) {
-        for (y, row) in graphics.get_pixels().iter().enumerate() {
-            for (x, color) in row.iter().enumerate() {
-                self.set_pixel_rgb(
-                    x as u8,
-                    y as u8,
-                    color.r(),
-                    color.g(),
-                    color.b(),
-                    self.brightness,
-                );
-            }
-        }
+    /// Wait until the next refresh of the display completes.
+    /// Drawing then presenting straight after keeps animations in step with the display.
+    pub async fn wait_for_vsync(&self) {
+        VSYNC.reset();
+        VSYNC.wait().await;
+    }
+
+    /// Set the color calibration, such as per channel gamma and white balance.
+    /// It is applied to pixels from the next call to [`Self::set_pixels`].
+    pub fn set_calibration(&mut self, calibration: ColorCalibration) {
+        self.pending_calibration = Some(calibration);
+        self.encoded_brightness = None;
+    }
+
+    /// Get the color depth and timing of the bitstream.
+    pub fn config(&self) -> BitstreamConfig {
+        // SAFETY: the config of the buffers is only changed in `new`.
+        unsafe { (*addr_of!(BITSTREAMS[0])).config() }
     }
 
     /// Increase brightness by the given step.
@@ -409,6 +516,100 @@ impl<'a> GalacticUnicorn<'a> {
             Err(_) => 0,
         }
     }
+
+    fn disabled_auto_brightness() -> AutoBrightness {
+        let mut auto_brightness = AutoBrightness::default();
+        auto_brightness.disable();
+        auto_brightness
+    }
+
+    /// Enable auto brightness with the given config.
+    /// Brightness is then set from the light sensor each time [`Self::update_auto_brightness`] is called.
+    pub fn enable_auto_brightness(&mut self, config: AutoBrightnessConfig) {
+        self.auto_brightness.config = config;
+        self.auto_brightness.enable();
+    }
+
+    /// Disable auto brightness, keeping the current brightness.
+    pub fn disable_auto_brightness(&mut self) {
+        self.auto_brightness.disable();
+    }
+
+    /// Toggle auto brightness between enabled and disabled.
+    pub fn toggle_auto_brightness(&mut self) {
+        self.auto_brightness.toggle();
+    }
+
+    /// Check if auto brightness is enabled.
+    pub fn is_auto_brightness_enabled(&self) -> bool {
+        self.auto_brightness.is_enabled()
+    }
+
+    /// Read the light sensor and update the brightness, if auto brightness is enabled.
+    /// This should be called periodically, for example every 100ms.
+    /// The new brightness is used from the next call to [`Self::set_pixels`].
+    pub async fn update_auto_brightness(&mut self) {
+        if !self.auto_brightness.is_enabled() {
+            return;
+        }
+
+        let light_level = self.get_light_level().await;
+        if let Some(brightness) = self.auto_brightness.update(light_level) {
+            self.brightness = brightness;
+        }
+    }
+
+    /// Handle an event from the brightness buttons.
+    /// A long press of either button toggles auto brightness,
+    /// otherwise presses adjust the brightness by the given step while auto brightness is disabled.
+    ///
+    /// Repeats are ignored, as they start before the long press and would change the brightness
+    /// while holding a button to toggle auto brightness.
+    pub fn handle_button_event(&mut self, event: ButtonEvent, step: u8) {
+        if !matches!(
+            event.button,
+            UnicornButtons::BrightnessUp | UnicornButtons::BrightnessDown
+        ) {
+            return;
+        }
+
+        match event.kind {
+            ButtonEventKind::LongPress => self.toggle_auto_brightness(),
+            ButtonEventKind::Pressed if !self.auto_brightness.is_enabled() => {
+                if event.button == UnicornButtons::BrightnessUp {
+                    self.increase_brightness(step);
+                } else {
+                    self.decrease_brightness(step);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'a> UnicornDisplay<WIDTH, HEIGHT> for GalacticUnicorn<'a> {
+    fn set_pixels(&mut self, graphics: &UnicornGraphics<WIDTH, HEIGHT>) {
+        GalacticUnicorn::set_pixels(self, graphics);
+    }
+
+    fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    fn set_brightness(&mut self, brightness: u8) {
+        GalacticUnicorn::set_brightness(self, brightness);
+    }
+
+    /// Reads the light sensor without waiting on the ADC interrupt.
+    fn light_level(&mut self) -> Option<u16> {
+        self.adc.blocking_read(&mut self.light_sensor).ok()
+    }
+
+    /// Uses the debounced state from [`buttons::UnicornButtonEvents`], which must be started,
+    /// as the display does not own the button pins. Always returns false until it is.
+    fn is_button_pressed(&mut self, button: UnicornButtons) -> bool {
+        buttons::is_button_held(button)
+    }
 }
 
 #[embassy_executor::task]
@@ -417,32 +618,18 @@ async fn auto_draw(
     mut channel: PeripheralRef<'static, DMA_CH0>,
 ) -> ! {
     loop {
-        let s32 = unsafe {
-            core::slice::from_raw_parts_mut(
-                BITSTREAM.0.as_mut_ptr() as *mut u32,
-                BITSTREAM_LENGTH / 4,
-            )
-        };
+        let front = FRONT_BUFFER.load(Ordering::Acquire);
+        let s32 = unsafe { BITSTREAMS[front].as_words() };
 
         sm.tx().dma_push(channel.reborrow(), s32).await;
+
+        // the whole display has been refreshed, so swap to the back buffer if it is ready
+        SWAP_PENDING.lock(|pending| {
+            if pending.replace(false) {
+                FRONT_BUFFER.store(1 - front, Ordering::Release);
+            }
+        });
+
+        VSYNC.signal(());
     }
 }
-
-static GAMMA_14BIT: [u16; 256] = [
-    0, 0, 0, 1, 2, 3, 4, 6, 8, 10, 13, 16, 20, 23, 28, 32, 37, 42, 48, 54, 61, 67, 75, 82, 90, 99,
-    108, 117, 127, 137, 148, 159, 170, 182, 195, 207, 221, 234, 249, 263, 278, 294, 310, 326, 343,
-    361, 379, 397, 416, 435, 455, 475, 496, 517, 539, 561, 583, 607, 630, 654, 679, 704, 730, 756,
-    783, 810, 838, 866, 894, 924, 953, 983, 1014, 1045, 1077, 1110, 1142, 1176, 1210, 1244, 1279,
-    1314, 1350, 1387, 1424, 1461, 1499, 1538, 1577, 1617, 1657, 1698, 1739, 1781, 1823, 1866, 1910,
-    1954, 1998, 2044, 2089, 2136, 2182, 2230, 2278, 2326, 2375, 2425, 2475, 2525, 2577, 2629, 2681,
-    2734, 2787, 2841, 2896, 2951, 3007, 3063, 3120, 3178, 3236, 3295, 3354, 3414, 3474, 3535, 3596,
-    3658, 3721, 3784, 3848, 3913, 3978, 4043, 4110, 4176, 4244, 4312, 4380, 4449, 4519, 4589, 4660,
-    4732, 4804, 4876, 4950, 5024, 5098, 5173, 5249, 5325, 5402, 5479, 5557, 5636, 5715, 5795, 5876,
-    5957, 6039, 6121, 6204, 6287, 6372, 6456, 6542, 6628, 6714, 6801, 6889, 6978, 7067, 7156, 7247,
-    7337, 7429, 7521, 7614, 7707, 7801, 7896, 7991, 8087, 8183, 8281, 8378, 8477, 8576, 8675, 8775,
-    8876, 8978, 9080, 9183, 9286, 9390, 9495, 9600, 9706, 9812, 9920, 10027, 10136, 10245, 10355,
-    10465, 10576, 10688, 10800, 10913, 11027, 11141, 11256, 11371, 11487, 11604, 11721, 11840,
-    11958, 12078, 12198, 12318, 12440, 12562, 12684, 12807, 12931, 13056, 13181, 13307, 13433,
-    13561, 13688, 13817, 13946, 14076, 14206, 14337, 14469, 14602, 14735, 14868, 15003, 15138,
-    15273, 15410, 15547, 15685, 15823, 15962, 16102, 16242, 16383,
-];
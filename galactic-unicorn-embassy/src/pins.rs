@@ -1,8 +1,8 @@
 use embassy_rp::{
     gpio::Input,
     peripherals::{
-        PIN_0, PIN_1, PIN_13, PIN_14, PIN_15, PIN_16, PIN_17, PIN_18, PIN_19, PIN_20, PIN_21,
-        PIN_26, PIN_27, PIN_28, PIN_3, PIN_6, PIN_7, PIN_8,
+        PIN_0, PIN_1, PIN_10, PIN_11, PIN_13, PIN_14, PIN_15, PIN_16, PIN_17, PIN_18, PIN_19,
+        PIN_20, PIN_21, PIN_22, PIN_26, PIN_27, PIN_28, PIN_3, PIN_6, PIN_7, PIN_8, PIN_9,
     },
 };
 
@@ -21,6 +21,13 @@ pub struct UnicornSensorPins {
     pub light_sensor: PIN_28,
 }
 
+pub struct UnicornAudioPins {
+    pub i2s_data: PIN_9,
+    pub i2s_bclk: PIN_10,
+    pub i2s_lrclk: PIN_11,
+    pub mute: PIN_22,
+}
+
 pub struct UnicornButtonPins<'d> {
     pub switch_a: Input<'d, PIN_0>,
     pub switch_b: Input<'d, PIN_1>,
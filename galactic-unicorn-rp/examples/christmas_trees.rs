@@ -24,9 +24,11 @@ use panic_halt as _;
 
 use unicorn_graphics::UnicornGraphics;
 
-use galatic_unicorn_rp::pins::{UnicornButtonPins, UnicornDisplayPins, UnicornPins};
+use galatic_unicorn_rp::pins::{
+    UnicornButtonPins, UnicornDisplayPins, UnicornPins, UnicornSensorPins,
+};
 use galatic_unicorn_rp::{self, GalacticUnicorn, XOSC_CRYSTAL_FREQ};
-use galatic_unicorn_rp::{buttons::UnicornButtons, HEIGHT, WIDTH};
+use galatic_unicorn_rp::{buttons::UnicornButtons, BitstreamConfig, HEIGHT, WIDTH};
 
 #[entry]
 fn main() -> ! {
@@ -78,6 +80,10 @@ fn main() -> ! {
             volume_down: pins.gpio8.into_pull_up_input(),
             sleep: pins.gpio27.into_pull_up_input(),
         },
+
+        sensor_pins: UnicornSensorPins {
+            light_sensor: pins.gpio28.into_floating_input(),
+        },
     };
 
     let dma = p.DMA.split(&mut p.RESETS);
@@ -87,11 +93,13 @@ fn main() -> ! {
         &mut p.RESETS,
         &mut delay,
         unipins,
-        (dma.ch0, dma.ch1, dma.ch2, dma.ch3),
+        p.ADC,
+        (dma.ch0, dma.ch1),
+        BitstreamConfig::default(),
     );
 
     let mut graphics = UnicornGraphics::<WIDTH, HEIGHT>::new();
-    gu.update(&graphics);
+    gu.update_and_draw(&graphics);
 
     let mut x: i32 = 0;
     let mut y: i32 = 0;
@@ -121,11 +129,11 @@ fn main() -> ! {
         x += 1;
         y += 1;
 
-        if y as usize > galatic_unicorn::WIDTH {
+        if y as usize > WIDTH {
             y = 0;
         }
 
-        if x as usize > galatic_unicorn::WIDTH {
+        if x as usize > WIDTH {
             x = 0;
         }
 
@@ -156,7 +164,7 @@ fn main() -> ! {
         draw_tree_alt(&mut tree_4, &mut graphics, ticks);
         draw_tree(&mut tree_5, &mut graphics, ticks);
 
-        gu.update(&graphics);
+        gu.update_and_draw(&graphics);
 
         if gu.is_button_pressed(UnicornButtons::BrightnessUp) {
             gu.increase_brightness(1);
@@ -25,9 +25,11 @@ use panic_halt as _;
 use unicorn_graphics::UnicornGraphics;
 
 use galatic_unicorn_rp::buttons::UnicornButtons;
-use galatic_unicorn_rp::pins::{UnicornButtonPins, UnicornDisplayPins, UnicornPins};
+use galatic_unicorn_rp::pins::{
+    UnicornButtonPins, UnicornDisplayPins, UnicornPins, UnicornSensorPins,
+};
 use galatic_unicorn_rp::{self, GalacticUnicorn, XOSC_CRYSTAL_FREQ};
-use galatic_unicorn_rp::{HEIGHT, WIDTH};
+use galatic_unicorn_rp::{BitstreamConfig, HEIGHT, WIDTH};
 
 #[entry]
 fn main() -> ! {
@@ -77,6 +79,10 @@ fn main() -> ! {
             volume_down: pins.gpio8.into_pull_up_input(),
             sleep: pins.gpio27.into_pull_up_input(),
         },
+
+        sensor_pins: UnicornSensorPins {
+            light_sensor: pins.gpio28.into_floating_input(),
+        },
     };
 
     let dma = p.DMA.split(&mut p.RESETS);
@@ -86,11 +92,13 @@ fn main() -> ! {
         &mut p.RESETS,
         &mut delay,
         unipins,
-        (dma.ch0, dma.ch1, dma.ch2, dma.ch3),
+        p.ADC,
+        (dma.ch0, dma.ch1),
+        BitstreamConfig::default(),
     );
 
     let mut graphics = UnicornGraphics::<WIDTH, HEIGHT>::new();
-    gu.update(&graphics);
+    gu.update_and_draw(&graphics);
 
     // keep track of scroll position
     let mut x: i32 = -53;
@@ -109,10 +117,10 @@ fn main() -> ! {
         }
 
         graphics.clear_all();
-        Text::new(message, Point::new((0 - x) as i32, 7), style)
+        Text::new(message, Point::new(-x, 7), style)
             .draw(&mut graphics)
             .unwrap();
-        gu.update(&graphics);
+        gu.update_and_draw(&graphics);
 
         if gu.is_button_pressed(UnicornButtons::BrightnessUp) {
             gu.increase_brightness(1);
@@ -12,24 +12,21 @@ use bsp::hal::{
     entry, pac, Sio, Watchdog,
 };
 use embedded_graphics::mono_font::{ascii::FONT_5X8, MonoTextStyle};
-use embedded_graphics::text::Text;
-use embedded_graphics::Drawable;
-use embedded_graphics_core::{
-    pixelcolor::{Rgb888, RgbColor},
-    prelude::Point,
-};
+use embedded_graphics_core::pixelcolor::{Rgb888, RgbColor};
 
 use rp_pico as bsp;
 
 use defmt_rtt as _;
 use panic_halt as _;
 
-use unicorn_graphics::UnicornGraphics;
+use unicorn_graphics::{Marquee, UnicornGraphics};
 
 use galatic_unicorn_rp::buttons::UnicornButtons;
-use galatic_unicorn_rp::pins::{UnicornButtonPins, UnicornDisplayPins, UnicornPins};
+use galatic_unicorn_rp::pins::{
+    UnicornButtonPins, UnicornDisplayPins, UnicornPins, UnicornSensorPins,
+};
 use galatic_unicorn_rp::{self, GalacticUnicorn, XOSC_CRYSTAL_FREQ};
-use galatic_unicorn_rp::{HEIGHT, WIDTH};
+use galatic_unicorn_rp::{BitstreamConfig, HEIGHT, WIDTH};
 
 #[entry]
 fn main() -> ! {
@@ -79,6 +76,10 @@ fn main() -> ! {
             volume_down: pins.gpio8.into_pull_up_input(),
             sleep: pins.gpio27.into_pull_up_input(),
         },
+
+        sensor_pins: UnicornSensorPins {
+            light_sensor: pins.gpio28.into_floating_input(),
+        },
     };
 
     let dma = p.DMA.split(&mut p.RESETS);
@@ -88,33 +89,26 @@ fn main() -> ! {
         &mut p.RESETS,
         &mut delay,
         unipins,
-        (dma.ch0, dma.ch1, dma.ch2, dma.ch3),
+        p.ADC,
+        (dma.ch0, dma.ch1),
+        BitstreamConfig::default(),
     );
 
     let mut graphics = UnicornGraphics::<WIDTH, HEIGHT>::new();
-    gu.update(&graphics);
-
-    // keep track of scroll position
-    let mut x: i32 = -53;
+    gu.update_and_draw(&graphics);
 
     // Create a new character style
     let style = MonoTextStyle::new(&FONT_5X8, Rgb888::WHITE);
-    let message = "Pirate. Monkey. Robot. Ninja.";
+    let mut marquee =
+        Marquee::<WIDTH, HEIGHT>::new("Pirate. Monkey. Robot. Ninja.", style).with_speed(100);
 
     loop {
         delay.delay_ms(10);
-
-        let width = message.len() * style.font.character_size.width as usize;
-        x += 1;
-        if x > width as i32 {
-            x = -53;
-        }
+        marquee.update(10);
 
         graphics.clear_all();
-        Text::new(message, Point::new((0 - x) as i32, 7), style)
-            .draw(&mut graphics)
-            .unwrap();
-        gu.update(&graphics);
+        marquee.draw(&mut graphics).unwrap();
+        gu.update_and_draw(&graphics);
 
         if gu.is_button_pressed(UnicornButtons::BrightnessUp) {
             gu.increase_brightness(1);
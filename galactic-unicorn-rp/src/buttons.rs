@@ -1,11 +1,3 @@
-pub enum UnicornButtons {
-    SwitchA,
-    SwitchB,
-    SwitchC,
-    SwitchD,
-    BrightnessUp,
-    BrightnessDown,
-    VolumeUp,
-    VolumeDown,
-    Sleep,
-}
+pub use unicorn_buttons::{
+    ButtonConfig, ButtonEvent, ButtonEventKind, ButtonEvents, ButtonStates, UnicornButtons,
+};
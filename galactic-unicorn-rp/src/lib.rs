@@ -1,70 +1,121 @@
 #![no_std]
 
 use core::iter::Iterator;
-use core::option::Option;
 use core::option::Option::*;
+use core::ptr::{addr_of, addr_of_mut};
+use core::sync::atomic::{AtomicU32, Ordering};
 
-use buttons::UnicornButtons;
+use buttons::{ButtonConfig, ButtonEvents, ButtonStates, UnicornButtons};
 use cortex_m::delay::Delay;
-use embedded_graphics_core::prelude::RgbColor;
-use pins::{UnicornButtonPins, UnicornPins};
+use pins::{UnicornButtonPins, UnicornPins, UnicornSensorPins};
 use rp_pico as bsp;
 
 use bsp::{
     hal::{
         self,
-        dma::{single_buffer, Channel, CH0, CH1, CH2, CH3},
-        gpio::{bank0::*, FunctionPio0, Pin, PinState, PullDown},
+        adc::{Adc, AdcPin},
+        dma::{Channel, SingleChannel, WriteTarget, CH0, CH1},
+        gpio::{bank0::*, FunctionPio0, FunctionSio, Pin, PinState, PullDown, PullNone, SioInput},
         pac::RESETS,
         pio::PIOExt,
     },
     pac,
 };
 
-use embedded_hal::digital::v2::{InputPin, OutputPin};
-use unicorn_graphics::UnicornGraphics;
+use embedded_graphics_core::{
+    prelude::{Point, Size},
+    primitives::Rectangle,
+};
+use embedded_hal::{
+    adc::OneShot,
+    digital::v2::{InputPin, OutputPin},
+};
+use unicorn_bitstream::{grow_area, BitstreamEncoder, DRIVER_CHIP_COUNT};
+use unicorn_graphics::{diff_area, UnicornGraphics};
+
+pub use unicorn_bitstream::{gamma_table, BitstreamConfig, ColorCalibration, HEIGHT, WIDTH};
+pub use unicorn_display::UnicornDisplay;
 
 pub mod buttons;
 pub mod pins;
 
-/// Width of the pimoroni galactic unicorn led matrix.
-pub const WIDTH: usize = 53;
+pub const XOSC_CRYSTAL_FREQ: u32 = 12_000_000;
 
-/// Height of the pimoroni galactic unicorn led matrix.
-pub const HEIGHT: usize = 11;
+/// The front buffer is shown on the display while the back buffer is drawn to,
+/// so a frame is never shown part way through being drawn.
+static mut BITSTREAMS: [BitstreamEncoder; 2] = [BitstreamEncoder::new(), BitstreamEncoder::new()];
 
-pub const XOSC_CRYSTAL_FREQ: u32 = 12_000_000;
+/// Address of the buffer to show on the display.
+/// Read by the control DMA channel at the end of every refresh, to restart the data channel.
+static FRAME_ADDRESS: AtomicU32 = AtomicU32::new(0);
 
-const ROW_COUNT: usize = 11;
-const BCD_FRAME_COUNT: usize = 14;
-const BCD_FRAME_BYTES: usize = 60;
-const ROW_BYTES: usize = BCD_FRAME_COUNT * BCD_FRAME_BYTES;
-const BITSTREAM_LENGTH: usize = ROW_COUNT * ROW_BYTES;
+/// Value of the DMA `TREQ_SEL` field for transfers that run as fast as possible.
+const TREQ_UNPACED: u8 = 0x3f;
 
-#[repr(C, align(4))]
-struct Bitstream([u8; BITSTREAM_LENGTH]);
+/// The cosmic unicorn uses the same pins and PIO program as the galactic unicorn,
+/// so is driven by the same type with the `cosmic` feature enabled.
+#[cfg(feature = "cosmic")]
+pub type CosmicUnicorn = GalacticUnicorn;
 
-static mut BITSTREAM: Bitstream = Bitstream([156; BITSTREAM_LENGTH]);
+/// The stellar unicorn uses the same pins and PIO program as the galactic unicorn,
+/// so is driven by the same type with the `stellar` feature enabled.
+#[cfg(feature = "stellar")]
+pub type StellarUnicorn = GalacticUnicorn;
 
 pub struct GalacticUnicorn {
-    tx: Option<hal::pio::Tx<(hal::pac::PIO0, hal::pio::SM0)>>,
-    channel: Option<Channel<CH0>>,
+    /// Held so the PIO TX FIFO is not used elsewhere while the DMA channels write to it.
+    _tx: hal::pio::Tx<(hal::pac::PIO0, hal::pio::SM0)>,
+
+    /// The data channel, feeding the bitstream to the PIO, and the control channel,
+    /// restarting the data channel each time it completes.
+    channels: (Channel<CH0>, Channel<CH1>),
+
+    /// Index of the buffer being shown, or about to be shown, on the display.
+    front: usize,
+
+    /// If the buffers have been swapped since the back buffer was last written to.
+    swapped: bool,
+
+    /// Color calibration to apply to the back buffer the next time it is written to.
+    pending_calibration: Option<ColorCalibration>,
+
+    /// Color depth and timing of the bitstream.
+    config: BitstreamConfig,
+
+    /// Brightness the pixels in the buffers were encoded at,
+    /// or `None` when every pixel needs encoding again, such as after a new calibration.
+    encoded_brightness: Option<u8>,
+
+    /// Pixels of the graphics last encoded, to find the pixels that differ in the next graphics.
+    drawn: UnicornGraphics<WIDTH, HEIGHT>,
+
+    /// Area of the back buffer written to since the buffers were last swapped,
+    /// which is all the other buffer needs copying once it becomes the back buffer.
+    written: Option<Rectangle>,
+
+    /// If the other buffer needs the calibration of the back buffer once it becomes the back buffer.
+    copy_calibration: bool,
+
     pins: UnicornButtonPins,
+    light_sensor: AdcPin<Pin<Gpio28, FunctionSio<SioInput>, PullNone>>,
+    adc: Adc,
+    button_states: ButtonStates,
     pub brightness: u8,
 }
 
 #[allow(dead_code)]
 impl GalacticUnicorn {
     /// Create a new galactic unicorn instance.
+    /// The config sets the color depth and refresh rate, use `BitstreamConfig::default()` for the full color depth.
     pub fn new(
         pio0: pac::PIO0,
-        mut resets: &mut RESETS,
+        resets: &mut RESETS,
         delay: &mut Delay,
         pins: UnicornPins,
-        dma: (Channel<CH0>, Channel<CH1>, Channel<CH2>, Channel<CH3>),
+        adc: pac::ADC,
+        dma: (Channel<CH0>, Channel<CH1>),
+        config: BitstreamConfig,
     ) -> Self {
-        Self::init_bitstream();
-
         let mut column_clock_pin = pins
             .display_pins
             .column_clock
@@ -103,7 +154,8 @@ impl GalacticUnicorn {
 
         let reg1: u16 = 0b1111111111001110;
 
-        for _ in 0..9 {
+        // clock the register value to all but the last driver chip, which is latched below
+        for _ in 0..DRIVER_CHIP_COUNT - 1 {
             for i in 0..16 {
                 if reg1 & (1 << (15 - i)) != 0 {
                     column_data_pin.set_high().unwrap();
@@ -156,9 +208,9 @@ impl GalacticUnicorn {
         let pio0_program = Self::build_pio_program();
 
         // Initialize and start PIO
-        let (mut pio, sm0, _, _, _) = pio0.split(&mut resets);
+        let (mut pio, sm0, _, _, _) = pio0.split(resets);
         let installed = pio.install(&pio0_program).unwrap();
-        let (mut sm, _, tx) = hal::pio::PIOBuilder::from_program(installed)
+        let (mut sm, _, mut tx) = hal::pio::PIOBuilder::from_program(installed)
             .buffers(bsp::hal::pio::Buffers::OnlyTx)
             .out_pins(row_bit_0_pin.id().num, 4)
             .set_pins(column_data_pin.id().num, 3)
@@ -192,14 +244,97 @@ impl GalacticUnicorn {
 
         sm.start();
 
+        // SAFETY: the DMA channels have not been started yet, so nothing else is using the buffers.
+        for bitstream in unsafe { &mut *addr_of_mut!(BITSTREAMS) } {
+            bitstream.set_config(config);
+        }
+
+        let UnicornSensorPins { light_sensor } = pins.sensor_pins;
+        let light_sensor = AdcPin::new(light_sensor);
+        let adc = Adc::new(adc, resets);
+
+        let (data_channel, control_channel) = dma;
+        let channels = Self::start_refresh(data_channel, control_channel, &mut tx, config);
+
         Self {
-            tx: Some(tx),
-            channel: Some(dma.0),
+            _tx: tx,
+            channels,
+            front: 0,
+            swapped: false,
+            pending_calibration: None,
+            encoded_brightness: None,
+            drawn: UnicornGraphics::new(),
+            written: None,
+            copy_calibration: false,
+            config,
             pins: pins.button_pins,
+            light_sensor,
+            adc,
+            button_states: ButtonStates::new(ButtonConfig::default()),
             brightness: 255,
         }
     }
 
+    /// Start refreshing the display continuously in the background, without using the CPU.
+    ///
+    /// The data channel pushes the whole bitstream to the PIO TX FIFO, then chains to the control channel.
+    /// The control channel writes [`FRAME_ADDRESS`] to the read address trigger of the data channel,
+    /// restarting it from the start of the buffer to show.
+    fn start_refresh(
+        data_channel: Channel<CH0>,
+        control_channel: Channel<CH1>,
+        tx: &mut hal::pio::Tx<(hal::pac::PIO0, hal::pio::SM0)>,
+        config: BitstreamConfig,
+    ) -> (Channel<CH0>, Channel<CH1>) {
+        FRAME_ADDRESS.store(buffer_address(0), Ordering::Release);
+
+        let (tx_address, _) = tx.tx_address_count();
+        let tx_treq = <hal::pio::Tx<(hal::pac::PIO0, hal::pio::SM0)> as WriteTarget>::tx_treq()
+            .unwrap_or(TREQ_UNPACED);
+
+        let data = data_channel.ch();
+        data.ch_read_addr
+            .write(|w| unsafe { w.bits(buffer_address(0)) });
+        data.ch_write_addr.write(|w| unsafe { w.bits(tx_address) });
+        data.ch_trans_count
+            .write(|w| unsafe { w.bits((config.bitstream_length() / 4) as u32) });
+        data.ch_al1_ctrl.write(|w| unsafe {
+            w.data_size().size_word();
+            w.incr_read().set_bit();
+            w.incr_write().clear_bit();
+            w.treq_sel().bits(tx_treq);
+            w.chain_to().bits(control_channel.id());
+            w.en().set_bit();
+            w
+        });
+
+        let control = control_channel.ch();
+        control
+            .ch_read_addr
+            .write(|w| unsafe { w.bits(FRAME_ADDRESS.as_ptr() as u32) });
+        control
+            .ch_write_addr
+            .write(|w| unsafe { w.bits(addr_of!(data.ch_al3_read_addr_trig) as u32) });
+        control.ch_trans_count.write(|w| unsafe { w.bits(1) });
+        control.ch_al1_ctrl.write(|w| unsafe {
+            w.data_size().size_word();
+            w.incr_read().clear_bit();
+            w.incr_write().clear_bit();
+            w.treq_sel().bits(TREQ_UNPACED);
+            // chaining to itself disables chaining
+            w.chain_to().bits(control_channel.id());
+            w.en().set_bit();
+            w
+        });
+
+        // the data channel is started by the control channel, and from then on they run forever
+        unsafe { &*pac::DMA::ptr() }
+            .multi_chan_trigger
+            .write(|w| unsafe { w.bits(1 << control_channel.id()) });
+
+        (data_channel, control_channel)
+    }
+
     fn build_pio_program() -> pio::Program<32_usize> {
         pio_proc::pio_asm!(
             "
@@ -285,111 +420,132 @@ impl GalacticUnicorn {
         .program
     }
 
-    fn init_bitstream() {
-        // Iterate through rows and frames
-        for row in 0..HEIGHT {
-            for frame in 0..BCD_FRAME_COUNT {
-                // Calculate the offset in the bitstream array for the current row and frame
-                let offset = row * ROW_BYTES + (BCD_FRAME_BYTES * frame);
-
-                unsafe {
-                    // Set row pixel count and row select in the bitstream array
-                    BITSTREAM.0[offset] = (WIDTH - 1) as u8; // Row pixel count
-                    BITSTREAM.0[offset + 1] = row as u8; // Row select
-                }
+    /// Check if the data channel is reading from the buffer at the given index.
+    /// The end of the buffer is included, as the channel is left there until it is restarted.
+    fn is_showing(&self, index: usize) -> bool {
+        let start = buffer_address(index);
+        let end = start + self.config.bitstream_length() as u32;
+        let read_address = self.channels.0.ch().ch_read_addr.read().bits();
 
-                // Calculate and set BCD ticks for the current frame
-                let bcd_ticks: u32 = 1 << frame;
-                // Split 32-bit BCD ticks into 8-bit parts and store them in the bitstream array
-                unsafe {
-                    BITSTREAM.0[offset + 56] = ((bcd_ticks & 0xff) >> 0) as u8;
-                    BITSTREAM.0[offset + 57] = ((bcd_ticks & 0xff00) >> 8) as u8;
-                    BITSTREAM.0[offset + 58] = ((bcd_ticks & 0xff0000) >> 16) as u8;
-                    BITSTREAM.0[offset + 59] = ((bcd_ticks & 0xff000000) >> 24) as u8;
-                }
-            }
-        }
+        (start..=end).contains(&read_address)
     }
 
-    /// Set the pixel at x, y with the color of r, g, b and the given brightness.
-    pub fn set_pixel_rgb(&mut self, x: u8, y: u8, r: u8, g: u8, b: u8, brightness: u8) {
-        let x = x as usize;
-        let y = y as usize;
+    /// Get the back buffer to write to.
+    ///
+    /// If the buffers have been swapped since it was last written to, this waits for the display
+    /// to finish with it, which is at most one refresh, then brings the area written to before the swap
+    /// up to date with the front buffer.
+    fn back_buffer(&mut self) -> &mut BitstreamEncoder {
+        let back = 1 - self.front;
+
+        if self.swapped {
+            while self.is_showing(back) {
+                core::hint::spin_loop();
+            }
 
-        if x >= WIDTH || y >= HEIGHT {
-            return;
+            // SAFETY: the back buffer is no longer read by the DMA channels,
+            // and the front buffer is only read by them.
+            let (back_buffer, front_buffer) = unsafe {
+                (
+                    &mut *addr_of_mut!(BITSTREAMS[back]),
+                    &*addr_of!(BITSTREAMS[self.front]),
+                )
+            };
+
+            // only the area written to the other buffer since the last swap differs from it
+            if let Some(area) = self.written.take() {
+                back_buffer.copy_area_from(front_buffer, &area);
+            }
+            if core::mem::take(&mut self.copy_calibration) {
+                back_buffer.set_calibration(*front_buffer.calibration());
+            }
+            self.swapped = false;
         }
 
-        // Make those coordinates sane
-        let x = WIDTH - 1 - x;
-        let y = HEIGHT - 1 - y;
-
-        let r = (r as u16 * brightness as u16) >> 8;
-        let g = (g as u16 * brightness as u16) >> 8;
-        let b = (b as u16 * brightness as u16) >> 8;
-
-        let mut gamma_r = GAMMA_14BIT[r as usize];
-        let mut gamma_g = GAMMA_14BIT[g as usize];
-        let mut gamma_b = GAMMA_14BIT[b as usize];
+        // SAFETY: the DMA channels only read from the back buffer once it is swapped to the front,
+        // and the returned reference borrows self, so cannot outlive a call to `draw`.
+        let back_buffer = unsafe { &mut *addr_of_mut!(BITSTREAMS[back]) };
 
-        // Set the appropriate bits in the separate BCD frames
-        for frame in 0..BCD_FRAME_COUNT {
-            let offset = y * ROW_BYTES + (BCD_FRAME_BYTES * frame) + 2 + x;
-
-            let red_bit = gamma_r & 0b1;
-            let green_bit = gamma_g & 0b1;
-            let blue_bit = gamma_b & 0b1;
+        // the front buffer gets the calibration once it becomes the back buffer
+        if let Some(calibration) = self.pending_calibration.take() {
+            back_buffer.set_calibration(calibration);
+            self.copy_calibration = true;
+        }
 
-            unsafe {
-                BITSTREAM.0[offset] =
-                    (blue_bit << 0) as u8 | (green_bit << 1) as u8 | (red_bit << 2) as u8;
-            }
+        back_buffer
+    }
 
-            gamma_r >>= 1;
-            gamma_g >>= 1;
-            gamma_b >>= 1;
-        }
+    /// Set the pixel at x, y with the color of r, g, b and the given brightness.
+    /// The pixel is shown from the next call to [`Self::draw`].
+    pub fn set_pixel_rgb(&mut self, x: u8, y: u8, r: u8, g: u8, b: u8, brightness: u8) {
+        self.back_buffer().set_pixel_rgb(x, y, r, g, b, brightness);
+        grow_area(
+            &mut self.written,
+            Rectangle::new(Point::new(x as i32, y as i32), Size::new(1, 1)),
+        );
+
+        // the pixel is not in the graphics, so the next call to `set_pixels` encodes them all again
+        self.encoded_brightness = None;
     }
 
-    /// Update the entire buffer of the display with the buffer from the unicorn graphics instance and draw it to the display.
+    /// Update the buffer of the display with the buffer from the unicorn graphics instance and draw it to the display.
+    /// See [`Self::set_pixels`] for how the graphics are encoded.
     pub fn update_and_draw(&mut self, graphics: &UnicornGraphics<WIDTH, HEIGHT>) {
         self.set_pixels(graphics);
         self.draw();
     }
 
-    /// Update the entire buffer of the display with the buffer from the unicorn graphics instance.
+    /// Update the buffer of the display with the buffer from the unicorn graphics instance.
+    /// Only the area that differs from the graphics last passed is encoded,
+    /// so any graphics can be passed, such as the output of a compositor.
+    /// The buffer is shown from the next call to [`Self::draw`].
     pub fn set_pixels(&mut self, graphics: &UnicornGraphics<WIDTH, HEIGHT>) {
-        for (y, row) in graphics.get_pixels().iter().enumerate() {
-            for (x, color) in row.iter().enumerate() {
-                self.set_pixel_rgb(
-                    x as u8,
-                    y as u8,
-                    color.r(),
-                    color.g(),
-                    color.b(),
-                    self.brightness,
-                );
-            }
+        let brightness = self.brightness;
+
+        // a new brightness or calibration changes every pixel, so the whole frame is encoded again
+        let area = if self.encoded_brightness == Some(brightness) {
+            diff_area(&self.drawn, graphics)
+        } else {
+            Some(Rectangle::new(
+                Point::zero(),
+                Size::new(WIDTH as u32, HEIGHT as u32),
+            ))
+        };
+        self.encoded_brightness = Some(brightness);
+        self.drawn = *graphics;
+
+        let back_buffer = self.back_buffer();
+        if let Some(area) = area {
+            back_buffer.set_pixels_in(graphics, brightness, &area);
+            grow_area(&mut self.written, area);
         }
     }
 
-    /// Draw the current buffer on the display.
+    /// Show the current buffer on the display, from the end of the current refresh.
+    ///
+    /// The display is refreshed continuously in the background,
+    /// so this only needs to be called when the buffer has changed.
     pub fn draw(&mut self) {
-        let s32 = unsafe {
-            core::slice::from_raw_parts_mut(
-                BITSTREAM.0.as_mut_ptr() as *mut u32,
-                BITSTREAM_LENGTH / 4,
-            )
-        };
+        // bring the back buffer up to date first, in case nothing has been written since the last swap
+        self.back_buffer();
 
-        if let Some(channel) = self.channel.take() {
-            if let Some(tx) = self.tx.take() {
-                let tx_transfer = single_buffer::Config::new(channel, s32, tx).start();
-                let (channel, _, to) = tx_transfer.wait();
-                self.tx.replace(to);
-                self.channel.replace(channel);
-            }
-        }
+        let back = 1 - self.front;
+        FRAME_ADDRESS.store(buffer_address(back), Ordering::Release);
+
+        self.front = back;
+        self.swapped = true;
+    }
+
+    /// Set the color calibration, such as per channel gamma and white balance.
+    /// It is applied to pixels from the next call to [`Self::set_pixels`].
+    pub fn set_calibration(&mut self, calibration: ColorCalibration) {
+        self.pending_calibration = Some(calibration);
+        self.encoded_brightness = None;
+    }
+
+    /// Get the color depth and timing of the bitstream.
+    pub fn config(&self) -> BitstreamConfig {
+        self.config
     }
 
     /// Increase brightness by the given step.
@@ -411,37 +567,79 @@ impl GalacticUnicorn {
         self.brightness = brightness;
     }
 
-    /// Check if a button is being pressed.
+    /// Get the current light level reading.
+    pub fn get_light_level(&mut self) -> u16 {
+        // the read is blocking and cannot fail for a fixed pin
+        self.adc.read(&mut self.light_sensor).unwrap_or_default()
+    }
+
+    /// Check if a button is being pressed, without debouncing.
     pub fn is_button_pressed(&mut self, button: UnicornButtons) -> bool {
-        match button {
-            UnicornButtons::SwitchA => self.pins.switch_a.is_low().unwrap(),
-            UnicornButtons::SwitchB => self.pins.switch_b.is_low().unwrap(),
-            UnicornButtons::SwitchC => self.pins.switch_c.is_low().unwrap(),
-            UnicornButtons::SwitchD => self.pins.switch_d.is_low().unwrap(),
-            UnicornButtons::BrightnessUp => self.pins.brightness_up.is_low().unwrap(),
-            UnicornButtons::BrightnessDown => self.pins.brightness_down.is_low().unwrap(),
-            UnicornButtons::VolumeUp => self.pins.volume_up.is_low().unwrap(),
-            UnicornButtons::VolumeDown => self.pins.volume_down.is_low().unwrap(),
-            UnicornButtons::Sleep => self.pins.sleep.is_low().unwrap(),
-        }
+        read_button(&self.pins, button)
+    }
+
+    /// Read all buttons at the given time in milliseconds, returning the debounced events.
+    /// Call this regularly from the main loop, e.g. with the timer counter.
+    pub fn poll_buttons(&mut self, now_ms: u64) -> ButtonEvents {
+        self.button_states
+            .update(now_ms, |button| read_button(&self.pins, button))
+    }
+
+    /// Check if a button is pressed, as of the last call to [`Self::poll_buttons`].
+    pub fn is_button_held(&self, button: UnicornButtons) -> bool {
+        self.button_states.is_pressed(button)
+    }
+
+    /// Set the timings used to debounce the buttons and generate events.
+    pub fn set_button_config(&mut self, config: ButtonConfig) {
+        self.button_states.config = config;
     }
 }
 
-static GAMMA_14BIT: [u16; 256] = [
-    0, 0, 0, 1, 2, 3, 4, 6, 8, 10, 13, 16, 20, 23, 28, 32, 37, 42, 48, 54, 61, 67, 75, 82, 90, 99,
-    108, 117, 127, 137, 148, 159, 170, 182, 195, 207, 221, 234, 249, 263, 278, 294, 310, 326, 343,
-    361, 379, 397, 416, 435, 455, 475, 496, 517, 539, 561, 583, 607, 630, 654, 679, 704, 730, 756,
-    783, 810, 838, 866, 894, 924, 953, 983, 1014, 1045, 1077, 1110, 1142, 1176, 1210, 1244, 1279,
-    1314, 1350, 1387, 1424, 1461, 1499, 1538, 1577, 1617, 1657, 1698, 1739, 1781, 1823, 1866, 1910,
-    1954, 1998, 2044, 2089, 2136, 2182, 2230, 2278, 2326, 2375, 2425, 2475, 2525, 2577, 2629, 2681,
-    2734, 2787, 2841, 2896, 2951, 3007, 3063, 3120, 3178, 3236, 3295, 3354, 3414, 3474, 3535, 3596,
-    3658, 3721, 3784, 3848, 3913, 3978, 4043, 4110, 4176, 4244, 4312, 4380, 4449, 4519, 4589, 4660,
-    4732, 4804, 4876, 4950, 5024, 5098, 5173, 5249, 5325, 5402, 5479, 5557, 5636, 5715, 5795, 5876,
-    5957, 6039, 6121, 6204, 6287, 6372, 6456, 6542, 6628, 6714, 6801, 6889, 6978, 7067, 7156, 7247,
-    7337, 7429, 7521, 7614, 7707, 7801, 7896, 7991, 8087, 8183, 8281, 8378, 8477, 8576, 8675, 8775,
-    8876, 8978, 9080, 9183, 9286, 9390, 9495, 9600, 9706, 9812, 9920, 10027, 10136, 10245, 10355,
-    10465, 10576, 10688, 10800, 10913, 11027, 11141, 11256, 11371, 11487, 11604, 11721, 11840,
-    11958, 12078, 12198, 12318, 12440, 12562, 12684, 12807, 12931, 13056, 13181, 13307, 13433,
-    13561, 13688, 13817, 13946, 14076, 14206, 14337, 14469, 14602, 14735, 14868, 15003, 15138,
-    15273, 15410, 15547, 15685, 15823, 15962, 16102, 16242, 16383,
-];
+impl UnicornDisplay<WIDTH, HEIGHT> for GalacticUnicorn {
+    /// Updates the buffer and draws it to the display.
+    fn set_pixels(&mut self, graphics: &UnicornGraphics<WIDTH, HEIGHT>) {
+        self.update_and_draw(graphics);
+    }
+
+    fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    fn set_brightness(&mut self, brightness: u8) {
+        GalacticUnicorn::set_brightness(self, brightness);
+    }
+
+    /// Keeps a brightness of at least 1, so the display is never turned off.
+    fn decrease_brightness(&mut self, step: u8) {
+        GalacticUnicorn::decrease_brightness(self, step);
+    }
+
+    fn light_level(&mut self) -> Option<u16> {
+        Some(self.get_light_level())
+    }
+
+    fn is_button_pressed(&mut self, button: UnicornButtons) -> bool {
+        GalacticUnicorn::is_button_pressed(self, button)
+    }
+}
+
+/// Get the address of the bitstream buffer at the given index.
+fn buffer_address(index: usize) -> u32 {
+    // SAFETY: only the address is taken, no reference to the buffer is created.
+    unsafe { addr_of!(BITSTREAMS[index]) as u32 }
+}
+
+fn read_button(pins: &UnicornButtonPins, button: UnicornButtons) -> bool {
+    match button {
+        UnicornButtons::SwitchA => pins.switch_a.is_low().unwrap(),
+        UnicornButtons::SwitchB => pins.switch_b.is_low().unwrap(),
+        UnicornButtons::SwitchC => pins.switch_c.is_low().unwrap(),
+        UnicornButtons::SwitchD => pins.switch_d.is_low().unwrap(),
+        UnicornButtons::BrightnessUp => pins.brightness_up.is_low().unwrap(),
+        UnicornButtons::BrightnessDown => pins.brightness_down.is_low().unwrap(),
+        UnicornButtons::VolumeUp => pins.volume_up.is_low().unwrap(),
+        UnicornButtons::VolumeDown => pins.volume_down.is_low().unwrap(),
+        UnicornButtons::Sleep => pins.sleep.is_low().unwrap(),
+    }
+}
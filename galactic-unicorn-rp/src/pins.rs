@@ -1,8 +1,11 @@
-use rp_pico::hal::gpio::{bank0::*, FunctionPio0, FunctionSio, Pin, PullDown, PullUp, SioInput};
+use rp_pico::hal::gpio::{
+    bank0::*, FunctionPio0, FunctionSio, Pin, PullDown, PullNone, PullUp, SioInput,
+};
 
 pub struct UnicornPins {
     pub display_pins: UnicornDisplayPins,
     pub button_pins: UnicornButtonPins,
+    pub sensor_pins: UnicornSensorPins,
 }
 
 pub struct UnicornDisplayPins {
@@ -16,6 +19,10 @@ pub struct UnicornDisplayPins {
     pub row_bit_3: Pin<Gpio20, FunctionPio0, PullDown>,
 }
 
+pub struct UnicornSensorPins {
+    pub light_sensor: Pin<Gpio28, FunctionSio<SioInput>, PullNone>,
+}
+
 pub struct UnicornButtonPins {
     pub switch_a: Pin<Gpio0, FunctionSio<SioInput>, PullUp>,
     pub switch_b: Pin<Gpio1, FunctionSio<SioInput>, PullUp>,
@@ -0,0 +1,144 @@
+use embedded_graphics_core::{pixelcolor::Rgb888, prelude::RgbColor};
+
+use crate::GAMMA_14BIT;
+
+/// Maximum value of a 14 bit BCD value.
+const BCD_MAX: u16 = (1 << 14) - 1;
+
+/// Color calibration applied to each pixel while it is encoded.
+///
+/// Each channel has its own gamma table and gain, so the color of the LEDs can be
+/// matched to each other, for example to stop dim colors looking blue.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ColorCalibration {
+    /// Gamma tables of the red, green and blue channels, mapping 8 bit color values to 14 bit BCD values.
+    pub gamma: [[u16; 256]; 3],
+
+    /// Gain of the red, green and blue channels, applied after gamma correction,
+    /// where 255 leaves the channel as is. Sets the white point of the display.
+    pub gain: [u8; 3],
+}
+
+impl ColorCalibration {
+    /// Create a new calibration using [`GAMMA_14BIT`] for all channels at full gain.
+    pub const fn new() -> Self {
+        Self {
+            gamma: [GAMMA_14BIT; 3],
+            gain: [u8::MAX; 3],
+        }
+    }
+
+    /// Set the gamma of each channel, generating a table for each.
+    /// A gamma of 1.0 is linear, and higher values give more range to dim colors.
+    pub fn with_gamma(mut self, r: f32, g: f32, b: f32) -> Self {
+        self.gamma = [gamma_table(r), gamma_table(g), gamma_table(b)];
+        self
+    }
+
+    /// Set custom gamma tables for each channel, mapping 8 bit color values to 14 bit BCD values.
+    /// Values above the 14 bit maximum are clamped when encoded.
+    pub fn with_gamma_tables(mut self, r: [u16; 256], g: [u16; 256], b: [u16; 256]) -> Self {
+        self.gamma = [r, g, b];
+        self
+    }
+
+    /// Set the gain of each channel, where 255 leaves the channel as is.
+    pub fn with_gain(mut self, r: u8, g: u8, b: u8) -> Self {
+        self.gain = [r, g, b];
+        self
+    }
+
+    /// Set the gain of each channel from the color full white should be shown as.
+    /// For example, `Rgb888::new(255, 230, 200)` gives a warmer white.
+    pub fn with_white_point(self, white: Rgb888) -> Self {
+        self.with_gain(white.r(), white.g(), white.b())
+    }
+
+    /// Get the calibrated 14 bit BCD values for the color.
+    pub fn apply(&self, r: u8, g: u8, b: u8) -> (u16, u16, u16) {
+        (
+            self.apply_channel(0, r),
+            self.apply_channel(1, g),
+            self.apply_channel(2, b),
+        )
+    }
+
+    fn apply_channel(&self, channel: usize, value: u8) -> u16 {
+        let value = self.gamma[channel][value as usize].min(BCD_MAX) as u32;
+        (value * self.gain[channel] as u32 / u8::MAX as u32) as u16
+    }
+}
+
+impl Default for ColorCalibration {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Generate a gamma table for the given gamma, mapping 8 bit color values to 14 bit BCD values.
+pub fn gamma_table(gamma: f32) -> [u16; 256] {
+    let mut table = [0; 256];
+
+    for (value, entry) in table.iter_mut().enumerate().skip(1) {
+        let linear = powf(value as f32 / 255.0, gamma);
+        *entry = (linear * BCD_MAX as f32 + 0.5) as u16;
+    }
+
+    table
+}
+
+/// Raise a value between 0 and 1 to the given power, without needing the standard library.
+fn powf(base: f32, exponent: f32) -> f32 {
+    if base <= 0.0 {
+        return 0.0;
+    }
+
+    exp2(exponent * log2(base))
+}
+
+/// Base 2 logarithm of a positive value.
+fn log2(value: f32) -> f32 {
+    let bits = value.to_bits();
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127;
+
+    // the mantissa as a value between 1 and 2, whose natural log is found with the series
+    // ln(m) = 2 * (y + y^3 / 3 + y^5 / 5 + ...) where y = (m - 1) / (m + 1)
+    let mantissa = f32::from_bits((bits & 0x007f_ffff) | 0x3f80_0000);
+    let y = (mantissa - 1.0) / (mantissa + 1.0);
+    let y2 = y * y;
+
+    let mut term = y;
+    let mut ln = 0.0;
+    for n in [1.0, 3.0, 5.0, 7.0, 9.0, 11.0] {
+        ln += term / n;
+        term *= y2;
+    }
+
+    exponent as f32 + 2.0 * ln * core::f32::consts::LOG2_E
+}
+
+/// Base 2 exponential of a value.
+fn exp2(value: f32) -> f32 {
+    if value < -126.0 {
+        return 0.0;
+    }
+    if value >= 128.0 {
+        return f32::INFINITY;
+    }
+
+    let mut whole = value as i32;
+    if whole as f32 > value {
+        whole -= 1;
+    }
+
+    // 2^fraction = e^(fraction * ln 2), with the fraction between 0 and 1
+    let x = (value - whole as f32) * core::f32::consts::LN_2;
+    let mut term = 1.0;
+    let mut fraction = 1.0;
+    for n in 1..10 {
+        term *= x / n as f32;
+        fraction += term;
+    }
+
+    fraction * f32::from_bits(((whole + 127) as u32) << 23)
+}
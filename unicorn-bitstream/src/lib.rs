@@ -0,0 +1,749 @@
+#![no_std]
+
+use embedded_graphics_core::{
+    prelude::{Point, PointsIter, RgbColor, Size},
+    primitives::Rectangle,
+};
+use unicorn_graphics::UnicornGraphics;
+
+mod calibration;
+
+pub use calibration::{gamma_table, ColorCalibration};
+
+#[cfg(not(any(feature = "galactic", feature = "cosmic", feature = "stellar")))]
+compile_error!("one of the `galactic`, `cosmic` or `stellar` features must be enabled");
+
+#[cfg(any(
+    all(feature = "galactic", feature = "cosmic"),
+    all(feature = "galactic", feature = "stellar"),
+    all(feature = "cosmic", feature = "stellar"),
+))]
+compile_error!("only one of the `galactic`, `cosmic` or `stellar` features can be enabled, disable default features to select `cosmic` or `stellar`");
+
+/// Width of the pimoroni galactic unicorn led matrix.
+#[cfg(feature = "galactic")]
+pub const WIDTH: usize = 53;
+
+/// Height of the pimoroni galactic unicorn led matrix.
+#[cfg(feature = "galactic")]
+pub const HEIGHT: usize = 11;
+
+/// Number of rows scanned by the PIO program.
+#[cfg(feature = "galactic")]
+pub const ROW_COUNT: usize = 11;
+
+/// Number of column driver chips chained together.
+#[cfg(feature = "galactic")]
+pub const DRIVER_CHIP_COUNT: usize = 10;
+
+/// Width of the pimoroni cosmic unicorn led matrix.
+#[cfg(feature = "cosmic")]
+pub const WIDTH: usize = 32;
+
+/// Height of the pimoroni cosmic unicorn led matrix.
+#[cfg(feature = "cosmic")]
+pub const HEIGHT: usize = 32;
+
+/// Number of rows scanned by the PIO program.
+/// Two rows of the matrix are driven at once, as one row twice the width.
+#[cfg(feature = "cosmic")]
+pub const ROW_COUNT: usize = 16;
+
+/// Number of column driver chips chained together.
+#[cfg(feature = "cosmic")]
+pub const DRIVER_CHIP_COUNT: usize = 12;
+
+/// Width of the pimoroni stellar unicorn led matrix.
+#[cfg(feature = "stellar")]
+pub const WIDTH: usize = 16;
+
+/// Height of the pimoroni stellar unicorn led matrix.
+#[cfg(feature = "stellar")]
+pub const HEIGHT: usize = 16;
+
+/// Number of rows scanned by the PIO program.
+/// Two rows of the matrix are driven at once, as one row twice the width.
+#[cfg(feature = "stellar")]
+pub const ROW_COUNT: usize = 8;
+
+/// Number of column driver chips chained together.
+#[cfg(feature = "stellar")]
+pub const DRIVER_CHIP_COUNT: usize = 6;
+
+/// Number of pixels clocked out for each scanned row.
+pub const ROW_PIXELS: usize = WIDTH * (HEIGHT / ROW_COUNT);
+
+/// Maximum number of binary coded decimal frames per row, one for each bit of color depth.
+pub const BCD_FRAME_COUNT: usize = 14;
+
+/// Minimum number of binary coded decimal frames per row.
+pub const MIN_BCD_FRAME_COUNT: usize = 8;
+
+/// Offset of the first pixel within a BCD frame.
+pub const PIXEL_OFFSET: usize = 2;
+
+/// Number of bytes in a single BCD frame.
+///
+/// Each frame is laid out as below, where the galactic unicorn has 53 pixels per row
+/// and is 60 bytes long:
+///
+/// ```text
+///      0: 00110100                           // row pixel count (minus one)
+///      1: xxxxrrrr                           // row select bits
+///  2 - 54: xxxxxbgr, xxxxxbgr, xxxxxbgr, ... // pixel data
+///     55: xxxxxxxx                           // dummy bytes to dword align
+/// 56 - 59: tttttttt, tttttttt, tttttttt, ... // bcd tick count
+/// ```
+pub const BCD_FRAME_BYTES: usize = (PIXEL_OFFSET + ROW_PIXELS).next_multiple_of(4) + 4;
+
+/// Number of bytes for all BCD frames of a single row, at the maximum color depth.
+pub const ROW_BYTES: usize = BCD_FRAME_COUNT * BCD_FRAME_BYTES;
+
+/// Total number of bytes in the bitstream, at the maximum color depth.
+pub const BITSTREAM_LENGTH: usize = ROW_COUNT * ROW_BYTES;
+
+/// Offset of the tick count within a BCD frame.
+const TICKS_OFFSET: usize = BCD_FRAME_BYTES - 4;
+
+/// Get the column and scanned row in the bitstream of the pixel at x, y on the display.
+///
+/// The display is mounted upside down, so both axes are flipped. On boards with more rows
+/// than are scanned, the lower half of the display follows the upper half in the same row.
+pub const fn scan_position(x: usize, y: usize) -> (usize, usize) {
+    let x = WIDTH - 1 - x;
+    let y = HEIGHT - 1 - y;
+
+    if HEIGHT == ROW_COUNT {
+        (x, y)
+    } else if y < ROW_COUNT {
+        (x + WIDTH, y)
+    } else {
+        (x, y - ROW_COUNT)
+    }
+}
+
+/// Grow the area to also hold the other area, such as to track the area of a bitstream written to.
+/// An empty other area leaves it as it is.
+pub fn grow_area(area: &mut Option<Rectangle>, other: Rectangle) {
+    let Some(other_bottom_right) = other.bottom_right() else {
+        return;
+    };
+
+    *area = match area.and_then(|area| Some((area.top_left, area.bottom_right()?))) {
+        Some((top_left, bottom_right)) => Some(Rectangle::with_corners(
+            top_left.component_min(other.top_left),
+            bottom_right.component_max(other_bottom_right),
+        )),
+        None => Some(other),
+    };
+}
+
+/// Color depth and timing of the bitstream.
+///
+/// Each row is shown once per BCD frame, with each frame shown for twice as long as the one before.
+/// Fewer frames give less color depth, particularly in dark colors, but a higher refresh rate
+/// and less data for the DMA to move. A longer base tick length gives the inverse.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct BitstreamConfig {
+    bcd_frame_count: usize,
+    base_ticks: u32,
+}
+
+impl BitstreamConfig {
+    /// Create a new config with the given number of BCD frames, which is the color depth in bits,
+    /// and the number of PIO delay loops the first frame is shown for.
+    ///
+    /// The frame count is clamped between [`MIN_BCD_FRAME_COUNT`] and [`BCD_FRAME_COUNT`],
+    /// and the base ticks are at least 1.
+    pub const fn new(bcd_frame_count: usize, base_ticks: u32) -> Self {
+        let bcd_frame_count = if bcd_frame_count < MIN_BCD_FRAME_COUNT {
+            MIN_BCD_FRAME_COUNT
+        } else if bcd_frame_count > BCD_FRAME_COUNT {
+            BCD_FRAME_COUNT
+        } else {
+            bcd_frame_count
+        };
+
+        let base_ticks = if base_ticks == 0 { 1 } else { base_ticks };
+
+        Self {
+            bcd_frame_count,
+            base_ticks,
+        }
+    }
+
+    /// Number of BCD frames per row, which is the color depth in bits.
+    pub const fn bcd_frame_count(&self) -> usize {
+        self.bcd_frame_count
+    }
+
+    /// Number of PIO delay loops the first BCD frame is shown for.
+    pub const fn base_ticks(&self) -> u32 {
+        self.base_ticks
+    }
+
+    /// Number of PIO delay loops the given BCD frame is shown for.
+    pub const fn frame_ticks(&self, frame: usize) -> u32 {
+        self.base_ticks.saturating_mul(1 << frame)
+    }
+
+    /// Number of bytes for all BCD frames of a single row.
+    pub const fn row_bytes(&self) -> usize {
+        self.bcd_frame_count * BCD_FRAME_BYTES
+    }
+
+    /// Number of bytes of the bitstream sent to the display.
+    pub const fn bitstream_length(&self) -> usize {
+        ROW_COUNT * self.row_bytes()
+    }
+}
+
+impl Default for BitstreamConfig {
+    /// 14 bit color depth with a base tick length of 1.
+    fn default() -> Self {
+        Self::new(BCD_FRAME_COUNT, 1)
+    }
+}
+
+/// Encodes pixels into the bitstream consumed by the unicorn PIO program.
+///
+/// The bitstream is word aligned so it can be handed straight to a DMA channel via [`Self::as_words`].
+/// Space is always kept for the maximum color depth, but only the part used by the config is sent.
+#[repr(C, align(4))]
+pub struct BitstreamEncoder {
+    bitstream: [u8; BITSTREAM_LENGTH],
+    config: BitstreamConfig,
+    calibration: ColorCalibration,
+}
+
+impl BitstreamEncoder {
+    /// Create a new encoder at the maximum color depth, with the row headers and BCD tick counts populated.
+    pub const fn new() -> Self {
+        Self::with_config(BitstreamConfig::new(BCD_FRAME_COUNT, 1))
+    }
+
+    /// Create a new encoder with the given config, with the row headers and BCD tick counts populated.
+    pub const fn with_config(config: BitstreamConfig) -> Self {
+        let mut bitstream = [156; BITSTREAM_LENGTH];
+        let row_bytes = config.row_bytes();
+
+        // Iterate through rows and frames
+        let mut row = 0;
+        while row < ROW_COUNT {
+            let mut frame = 0;
+            while frame < config.bcd_frame_count {
+                // Calculate the offset in the bitstream array for the current row and frame
+                let offset = row * row_bytes + (BCD_FRAME_BYTES * frame);
+
+                // Set row pixel count and row select in the bitstream array
+                bitstream[offset] = (ROW_PIXELS - 1) as u8; // Row pixel count
+                bitstream[offset + 1] = row as u8; // Row select
+
+                // Calculate and set BCD ticks for the current frame
+                let bcd_ticks: u32 = config.frame_ticks(frame);
+
+                // Split 32-bit BCD ticks into 8-bit parts and store them in the bitstream array
+                let ticks = bcd_ticks.to_le_bytes();
+                bitstream[offset + TICKS_OFFSET] = ticks[0];
+                bitstream[offset + TICKS_OFFSET + 1] = ticks[1];
+                bitstream[offset + TICKS_OFFSET + 2] = ticks[2];
+                bitstream[offset + TICKS_OFFSET + 3] = ticks[3];
+
+                frame += 1;
+            }
+            row += 1;
+        }
+
+        Self {
+            bitstream,
+            config,
+            calibration: ColorCalibration::new(),
+        }
+    }
+
+    /// Get the config of the bitstream.
+    pub fn config(&self) -> BitstreamConfig {
+        self.config
+    }
+
+    /// Change the config of the bitstream, which clears all pixels.
+    pub fn set_config(&mut self, config: BitstreamConfig) {
+        let calibration = self.calibration;
+        *self = Self::with_config(config);
+        self.calibration = calibration;
+    }
+
+    /// Get the color calibration applied to pixels as they are encoded.
+    pub fn calibration(&self) -> &ColorCalibration {
+        &self.calibration
+    }
+
+    /// Set the color calibration applied to pixels as they are encoded.
+    /// Pixels already encoded are not changed.
+    pub fn set_calibration(&mut self, calibration: ColorCalibration) {
+        self.calibration = calibration;
+    }
+
+    /// Set the pixel at x, y with the color of r, g, b and the given brightness.
+    pub fn set_pixel_rgb(&mut self, x: u8, y: u8, r: u8, g: u8, b: u8, brightness: u8) {
+        let x = x as usize;
+        let y = y as usize;
+
+        if x >= WIDTH || y >= HEIGHT {
+            return;
+        }
+
+        // Make those coordinates sane
+        let (x, y) = scan_position(x, y);
+
+        let r = (r as u16 * brightness as u16) >> 8;
+        let g = (g as u16 * brightness as u16) >> 8;
+        let b = (b as u16 * brightness as u16) >> 8;
+
+        // Drop the least significant bits when the color depth is lower
+        let shift = BCD_FRAME_COUNT - self.config.bcd_frame_count;
+        let (gamma_r, gamma_g, gamma_b) = self.calibration.apply(r as u8, g as u8, b as u8);
+        let mut gamma_r = gamma_r >> shift;
+        let mut gamma_g = gamma_g >> shift;
+        let mut gamma_b = gamma_b >> shift;
+
+        let row_bytes = self.config.row_bytes();
+
+        // Set the appropriate bits in the separate BCD frames
+        for frame in 0..self.config.bcd_frame_count {
+            let offset = y * row_bytes + (BCD_FRAME_BYTES * frame) + PIXEL_OFFSET + x;
+
+            let red_bit = gamma_r & 0b1;
+            let green_bit = gamma_g & 0b1;
+            let blue_bit = gamma_b & 0b1;
+
+            self.bitstream[offset] = blue_bit as u8 | (green_bit << 1) as u8 | (red_bit << 2) as u8;
+
+            gamma_r >>= 1;
+            gamma_g >>= 1;
+            gamma_b >>= 1;
+        }
+    }
+
+    /// Encode the entire buffer from the unicorn graphics instance at the given brightness.
+    pub fn set_pixels(&mut self, graphics: &UnicornGraphics<WIDTH, HEIGHT>, brightness: u8) {
+        for (y, row) in graphics.get_pixels().iter().enumerate() {
+            for (x, color) in row.iter().enumerate() {
+                self.set_pixel_rgb(
+                    x as u8,
+                    y as u8,
+                    color.r(),
+                    color.g(),
+                    color.b(),
+                    brightness,
+                );
+            }
+        }
+    }
+
+    /// Encode the pixels of the unicorn graphics instance within the area at the given brightness,
+    /// such as its dirty area. Pixels outside of the area are left as they are.
+    pub fn set_pixels_in(
+        &mut self,
+        graphics: &UnicornGraphics<WIDTH, HEIGHT>,
+        brightness: u8,
+        area: &Rectangle,
+    ) {
+        for point in area.points() {
+            if let Some(color) = graphics.get_item(point) {
+                self.set_pixel_rgb(
+                    point.x as u8,
+                    point.y as u8,
+                    color.r(),
+                    color.g(),
+                    color.b(),
+                    brightness,
+                );
+            }
+        }
+    }
+
+    /// Copy the encoded bitstream and color calibration of another encoder into this one.
+    pub fn copy_from(&mut self, other: &BitstreamEncoder) {
+        self.bitstream.copy_from_slice(&other.bitstream);
+        self.calibration = other.calibration;
+    }
+
+    /// Copy the encoded pixels within the area of another encoder into this one,
+    /// which is much cheaper than [`Self::copy_from`] for a small area.
+    ///
+    /// Both encoders must have the same config. The color calibration is not copied.
+    pub fn copy_area_from(&mut self, other: &BitstreamEncoder, area: &Rectangle) {
+        let display = Rectangle::new(Point::zero(), Size::new(WIDTH as u32, HEIGHT as u32));
+        let area = area.intersection(&display);
+        let Some(bottom_right) = area.bottom_right() else {
+            return;
+        };
+
+        let row_bytes = self.config.row_bytes();
+        let len = area.size.width as usize;
+
+        for y in area.top_left.y..=bottom_right.y {
+            // the display is flipped, so the right of the area comes first in the bitstream
+            let (column, row) = scan_position(bottom_right.x as usize, y as usize);
+
+            for frame in 0..self.config.bcd_frame_count {
+                let start = row * row_bytes + BCD_FRAME_BYTES * frame + PIXEL_OFFSET + column;
+                let range = start..start + len;
+                self.bitstream[range.clone()].copy_from_slice(&other.bitstream[range]);
+            }
+        }
+    }
+
+    /// Get the encoded bitstream, as long as the config needs.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bitstream[..self.config.bitstream_length()]
+    }
+
+    /// Get the encoded bitstream as words, ready to be pushed to the PIO TX FIFO.
+    pub fn as_words(&self) -> &[u32] {
+        // SAFETY: the struct is `repr(C, align(4))` so the bitstream starts on a word boundary,
+        // and the length is a whole number of BCD frames, which are a multiple of 4 bytes.
+        unsafe {
+            core::slice::from_raw_parts(
+                self.bitstream.as_ptr() as *const u32,
+                self.config.bitstream_length() / 4,
+            )
+        }
+    }
+}
+
+impl Default for BitstreamEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Gamma correction table mapping 8 bit color values to 14 bit BCD values, with a gamma of 2.2.
+pub const GAMMA_14BIT: [u16; 256] = [
+    0, 0, 0, 1, 2, 3, 4, 6, 8, 10, 13, 16, 20, 23, 28, 32, 37, 42, 48, 54, 61, 67, 75, 82, 90, 99,
+    108, 117, 127, 137, 148, 159, 170, 182, 195, 207, 221, 234, 249, 263, 278, 294, 310, 326, 343,
+    361, 379, 397, 416, 435, 455, 475, 496, 517, 539, 561, 583, 607, 630, 654, 679, 704, 730, 756,
+    783, 810, 838, 866, 894, 924, 953, 983, 1014, 1045, 1077, 1110, 1142, 1176, 1210, 1244, 1279,
+    1314, 1350, 1387, 1424, 1461, 1499, 1538, 1577, 1617, 1657, 1698, 1739, 1781, 1823, 1866, 1910,
+    1954, 1998, 2044, 2089, 2136, 2182, 2230, 2278, 2326, 2375, 2425, 2475, 2525, 2577, 2629, 2681,
+    2734, 2787, 2841, 2896, 2951, 3007, 3063, 3120, 3178, 3236, 3295, 3354, 3414, 3474, 3535, 3596,
+    3658, 3721, 3784, 3848, 3913, 3978, 4043, 4110, 4176, 4244, 4312, 4380, 4449, 4519, 4589, 4660,
+    4732, 4804, 4876, 4950, 5024, 5098, 5173, 5249, 5325, 5402, 5479, 5557, 5636, 5715, 5795, 5876,
+    5957, 6039, 6121, 6204, 6287, 6372, 6456, 6542, 6628, 6714, 6801, 6889, 6978, 7067, 7156, 7247,
+    7337, 7429, 7521, 7614, 7707, 7801, 7896, 7991, 8087, 8183, 8281, 8378, 8477, 8576, 8675, 8775,
+    8876, 8978, 9080, 9183, 9286, 9390, 9495, 9600, 9706, 9812, 9920, 10027, 10136, 10245, 10355,
+    10465, 10576, 10688, 10800, 10913, 11027, 11141, 11256, 11371, 11487, 11604, 11721, 11840,
+    11958, 12078, 12198, 12318, 12440, 12562, 12684, 12807, 12931, 13056, 13181, 13307, 13433,
+    13561, 13688, 13817, 13946, 14076, 14206, 14337, 14469, 14602, 14735, 14868, 15003, 15138,
+    15273, 15410, 15547, 15685, 15823, 15962, 16102, 16242, 16383,
+];
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::pixelcolor::Rgb888;
+
+    use super::*;
+
+    /// Gamma tables giving a fixed 14 bit value for each channel, whatever the color.
+    const RED_BCD: u16 = 0b10_1010_1010_1010;
+    const GREEN_BCD: u16 = 0b01_0101_0101_0101;
+    const BLUE_BCD: u16 = 0b11_0000_1111_0011;
+
+    fn fixed_calibration() -> ColorCalibration {
+        ColorCalibration::new().with_gamma_tables([RED_BCD; 256], [GREEN_BCD; 256], [BLUE_BCD; 256])
+    }
+
+    /// Graphics with a different color in every pixel, offset by the seed.
+    fn pattern(seed: u8) -> UnicornGraphics<WIDTH, HEIGHT> {
+        let mut graphics = UnicornGraphics::new();
+        for y in 0..HEIGHT {
+            for x in 0..WIDTH {
+                let color = Rgb888::new(
+                    (x as u8).wrapping_mul(4).wrapping_add(seed),
+                    (y as u8).wrapping_mul(8),
+                    seed,
+                );
+                graphics.set_pixel(Point::new(x as i32, y as i32), color);
+            }
+        }
+        graphics
+    }
+
+    /// Offset of the BCD frame of the scanned row.
+    fn frame_offset(encoder: &BitstreamEncoder, row: usize, frame: usize) -> usize {
+        row * encoder.config().row_bytes() + frame * BCD_FRAME_BYTES
+    }
+
+    #[test]
+    fn row_headers() {
+        for config in [BitstreamConfig::default(), BitstreamConfig::new(10, 3)] {
+            let encoder = BitstreamEncoder::with_config(config);
+            let bytes = encoder.as_bytes();
+
+            for row in 0..ROW_COUNT {
+                for frame in 0..config.bcd_frame_count() {
+                    let offset = frame_offset(&encoder, row, frame);
+                    assert_eq!(bytes[offset] as usize, ROW_PIXELS - 1);
+                    assert_eq!(bytes[offset + 1] as usize, row);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn tick_words() {
+        for config in [BitstreamConfig::default(), BitstreamConfig::new(10, 3)] {
+            let encoder = BitstreamEncoder::with_config(config);
+            let bytes = encoder.as_bytes();
+
+            for row in 0..ROW_COUNT {
+                for frame in 0..config.bcd_frame_count() {
+                    let offset = frame_offset(&encoder, row, frame) + TICKS_OFFSET;
+                    let ticks = u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+                    assert_eq!(ticks, config.base_ticks() << frame);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn length_follows_config() {
+        let config = BitstreamConfig::new(10, 3);
+        let encoder = BitstreamEncoder::with_config(config);
+
+        assert_eq!(BCD_FRAME_BYTES % 4, 0);
+        assert_eq!(encoder.as_bytes().len(), ROW_COUNT * 10 * BCD_FRAME_BYTES);
+        assert_eq!(encoder.as_words().len() * 4, encoder.as_bytes().len());
+        assert_eq!(
+            encoder.as_words()[0].to_le_bytes(),
+            encoder.as_bytes()[0..4]
+        );
+    }
+
+    #[test]
+    fn pixel_bits_in_each_frame() {
+        let mut encoder = BitstreamEncoder::new();
+        encoder.set_calibration(fixed_calibration());
+        encoder.set_pixel_rgb(1, 2, 255, 255, 255, 255);
+
+        let (column, row) = scan_position(1, 2);
+        let bytes = encoder.as_bytes();
+
+        for frame in 0..BCD_FRAME_COUNT {
+            let offset = frame_offset(&encoder, row, frame) + PIXEL_OFFSET + column;
+            let bit = |value: u16| ((value >> frame) & 1) as u8;
+            let expected = bit(BLUE_BCD) | (bit(GREEN_BCD) << 1) | (bit(RED_BCD) << 2);
+            assert_eq!(bytes[offset], expected, "frame {frame}");
+        }
+    }
+
+    #[test]
+    fn pixel_bits_at_lower_color_depth() {
+        let mut encoder = BitstreamEncoder::with_config(BitstreamConfig::new(8, 1));
+        encoder.set_calibration(fixed_calibration());
+        encoder.set_pixel_rgb(0, 0, 255, 255, 255, 255);
+
+        let (column, row) = scan_position(0, 0);
+        let bytes = encoder.as_bytes();
+
+        // the 6 least significant bits are dropped
+        for frame in 0..8 {
+            let offset = frame_offset(&encoder, row, frame) + PIXEL_OFFSET + column;
+            let bit = |value: u16| ((value >> (frame + 6)) & 1) as u8;
+            let expected = bit(BLUE_BCD) | (bit(GREEN_BCD) << 1) | (bit(RED_BCD) << 2);
+            assert_eq!(bytes[offset], expected, "frame {frame}");
+        }
+    }
+
+    #[test]
+    fn black_pixels_are_cleared() {
+        let mut encoder = BitstreamEncoder::new();
+        encoder.set_pixels(&UnicornGraphics::new(), 255);
+
+        let bytes = encoder.as_bytes();
+        for row in 0..ROW_COUNT {
+            for frame in 0..BCD_FRAME_COUNT {
+                let offset = frame_offset(&encoder, row, frame) + PIXEL_OFFSET;
+                assert!(bytes[offset..offset + ROW_PIXELS]
+                    .iter()
+                    .all(|bits| *bits == 0));
+            }
+        }
+    }
+
+    #[test]
+    fn padding_is_left_alone() {
+        let empty = BitstreamEncoder::new();
+
+        let mut graphics = UnicornGraphics::<WIDTH, HEIGHT>::new();
+        graphics.fill(Rgb888::new(255, 255, 255));
+        graphics.set_pixel(Point::new(3, 4), Rgb888::new(10, 200, 30));
+
+        let mut encoder = BitstreamEncoder::new();
+        encoder.set_pixels(&graphics, 255);
+
+        for row in 0..ROW_COUNT {
+            for frame in 0..BCD_FRAME_COUNT {
+                let offset = frame_offset(&encoder, row, frame);
+                let padding = offset + PIXEL_OFFSET + ROW_PIXELS..offset + TICKS_OFFSET;
+                assert_eq!(
+                    encoder.as_bytes()[padding.clone()],
+                    empty.as_bytes()[padding]
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "galactic")]
+    #[test]
+    fn scan_position_of_corners() {
+        assert_eq!(scan_position(0, 0), (52, 10));
+        assert_eq!(scan_position(52, 0), (0, 10));
+        assert_eq!(scan_position(0, 10), (52, 0));
+        assert_eq!(scan_position(52, 10), (0, 0));
+    }
+
+    #[cfg(feature = "cosmic")]
+    #[test]
+    fn scan_position_of_corners() {
+        // top half
+        assert_eq!(scan_position(0, 0), (31, 15));
+        assert_eq!(scan_position(31, 0), (0, 15));
+        assert_eq!(scan_position(0, 15), (31, 0));
+        assert_eq!(scan_position(31, 15), (0, 0));
+
+        // bottom half
+        assert_eq!(scan_position(0, 16), (63, 15));
+        assert_eq!(scan_position(31, 16), (32, 15));
+        assert_eq!(scan_position(0, 31), (63, 0));
+        assert_eq!(scan_position(31, 31), (32, 0));
+    }
+
+    #[cfg(feature = "stellar")]
+    #[test]
+    fn scan_position_of_corners() {
+        // top half
+        assert_eq!(scan_position(0, 0), (15, 7));
+        assert_eq!(scan_position(15, 0), (0, 7));
+        assert_eq!(scan_position(0, 7), (15, 0));
+        assert_eq!(scan_position(15, 7), (0, 0));
+
+        // bottom half
+        assert_eq!(scan_position(0, 8), (31, 7));
+        assert_eq!(scan_position(15, 8), (16, 7));
+        assert_eq!(scan_position(0, 15), (31, 0));
+        assert_eq!(scan_position(15, 15), (16, 0));
+    }
+
+    #[test]
+    fn scan_position_covers_every_column_and_row_once() {
+        let mut seen = [[false; ROW_PIXELS]; ROW_COUNT];
+
+        for y in 0..HEIGHT {
+            for x in 0..WIDTH {
+                let (column, row) = scan_position(x, y);
+                assert!(!seen[row][column]);
+                seen[row][column] = true;
+            }
+        }
+
+        assert!(seen.iter().flatten().all(|seen| *seen));
+    }
+
+    #[test]
+    fn set_pixels_in_dirty_area_matches_full_encode() {
+        let mut graphics = pattern(0);
+        let mut encoder = BitstreamEncoder::new();
+        encoder.set_pixels(&graphics, 200);
+
+        graphics.clear_dirty();
+        graphics.set_pixel(Point::new(3, 2), Rgb888::WHITE);
+        graphics.set_pixel(Point::new(6, 4), Rgb888::BLACK);
+        let area = graphics.take_dirty_area().unwrap();
+        encoder.set_pixels_in(&graphics, 200, &area);
+
+        let mut expected = BitstreamEncoder::new();
+        expected.set_pixels(&graphics, 200);
+        assert_eq!(encoder.as_bytes(), expected.as_bytes());
+    }
+
+    #[test]
+    fn set_pixels_in_leaves_the_rest_alone() {
+        let graphics = pattern(0);
+        let mut encoder = BitstreamEncoder::new();
+        encoder.set_pixels_in(
+            &graphics,
+            255,
+            &Rectangle::new(Point::new(1, 1), Size::new(2, 1)),
+        );
+
+        let mut expected = BitstreamEncoder::new();
+        for x in 1..3 {
+            let color = graphics.get_item(Point::new(x, 1)).unwrap();
+            expected.set_pixel_rgb(x as u8, 1, color.r(), color.g(), color.b(), 255);
+        }
+        assert_eq!(encoder.as_bytes(), expected.as_bytes());
+    }
+
+    #[test]
+    fn copy_area_from() {
+        let (first, mut second) = (pattern(0), pattern(100));
+        let mut source = BitstreamEncoder::new();
+        source.set_pixels(&first, 255);
+        let mut encoder = BitstreamEncoder::new();
+        encoder.set_pixels(&second, 255);
+
+        // the area runs off the right and bottom of the display, which is skipped
+        let top_left = Point::new(WIDTH as i32 - 3, HEIGHT as i32 - 2);
+        let area = Rectangle::new(top_left, Size::new(5, 5));
+        encoder.copy_area_from(&source, &area);
+
+        for point in area.points() {
+            if let Some(color) = first.get_item(point) {
+                second.set_pixel(point, color);
+            }
+        }
+        let mut expected = BitstreamEncoder::new();
+        expected.set_pixels(&second, 255);
+        assert_eq!(encoder.as_bytes(), expected.as_bytes());
+    }
+
+    #[test]
+    fn copy_area_from_at_lower_color_depth() {
+        let config = BitstreamConfig::new(MIN_BCD_FRAME_COUNT, 2);
+        let mut source = BitstreamEncoder::with_config(config);
+        source.set_pixels(&pattern(0), 255);
+
+        let mut encoder = BitstreamEncoder::with_config(config);
+        let display = Rectangle::new(Point::zero(), Size::new(WIDTH as u32, HEIGHT as u32));
+        encoder.copy_area_from(&source, &display);
+        assert_eq!(encoder.as_bytes(), source.as_bytes());
+
+        // an empty area copies nothing
+        let mut encoder = BitstreamEncoder::with_config(config);
+        encoder.copy_area_from(&source, &Rectangle::new(Point::new(2, 2), Size::zero()));
+        assert_eq!(
+            encoder.as_bytes(),
+            BitstreamEncoder::with_config(config).as_bytes()
+        );
+    }
+
+    #[test]
+    fn grow_area_holds_both_areas() {
+        let mut area = None;
+        grow_area(&mut area, Rectangle::new(Point::new(3, 2), Size::new(2, 1)));
+        assert_eq!(
+            area,
+            Some(Rectangle::new(Point::new(3, 2), Size::new(2, 1)))
+        );
+
+        grow_area(&mut area, Rectangle::new(Point::new(1, 4), Size::new(1, 1)));
+        let expected = Rectangle::with_corners(Point::new(1, 2), Point::new(4, 4));
+        assert_eq!(area, Some(expected));
+
+        // an empty area grows nothing
+        grow_area(&mut area, Rectangle::new(Point::new(20, 20), Size::zero()));
+        assert_eq!(area, Some(expected));
+    }
+}
@@ -0,0 +1,331 @@
+#![no_std]
+
+/// Maximum reading from the 12 bit light sensor ADC.
+pub const MAX_LIGHT_LEVEL: u16 = 4095;
+
+/// Default curve, giving finer control of brightness in darker rooms.
+pub const DEFAULT_CURVE: &[CurvePoint] = &[
+    CurvePoint::new(0, 0),
+    CurvePoint::new(256, 64),
+    CurvePoint::new(1024, 160),
+    CurvePoint::new(MAX_LIGHT_LEVEL, 255),
+];
+
+/// A point on the curve mapping light levels to brightness.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct CurvePoint {
+    /// Light level reading from the sensor.
+    pub light_level: u16,
+
+    /// Brightness at this light level, where 0 is the minimum and 255 the maximum brightness.
+    pub brightness: u8,
+}
+
+impl CurvePoint {
+    /// Create a new curve point.
+    pub const fn new(light_level: u16, brightness: u8) -> Self {
+        Self {
+            light_level,
+            brightness,
+        }
+    }
+}
+
+/// Settings for how light levels are turned into display brightness.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct AutoBrightnessConfig {
+    /// Lowest brightness that will be set.
+    pub min_brightness: u8,
+
+    /// Highest brightness that will be set.
+    pub max_brightness: u8,
+
+    /// Weight given to each new reading out of 256, lower values smooth more.
+    pub smoothing: u8,
+
+    /// Smallest change in brightness that will be applied, to avoid flicker.
+    pub hysteresis: u8,
+
+    /// Points mapping light levels to brightness, in order of light level.
+    /// Light levels between points are linearly interpolated.
+    pub curve: &'static [CurvePoint],
+}
+
+impl Default for AutoBrightnessConfig {
+    fn default() -> Self {
+        Self {
+            min_brightness: 10,
+            max_brightness: 255,
+            smoothing: 32,
+            hysteresis: 4,
+            curve: DEFAULT_CURVE,
+        }
+    }
+}
+
+/// Turns light sensor readings into a display brightness.
+#[derive(Copy, Clone, Debug)]
+pub struct AutoBrightness {
+    pub config: AutoBrightnessConfig,
+    enabled: bool,
+
+    /// Smoothed light level, with 8 fractional bits.
+    smoothed_level: Option<u32>,
+
+    /// The brightness last returned.
+    brightness: u8,
+}
+
+impl AutoBrightness {
+    /// Create a new enabled auto brightness controller.
+    pub fn new(config: AutoBrightnessConfig) -> Self {
+        Self {
+            config,
+            enabled: true,
+            smoothed_level: None,
+            brightness: config.max_brightness,
+        }
+    }
+
+    /// Check if auto brightness is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enable auto brightness.
+    /// Smoothing restarts from the next reading.
+    pub fn enable(&mut self) {
+        self.enabled = true;
+        self.smoothed_level = None;
+    }
+
+    /// Disable auto brightness.
+    pub fn disable(&mut self) {
+        self.enabled = false;
+    }
+
+    /// Toggle auto brightness between enabled and disabled.
+    pub fn toggle(&mut self) {
+        if self.enabled {
+            self.disable();
+        } else {
+            self.enable();
+        }
+    }
+
+    /// Get the brightness last returned from [`Self::update`].
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Get the smoothed light level.
+    pub fn light_level(&self) -> Option<u16> {
+        self.smoothed_level.map(|level| (level >> 8) as u16)
+    }
+
+    /// Update with a new light sensor reading.
+    /// Returns the new brightness if it has changed, or `None` if disabled or unchanged.
+    pub fn update(&mut self, light_level: u16) -> Option<u8> {
+        if !self.enabled {
+            return None;
+        }
+
+        let reading = (light_level.min(MAX_LIGHT_LEVEL) as u32) << 8;
+        let smoothed = match self.smoothed_level {
+            Some(smoothed) => {
+                let weight = self.config.smoothing as i64;
+                let delta = reading as i64 - smoothed as i64;
+                let step = match (delta * weight) / 256 {
+                    // move at least one step, so the level settles on the reading rather than just short of it
+                    0 if weight > 0 => delta.signum(),
+                    step => step,
+                };
+                (smoothed as i64 + step) as u32
+            }
+            None => reading,
+        };
+        self.smoothed_level = Some(smoothed);
+
+        let target = self.target_brightness((smoothed >> 8) as u16);
+        let at_limit = target == self.config.min_brightness || target == self.config.max_brightness;
+
+        if target == self.brightness
+            || (target.abs_diff(self.brightness) < self.config.hysteresis && !at_limit)
+        {
+            return None;
+        }
+
+        self.brightness = target;
+        Some(target)
+    }
+
+    /// Map a light level through the curve to a brightness between the minimum and maximum.
+    pub fn target_brightness(&self, light_level: u16) -> u8 {
+        let curve = self.curve_value(light_level) as u32;
+        let min = self.config.min_brightness as u32;
+        let max = self.config.max_brightness.max(self.config.min_brightness) as u32;
+
+        (min + ((max - min) * curve + 127) / 255) as u8
+    }
+
+    fn curve_value(&self, light_level: u16) -> u8 {
+        let curve = self.config.curve;
+
+        let (first, last) = match (curve.first(), curve.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return (light_level as u32 * 255 / MAX_LIGHT_LEVEL as u32) as u8,
+        };
+
+        if light_level <= first.light_level {
+            return first.brightness;
+        }
+
+        if light_level >= last.light_level {
+            return last.brightness;
+        }
+
+        for pair in curve.windows(2) {
+            let (start, end) = (pair[0], pair[1]);
+            if light_level > end.light_level {
+                continue;
+            }
+
+            let span = (end.light_level - start.light_level) as i32;
+            if span == 0 {
+                return end.brightness;
+            }
+
+            let offset = (light_level - start.light_level) as i32;
+            let rise = end.brightness as i32 - start.brightness as i32;
+            return (start.brightness as i32 + rise * offset / span) as u8;
+        }
+
+        last.brightness
+    }
+}
+
+impl Default for AutoBrightness {
+    fn default() -> Self {
+        Self::new(AutoBrightnessConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_reading_is_taken_as_is() {
+        let mut auto_brightness = AutoBrightness::default();
+        auto_brightness.update(1000);
+
+        assert_eq!(auto_brightness.light_level(), Some(1000));
+    }
+
+    #[test]
+    fn readings_are_smoothed() {
+        let mut auto_brightness = AutoBrightness::default();
+        auto_brightness.update(0);
+
+        // each reading moves the level 32/256ths of the way towards it
+        auto_brightness.update(MAX_LIGHT_LEVEL);
+        assert_eq!(auto_brightness.light_level(), Some(511));
+
+        auto_brightness.update(MAX_LIGHT_LEVEL);
+        assert_eq!(auto_brightness.light_level(), Some(959));
+
+        for _ in 0..2000 {
+            auto_brightness.update(MAX_LIGHT_LEVEL);
+        }
+        assert_eq!(auto_brightness.light_level(), Some(MAX_LIGHT_LEVEL));
+    }
+
+    #[test]
+    fn readings_are_limited_to_the_sensor_range() {
+        let mut auto_brightness = AutoBrightness::default();
+        auto_brightness.update(u16::MAX);
+
+        assert_eq!(auto_brightness.light_level(), Some(MAX_LIGHT_LEVEL));
+    }
+
+    #[test]
+    fn curve_endpoints() {
+        let auto_brightness = AutoBrightness::default();
+
+        assert_eq!(auto_brightness.target_brightness(0), 10);
+        assert_eq!(auto_brightness.target_brightness(MAX_LIGHT_LEVEL), 255);
+        assert_eq!(auto_brightness.target_brightness(u16::MAX), 255);
+    }
+
+    #[test]
+    fn curve_is_interpolated() {
+        let auto_brightness = AutoBrightness::new(AutoBrightnessConfig {
+            min_brightness: 0,
+            ..AutoBrightnessConfig::default()
+        });
+
+        assert_eq!(auto_brightness.target_brightness(256), 64);
+        assert_eq!(auto_brightness.target_brightness(128), 32);
+        assert_eq!(auto_brightness.target_brightness(640), 112);
+    }
+
+    #[test]
+    fn linear_without_a_curve() {
+        let auto_brightness = AutoBrightness::new(AutoBrightnessConfig {
+            min_brightness: 0,
+            curve: &[],
+            ..AutoBrightnessConfig::default()
+        });
+
+        assert_eq!(auto_brightness.target_brightness(0), 0);
+        assert_eq!(auto_brightness.target_brightness(MAX_LIGHT_LEVEL), 255);
+    }
+
+    #[test]
+    fn small_changes_are_held_back() {
+        let config = AutoBrightnessConfig {
+            min_brightness: 0,
+            smoothing: 255,
+            curve: &[],
+            ..AutoBrightnessConfig::default()
+        };
+        let mut auto_brightness = AutoBrightness::new(config);
+
+        // changes of up to 3 are inside the band of 4
+        assert_eq!(auto_brightness.update(1606), Some(100));
+        assert_eq!((0..10).find_map(|_| auto_brightness.update(1654)), None);
+        assert_eq!(auto_brightness.brightness(), 100);
+
+        assert_eq!(
+            (0..10).find_map(|_| auto_brightness.update(1671)),
+            Some(104)
+        );
+    }
+
+    #[test]
+    fn limits_are_reached_inside_the_band() {
+        let config = AutoBrightnessConfig {
+            min_brightness: 0,
+            smoothing: 255,
+            curve: &[],
+            ..AutoBrightnessConfig::default()
+        };
+        let mut auto_brightness = AutoBrightness::new(config);
+
+        assert_eq!(auto_brightness.update(1606), Some(100));
+        assert_eq!(auto_brightness.update(MAX_LIGHT_LEVEL), Some(254));
+
+        // the last steps to the maximum are inside the band, but the maximum is always reached
+        let next = (0..10).find_map(|_| auto_brightness.update(MAX_LIGHT_LEVEL));
+        assert_eq!(next, Some(255));
+    }
+
+    #[test]
+    fn disabled_does_nothing() {
+        let mut auto_brightness = AutoBrightness::default();
+        auto_brightness.disable();
+
+        assert_eq!(auto_brightness.update(0), None);
+        assert_eq!(auto_brightness.light_level(), None);
+    }
+}
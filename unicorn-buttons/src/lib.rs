@@ -0,0 +1,410 @@
+#![no_std]
+
+/// Number of buttons on the unicorn.
+pub const BUTTON_COUNT: usize = 9;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum UnicornButtons {
+    SwitchA,
+    SwitchB,
+    SwitchC,
+    SwitchD,
+    BrightnessUp,
+    BrightnessDown,
+    VolumeUp,
+    VolumeDown,
+    Sleep,
+}
+
+impl UnicornButtons {
+    /// All buttons, in the order of their index.
+    pub const ALL: [UnicornButtons; BUTTON_COUNT] = [
+        UnicornButtons::SwitchA,
+        UnicornButtons::SwitchB,
+        UnicornButtons::SwitchC,
+        UnicornButtons::SwitchD,
+        UnicornButtons::BrightnessUp,
+        UnicornButtons::BrightnessDown,
+        UnicornButtons::VolumeUp,
+        UnicornButtons::VolumeDown,
+        UnicornButtons::Sleep,
+    ];
+
+    /// Index of the button within [`Self::ALL`].
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// What happened to a button.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ButtonEventKind {
+    /// The button was pressed down.
+    Pressed,
+
+    /// The button was let go.
+    Released,
+
+    /// The button has been held for the long press time.
+    LongPress,
+
+    /// The button is still held, sent at the repeat interval.
+    Repeat,
+}
+
+/// An event from one of the buttons.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ButtonEvent {
+    pub button: UnicornButtons,
+    pub kind: ButtonEventKind,
+}
+
+/// Timings used when turning button readings into events.
+/// All times are in milliseconds.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ButtonConfig {
+    /// How long a reading must be stable before it is accepted.
+    pub debounce_ms: u64,
+
+    /// How long a button must be held before a long press event.
+    pub long_press_ms: u64,
+
+    /// How long a button must be held before the first repeat event.
+    pub repeat_delay_ms: u64,
+
+    /// Time between repeat events. Repeats are disabled when zero.
+    pub repeat_interval_ms: u64,
+}
+
+impl Default for ButtonConfig {
+    fn default() -> Self {
+        Self {
+            debounce_ms: 20,
+            long_press_ms: 1000,
+            repeat_delay_ms: 500,
+            repeat_interval_ms: 100,
+        }
+    }
+}
+
+/// Debounced state of a single button.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ButtonState {
+    /// The accepted, debounced, state.
+    pressed: bool,
+
+    /// The most recent raw reading.
+    last_reading: bool,
+
+    /// When the raw reading last changed.
+    last_change: u64,
+
+    /// When the button was last accepted as pressed.
+    pressed_at: u64,
+
+    /// If the long press event has been sent for the current press.
+    long_press_sent: bool,
+
+    /// When the next repeat event is due.
+    next_repeat: u64,
+}
+
+impl ButtonState {
+    /// Create a new released button state.
+    pub const fn new() -> Self {
+        Self {
+            pressed: false,
+            last_reading: false,
+            last_change: 0,
+            pressed_at: 0,
+            long_press_sent: false,
+            next_repeat: 0,
+        }
+    }
+
+    /// Check if the button is pressed, after debouncing.
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    /// Update the state with a raw reading taken at the given time in milliseconds.
+    /// Returns the event caused by the reading, if any.
+    pub fn update(
+        &mut self,
+        reading: bool,
+        now_ms: u64,
+        config: &ButtonConfig,
+    ) -> Option<ButtonEventKind> {
+        if reading != self.last_reading {
+            self.last_reading = reading;
+            self.last_change = now_ms;
+        }
+
+        if reading != self.pressed && now_ms.saturating_sub(self.last_change) >= config.debounce_ms
+        {
+            self.pressed = reading;
+
+            if !reading {
+                return Some(ButtonEventKind::Released);
+            }
+
+            self.pressed_at = now_ms;
+            self.long_press_sent = false;
+            self.next_repeat = now_ms.saturating_add(config.repeat_delay_ms);
+            return Some(ButtonEventKind::Pressed);
+        }
+
+        if !self.pressed {
+            return None;
+        }
+
+        if !self.long_press_sent && now_ms.saturating_sub(self.pressed_at) >= config.long_press_ms {
+            self.long_press_sent = true;
+            return Some(ButtonEventKind::LongPress);
+        }
+
+        if config.repeat_interval_ms > 0 && now_ms >= self.next_repeat {
+            self.next_repeat = self.next_repeat.saturating_add(config.repeat_interval_ms);
+            return Some(ButtonEventKind::Repeat);
+        }
+
+        None
+    }
+}
+
+/// Debounced state of all buttons, turning raw readings into events.
+///
+/// Time is passed in with each update rather than read from a clock,
+/// so it can be driven by any timer.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ButtonStates {
+    pub config: ButtonConfig,
+    states: [ButtonState; BUTTON_COUNT],
+}
+
+impl ButtonStates {
+    /// Create a new set of released button states.
+    pub fn new(config: ButtonConfig) -> Self {
+        Self {
+            config,
+            states: [ButtonState::new(); BUTTON_COUNT],
+        }
+    }
+
+    /// Check if a button is pressed, after debouncing.
+    pub fn is_pressed(&self, button: UnicornButtons) -> bool {
+        self.states[button.index()].is_pressed()
+    }
+
+    /// Update all buttons with readings taken at the given time in milliseconds.
+    /// `is_pressed` is called once for each button to get its raw reading.
+    pub fn update<F>(&mut self, now_ms: u64, mut is_pressed: F) -> ButtonEvents
+    where
+        F: FnMut(UnicornButtons) -> bool,
+    {
+        let mut events = ButtonEvents::new();
+
+        for button in UnicornButtons::ALL {
+            let reading = is_pressed(button);
+            if let Some(kind) = self.states[button.index()].update(reading, now_ms, &self.config) {
+                events.push(ButtonEvent { button, kind });
+            }
+        }
+
+        events
+    }
+}
+
+/// Events from a single update of all buttons.
+#[derive(Copy, Clone, Debug)]
+pub struct ButtonEvents {
+    events: [Option<ButtonEvent>; BUTTON_COUNT],
+    len: usize,
+    next: usize,
+}
+
+impl ButtonEvents {
+    fn new() -> Self {
+        Self {
+            events: [None; BUTTON_COUNT],
+            len: 0,
+            next: 0,
+        }
+    }
+
+    fn push(&mut self, event: ButtonEvent) {
+        self.events[self.len] = Some(event);
+        self.len += 1;
+    }
+}
+
+impl Iterator for ButtonEvents {
+    type Item = ButtonEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.len {
+            return None;
+        }
+
+        self.next += 1;
+        self.events[self.next - 1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed the reading to the button from the start time up to the end time, a millisecond at a time,
+    /// collecting the time of each event.
+    fn hold(
+        state: &mut ButtonState,
+        reading: bool,
+        times: core::ops::Range<u64>,
+        config: &ButtonConfig,
+    ) -> ([(u64, ButtonEventKind); 32], usize) {
+        let mut events = [(0, ButtonEventKind::Pressed); 32];
+        let mut len = 0;
+
+        for now_ms in times {
+            if let Some(kind) = state.update(reading, now_ms, config) {
+                events[len] = (now_ms, kind);
+                len += 1;
+            }
+        }
+
+        (events, len)
+    }
+
+    #[test]
+    fn pressed_after_debounce() {
+        let config = ButtonConfig::default();
+        let mut state = ButtonState::new();
+
+        assert_eq!(state.update(true, 100, &config), None);
+        assert_eq!(state.update(true, 119, &config), None);
+        assert!(!state.is_pressed());
+
+        assert_eq!(
+            state.update(true, 120, &config),
+            Some(ButtonEventKind::Pressed)
+        );
+        assert!(state.is_pressed());
+    }
+
+    #[test]
+    fn bounces_are_ignored() {
+        let config = ButtonConfig::default();
+        let mut state = ButtonState::new();
+
+        // contact bounce every few milliseconds restarts the debounce time
+        for (now_ms, reading) in [(0, true), (5, false), (10, true), (15, false), (20, true)] {
+            assert_eq!(state.update(reading, now_ms, &config), None);
+        }
+
+        assert_eq!(state.update(true, 39, &config), None);
+        assert_eq!(
+            state.update(true, 40, &config),
+            Some(ButtonEventKind::Pressed)
+        );
+    }
+
+    #[test]
+    fn released_after_debounce() {
+        let config = ButtonConfig::default();
+        let mut state = ButtonState::new();
+        hold(&mut state, true, 0..21, &config);
+
+        assert_eq!(state.update(false, 50, &config), None);
+        assert_eq!(state.update(false, 69, &config), None);
+        assert_eq!(
+            state.update(false, 70, &config),
+            Some(ButtonEventKind::Released)
+        );
+        assert!(!state.is_pressed());
+    }
+
+    #[test]
+    fn long_press_and_repeats() {
+        let config = ButtonConfig::default();
+        let mut state = ButtonState::new();
+
+        let (events, len) = hold(&mut state, true, 0..1300, &config);
+
+        let mut expected = [(0, ButtonEventKind::Pressed); 32];
+        expected[0] = (20, ButtonEventKind::Pressed);
+        for (index, now_ms) in [520, 620, 720, 820, 920].into_iter().enumerate() {
+            expected[index + 1] = (now_ms, ButtonEventKind::Repeat);
+        }
+        expected[6] = (1020, ButtonEventKind::LongPress);
+        // the repeat due with the long press follows it on the next update
+        expected[7] = (1021, ButtonEventKind::Repeat);
+        expected[8] = (1120, ButtonEventKind::Repeat);
+        expected[9] = (1220, ButtonEventKind::Repeat);
+
+        assert_eq!(events[..len], expected[..10]);
+    }
+
+    #[test]
+    fn repeats_disabled_without_interval() {
+        let config = ButtonConfig {
+            repeat_interval_ms: 0,
+            ..ButtonConfig::default()
+        };
+        let mut state = ButtonState::new();
+
+        let (events, len) = hold(&mut state, true, 0..2000, &config);
+
+        assert_eq!(
+            events[..len],
+            [
+                (20, ButtonEventKind::Pressed),
+                (1020, ButtonEventKind::LongPress)
+            ]
+        );
+    }
+
+    #[test]
+    fn clock_going_backwards() {
+        let config = ButtonConfig::default();
+        let mut state = ButtonState::new();
+        hold(&mut state, true, 1000..1021, &config);
+        assert!(state.is_pressed());
+
+        // a reset clock does not panic, and the button is released once the new time has passed the debounce
+        hold(&mut state, true, 0..10, &config);
+        assert_eq!(state.update(false, 10, &config), None);
+        assert_eq!(
+            state.update(false, 30, &config),
+            Some(ButtonEventKind::Released)
+        );
+    }
+
+    #[test]
+    fn events_for_each_button() {
+        let mut states = ButtonStates::new(ButtonConfig::default());
+        let held = |button| matches!(button, UnicornButtons::SwitchA | UnicornButtons::Sleep);
+
+        assert_eq!(states.update(0, held).count(), 0);
+
+        let mut events = states.update(20, held);
+        assert_eq!(
+            events.next(),
+            Some(ButtonEvent {
+                button: UnicornButtons::SwitchA,
+                kind: ButtonEventKind::Pressed
+            })
+        );
+        assert_eq!(
+            events.next(),
+            Some(ButtonEvent {
+                button: UnicornButtons::Sleep,
+                kind: ButtonEventKind::Pressed
+            })
+        );
+        assert_eq!(events.next(), None);
+
+        assert!(states.is_pressed(UnicornButtons::SwitchA));
+        assert!(!states.is_pressed(UnicornButtons::SwitchB));
+    }
+}
@@ -0,0 +1,47 @@
+#![no_std]
+
+use embedded_graphics_core::prelude::Size;
+use unicorn_graphics::UnicornGraphics;
+
+pub use unicorn_buttons::UnicornButtons;
+
+mod mock;
+
+pub use mock::MockUnicorn;
+
+/// A unicorn board with a `W` by `H` pixel display.
+///
+/// Implemented by each of the drivers, so animations can be written once
+/// and run on any board, or against [`MockUnicorn`] on a host machine.
+pub trait UnicornDisplay<const W: usize, const H: usize> {
+    /// Size of the display in pixels.
+    fn size(&self) -> Size {
+        Size::new(W as u32, H as u32)
+    }
+
+    /// Update the display with the buffer from the unicorn graphics instance.
+    fn set_pixels(&mut self, graphics: &UnicornGraphics<W, H>);
+
+    /// Get the current brightness.
+    fn brightness(&self) -> u8;
+
+    /// Set the brightness of the display to the given value.
+    fn set_brightness(&mut self, brightness: u8);
+
+    /// Increase brightness by the given step.
+    fn increase_brightness(&mut self, step: u8) {
+        self.set_brightness(self.brightness().saturating_add(step));
+    }
+
+    /// Decrease brightness by the given step.
+    fn decrease_brightness(&mut self, step: u8) {
+        self.set_brightness(self.brightness().saturating_sub(step));
+    }
+
+    /// Get the current light level reading.
+    /// Returns `None` if the driver does not support the light sensor.
+    fn light_level(&mut self) -> Option<u16>;
+
+    /// Check if a button is pressed.
+    fn is_button_pressed(&mut self, button: UnicornButtons) -> bool;
+}
@@ -0,0 +1,174 @@
+use unicorn_buttons::BUTTON_COUNT;
+use unicorn_graphics::UnicornGraphics;
+
+use crate::{UnicornButtons, UnicornDisplay};
+
+/// A unicorn without hardware, for running animations in tests or on a host machine.
+///
+/// Pixels set on the display are kept so they can be checked, and the light level
+/// and buttons can be set to simulate input.
+#[derive(Copy, Clone)]
+pub struct MockUnicorn<const W: usize, const H: usize> {
+    graphics: UnicornGraphics<W, H>,
+    brightness: u8,
+    light_level: Option<u16>,
+    pressed: [bool; BUTTON_COUNT],
+    frame_count: u32,
+}
+
+impl<const W: usize, const H: usize> MockUnicorn<W, H> {
+    /// Create a new mock with a blank display at full brightness.
+    pub fn new() -> Self {
+        Self {
+            graphics: UnicornGraphics::new(),
+            brightness: 255,
+            light_level: None,
+            pressed: [false; BUTTON_COUNT],
+            frame_count: 0,
+        }
+    }
+
+    /// Get the pixels last set on the display.
+    pub fn graphics(&self) -> &UnicornGraphics<W, H> {
+        &self.graphics
+    }
+
+    /// Number of times the pixels have been set on the display.
+    pub fn frame_count(&self) -> u32 {
+        self.frame_count
+    }
+
+    /// Set the light level returned by the light sensor, or `None` for no light sensor.
+    pub fn set_light_level(&mut self, light_level: Option<u16>) {
+        self.light_level = light_level;
+    }
+
+    /// Press the button, until it is released.
+    pub fn press(&mut self, button: UnicornButtons) {
+        self.pressed[button.index()] = true;
+    }
+
+    /// Release the button.
+    pub fn release(&mut self, button: UnicornButtons) {
+        self.pressed[button.index()] = false;
+    }
+}
+
+impl<const W: usize, const H: usize> Default for MockUnicorn<W, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize, const H: usize> UnicornDisplay<W, H> for MockUnicorn<W, H> {
+    fn set_pixels(&mut self, graphics: &UnicornGraphics<W, H>) {
+        self.graphics = *graphics;
+        self.frame_count += 1;
+    }
+
+    fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness;
+    }
+
+    fn light_level(&mut self) -> Option<u16> {
+        self.light_level
+    }
+
+    fn is_button_pressed(&mut self, button: UnicornButtons) -> bool {
+        self.pressed[button.index()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::{pixelcolor::Rgb888, prelude::*};
+
+    use super::*;
+
+    #[test]
+    fn new_is_blank_at_full_brightness() {
+        let unicorn = MockUnicorn::<4, 3>::new();
+
+        assert_eq!(unicorn.size(), Size::new(4, 3));
+        assert_eq!(unicorn.brightness(), 255);
+        assert_eq!(unicorn.frame_count(), 0);
+        assert_eq!(
+            unicorn.graphics().get_item(Point::new(1, 1)),
+            Some(Rgb888::BLACK)
+        );
+    }
+
+    #[test]
+    fn brightness_saturates() {
+        let mut unicorn = MockUnicorn::<4, 3>::new();
+
+        unicorn.increase_brightness(10);
+        assert_eq!(unicorn.brightness(), 255);
+
+        unicorn.decrease_brightness(100);
+        assert_eq!(unicorn.brightness(), 155);
+
+        unicorn.decrease_brightness(200);
+        assert_eq!(unicorn.brightness(), 0);
+
+        unicorn.set_brightness(42);
+        unicorn.increase_brightness(8);
+        assert_eq!(unicorn.brightness(), 50);
+    }
+
+    #[test]
+    fn set_pixels_keeps_the_graphics_and_counts_frames() {
+        let mut unicorn = MockUnicorn::<4, 3>::new();
+        let mut graphics = UnicornGraphics::<4, 3>::new();
+        graphics.set_pixel(Point::new(2, 1), Rgb888::RED);
+
+        unicorn.set_pixels(&graphics);
+        graphics.set_pixel(Point::new(0, 0), Rgb888::BLUE);
+
+        assert_eq!(unicorn.frame_count(), 1);
+        assert_eq!(
+            unicorn.graphics().get_item(Point::new(2, 1)),
+            Some(Rgb888::RED)
+        );
+        assert_eq!(
+            unicorn.graphics().get_item(Point::new(0, 0)),
+            Some(Rgb888::BLACK)
+        );
+
+        unicorn.set_pixels(&graphics);
+        assert_eq!(unicorn.frame_count(), 2);
+        assert_eq!(
+            unicorn.graphics().get_item(Point::new(0, 0)),
+            Some(Rgb888::BLUE)
+        );
+    }
+
+    #[test]
+    fn buttons_are_held_until_released() {
+        let mut unicorn = MockUnicorn::<4, 3>::new();
+        assert!(!unicorn.is_button_pressed(UnicornButtons::SwitchA));
+
+        unicorn.press(UnicornButtons::SwitchA);
+        assert!(unicorn.is_button_pressed(UnicornButtons::SwitchA));
+        assert!(!unicorn.is_button_pressed(UnicornButtons::SwitchB));
+
+        unicorn.release(UnicornButtons::SwitchA);
+        assert!(!unicorn.is_button_pressed(UnicornButtons::SwitchA));
+    }
+
+    #[test]
+    fn light_level_is_set() {
+        let mut unicorn = MockUnicorn::<4, 3>::new();
+        assert_eq!(unicorn.light_level(), None);
+
+        unicorn.set_light_level(Some(1200));
+        assert_eq!(unicorn.light_level(), Some(1200));
+
+        unicorn.set_light_level(None);
+        assert_eq!(unicorn.light_level(), None);
+    }
+}
@@ -0,0 +1,221 @@
+//! Generates the built in bitmap fonts from the public domain X11 misc-fixed fonts
+//! included with embedded graphics.
+//!
+//! cargo run --example generate_fonts > src/font/fonts.rs
+
+use embedded_graphics::{
+    mono_font::{iso_8859_1, MonoFont, MonoTextStyle},
+    text::{Baseline, Text},
+    Drawable,
+};
+use embedded_graphics_core::{
+    pixelcolor::Rgb888,
+    prelude::{Point, RgbColor},
+};
+use unicorn_graphics::UnicornGraphics;
+
+/// Printable characters of Latin-1.
+fn characters() -> impl Iterator<Item = char> {
+    (0x20_u8..=0x7e).chain(0xa0..=0xff).map(char::from)
+}
+
+/// Left hand characters checked for kerning in the proportional font.
+const KERNING_LEFT: &str = "FLPTVWYfrtv\"'";
+
+/// Right hand characters checked for kerning in the proportional font.
+const KERNING_RIGHT: &str = "AJTVWYacdegjmnopqrsuvwxyz.,\"'";
+
+/// Width of the space character in the proportional font.
+const PROPORTIONAL_SPACE_WIDTH: usize = 2;
+
+struct Font {
+    name: &'static str,
+    doc: &'static str,
+    height: u32,
+    baseline: u32,
+    letter_spacing: u32,
+    glyphs: Vec<(char, Vec<u16>)>,
+    kerning: Vec<(char, char, i8)>,
+}
+
+/// Render a character of the font, returning the pixels of each column of its cell.
+fn render(font: &MonoFont, character: char) -> Vec<u16> {
+    let mut graphics = UnicornGraphics::<16, 16>::new();
+    let style = MonoTextStyle::new(font, Rgb888::WHITE);
+    let mut text = [0; 4];
+
+    Text::with_baseline(
+        character.encode_utf8(&mut text),
+        Point::zero(),
+        style,
+        Baseline::Top,
+    )
+    .draw(&mut graphics)
+    .unwrap();
+
+    (0..font.character_size.width)
+        .map(|x| {
+            (0..font.character_size.height).fold(0, |column, y| {
+                if graphics.is_colored(Point::new(x as i32, y as i32)) {
+                    column | (1 << y)
+                } else {
+                    column
+                }
+            })
+        })
+        .collect()
+}
+
+/// Create a monospaced font from the mono font.
+/// The last column of the cell is used as spacing, unless a glyph draws into it.
+fn monospaced(font: &MonoFont, name: &'static str, doc: &'static str) -> Font {
+    let mut glyphs: Vec<_> = characters().map(|c| (c, render(font, c))).collect();
+
+    let last_column_used = glyphs.iter().any(|(_, columns)| columns.last() != Some(&0));
+    let letter_spacing = if last_column_used {
+        0
+    } else {
+        for (_, columns) in glyphs.iter_mut() {
+            columns.pop();
+        }
+        1
+    };
+
+    Font {
+        name,
+        doc,
+        height: font.character_size.height,
+        baseline: font.baseline,
+        letter_spacing,
+        glyphs,
+        kerning: Vec::new(),
+    }
+}
+
+/// Create a proportional font from the mono font, by removing the empty columns each side of every glyph.
+fn proportional(font: &MonoFont, name: &'static str, doc: &'static str) -> Font {
+    let glyphs: Vec<_> = characters()
+        .map(|c| {
+            let columns = render(font, c);
+            let start = columns.iter().position(|&column| column != 0);
+            let end = columns.iter().rposition(|&column| column != 0);
+
+            match (start, end) {
+                (Some(start), Some(end)) => (c, columns[start..=end].to_vec()),
+                _ => (c, vec![0; PROPORTIONAL_SPACE_WIDTH]),
+            }
+        })
+        .collect();
+
+    let mut font = Font {
+        name,
+        doc,
+        height: font.character_size.height,
+        baseline: font.baseline,
+        letter_spacing: 1,
+        glyphs,
+        kerning: Vec::new(),
+    };
+    font.kerning = kerning(&font);
+    font
+}
+
+/// Find the pairs that can be moved one pixel closer without any of their pixels touching.
+fn kerning(font: &Font) -> Vec<(char, char, i8)> {
+    let glyph = |c: char| &font.glyphs.iter().find(|(g, _)| *g == c).unwrap().1;
+
+    let mut pairs = Vec::new();
+    for left in KERNING_LEFT.chars() {
+        for right in KERNING_RIGHT.chars() {
+            let left_columns = glyph(left);
+            let right_columns = glyph(right);
+
+            // with the pair one pixel closer, the right glyph starts straight after the left glyph
+            let last = *left_columns.last().unwrap();
+            let first = *right_columns.first().unwrap();
+            let spread = last | (last << 1) | (last >> 1);
+
+            if first & spread == 0 && !right_columns.iter().all(|&c| c == 0) {
+                pairs.push((left, right, -1));
+            }
+        }
+    }
+
+    pairs.sort();
+    pairs
+}
+
+fn print_font(font: &Font) {
+    let glyphs_name = format!("GLYPHS_{}", font.name.trim_start_matches("FONT_"));
+    let kerning_name = format!("KERNING_{}", font.name.trim_start_matches("FONT_"));
+
+    println!();
+    println!("/// {}", font.doc);
+    println!("pub const {}: BitmapFont = BitmapFont {{", font.name);
+    println!("    height: {},", font.height);
+    println!("    baseline: {},", font.baseline);
+    println!("    letter_spacing: {},", font.letter_spacing);
+    println!("    glyphs: {glyphs_name},");
+    println!("    kerning: {kerning_name},");
+    println!("    replacement: '?',");
+    println!("}};");
+
+    println!();
+    println!("const {glyphs_name}: &[Glyph] = &[");
+    for (character, columns) in font.glyphs.iter() {
+        let columns: Vec<_> = columns.iter().map(|c| format!("0x{c:03x}")).collect();
+        println!(
+            "    Glyph::new({:?}, &[{}]),",
+            character,
+            columns.join(", ")
+        );
+    }
+    println!("];");
+
+    println!();
+    if font.kerning.is_empty() {
+        println!("const {kerning_name}: &[KerningPair] = &[];");
+        return;
+    }
+
+    println!("const {kerning_name}: &[KerningPair] = &[");
+    for (left, right, offset) in font.kerning.iter() {
+        println!("    KerningPair::new({left:?}, {right:?}, {offset}),");
+    }
+    println!("];");
+}
+
+fn main() {
+    let fonts = [
+        monospaced(
+            &iso_8859_1::FONT_4X6,
+            "FONT_3X5",
+            "Monospaced font with 3×5 pixel capitals and a one pixel descender.",
+        ),
+        monospaced(
+            &iso_8859_1::FONT_5X7,
+            "FONT_4X7",
+            "Monospaced font with 4×6 pixel capitals and a one pixel descender.",
+        ),
+        monospaced(
+            &iso_8859_1::FONT_6X9,
+            "FONT_5X9",
+            "Monospaced font with 5×6 pixel capitals, room for accents above and a two pixel descender.",
+        ),
+        proportional(
+            &iso_8859_1::FONT_5X7,
+            "FONT_PROPORTIONAL",
+            "Proportional font with the glyphs of [`FONT_4X7`], fitting the most text on the display.",
+        ),
+    ];
+
+    println!("//! Generated by `cargo run --example generate_fonts > src/font/fonts.rs`, do not edit by hand.");
+    println!("//!");
+    println!("//! Glyphs are taken from the public domain X11 misc-fixed fonts.");
+    println!();
+    println!("use super::{{BitmapFont, Glyph, KerningPair}};");
+
+    for font in fonts.iter() {
+        print_font(font);
+    }
+}
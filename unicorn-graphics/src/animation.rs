@@ -0,0 +1,461 @@
+use embedded_graphics_core::{pixelcolor::Rgb888, prelude::Point};
+
+use crate::blend::lerp;
+
+/// How progress between two keyframes is shaped over time.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Easing {
+    /// Constant speed.
+    #[default]
+    Linear,
+
+    /// Start slowly and speed up.
+    EaseIn,
+
+    /// Start quickly and slow down.
+    EaseOut,
+
+    /// Start and end slowly, fastest in the middle.
+    EaseInOut,
+
+    /// Like [`Easing::EaseIn`], but more pronounced.
+    CubicIn,
+
+    /// Like [`Easing::EaseOut`], but more pronounced.
+    CubicOut,
+
+    /// Like [`Easing::EaseInOut`], but more pronounced.
+    CubicInOut,
+
+    /// Hold the value until the next keyframe, for frame by frame animation.
+    Step,
+}
+
+impl Easing {
+    /// Apply the easing to the progress between two keyframes, where 0 is the start and 255 the end.
+    pub fn apply(&self, progress: u8) -> u8 {
+        let t = progress as u32;
+        let inverse = 255 - t;
+
+        let eased = match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t / 255,
+            Easing::EaseOut => 255 - inverse * inverse / 255,
+            Easing::EaseInOut if t < 128 => 2 * t * t / 255,
+            Easing::EaseInOut => 255 - 2 * inverse * inverse / 255,
+            Easing::CubicIn => t * t * t / (255 * 255),
+            Easing::CubicOut => 255 - inverse * inverse * inverse / (255 * 255),
+            Easing::CubicInOut if t < 128 => 4 * t * t * t / (255 * 255),
+            Easing::CubicInOut => 255 - 4 * inverse * inverse * inverse / (255 * 255),
+            Easing::Step => 0,
+        };
+
+        eased.min(255) as u8
+    }
+}
+
+/// A value that can be interpolated between two keyframes.
+pub trait Tween: Copy {
+    /// Get the value between `from` and `to`, where an amount of 0 gives `from` and 255 gives `to`.
+    fn tween(from: Self, to: Self, amount: u8) -> Self;
+}
+
+impl Tween for i32 {
+    fn tween(from: Self, to: Self, amount: u8) -> Self {
+        from + (to - from) * amount as i32 / 255
+    }
+}
+
+impl Tween for u8 {
+    fn tween(from: Self, to: Self, amount: u8) -> Self {
+        i32::tween(from as i32, to as i32, amount) as u8
+    }
+}
+
+impl Tween for Point {
+    fn tween(from: Self, to: Self, amount: u8) -> Self {
+        Point::new(
+            i32::tween(from.x, to.x, amount),
+            i32::tween(from.y, to.y, amount),
+        )
+    }
+}
+
+impl Tween for Rgb888 {
+    fn tween(from: Self, to: Self, amount: u8) -> Self {
+        lerp(from, to, amount)
+    }
+}
+
+impl<A: Tween, B: Tween> Tween for (A, B) {
+    fn tween(from: Self, to: Self, amount: u8) -> Self {
+        (
+            A::tween(from.0, to.0, amount),
+            B::tween(from.1, to.1, amount),
+        )
+    }
+}
+
+impl<A: Tween, B: Tween, C: Tween> Tween for (A, B, C) {
+    fn tween(from: Self, to: Self, amount: u8) -> Self {
+        (
+            A::tween(from.0, to.0, amount),
+            B::tween(from.1, to.1, amount),
+            C::tween(from.2, to.2, amount),
+        )
+    }
+}
+
+/// A value at a point in time of a [`Timeline`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Keyframe<T> {
+    /// Time of the keyframe from the start of the timeline, in milliseconds.
+    pub time_ms: u32,
+
+    pub value: T,
+
+    /// Easing used from this keyframe to the next.
+    pub easing: Easing,
+}
+
+impl<T> Keyframe<T> {
+    /// Create a new keyframe, eased linearly to the next keyframe.
+    pub const fn new(time_ms: u32, value: T) -> Self {
+        Self {
+            time_ms,
+            value,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Create a new keyframe with the easing used to the next keyframe.
+    pub const fn with_easing(time_ms: u32, value: T, easing: Easing) -> Self {
+        Self {
+            time_ms,
+            value,
+            easing,
+        }
+    }
+}
+
+/// How a timeline plays once it reaches the end.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum PlaybackMode {
+    /// Play once, then hold the last keyframe.
+    Once,
+
+    /// Play from the start again.
+    #[default]
+    Loop,
+
+    /// Play backwards to the start, then forwards again.
+    PingPong,
+}
+
+/// Plays keyframes over time, tweening between them or stepping through them as frames.
+///
+/// Time is passed in with [`Self::tick`], so a timeline works with any timer
+/// and can be checked on a host machine.
+#[derive(Clone, Debug)]
+pub struct Timeline<'a, T> {
+    keyframes: &'a [Keyframe<T>],
+
+    /// How the timeline plays once it reaches the end.
+    pub mode: PlaybackMode,
+
+    /// Length of the timeline in milliseconds.
+    duration_ms: u32,
+
+    /// Time played since the start, up to twice the duration in ping pong mode,
+    /// so is wider than the duration to not overflow.
+    elapsed_ms: u64,
+
+    finished: bool,
+}
+
+impl<'a, T> Timeline<'a, T> {
+    /// Create a new looping timeline of the keyframes, which must be sorted by time.
+    /// The timeline ends at the last keyframe.
+    pub fn new(keyframes: &'a [Keyframe<T>]) -> Self {
+        Self {
+            keyframes,
+            mode: PlaybackMode::Loop,
+            duration_ms: keyframes.last().map_or(0, |keyframe| keyframe.time_ms),
+            elapsed_ms: 0,
+            finished: false,
+        }
+    }
+
+    /// Set the mode, restarting the timeline.
+    pub fn with_mode(mut self, mode: PlaybackMode) -> Self {
+        self.mode = mode;
+        self.reset();
+        self
+    }
+
+    /// Set the length of the timeline, holding the last keyframe from its time to the end.
+    /// Useful for frames, so the last frame is shown for as long as the others.
+    pub fn with_duration(mut self, duration_ms: u32) -> Self {
+        self.duration_ms = duration_ms;
+        self.reset();
+        self
+    }
+
+    /// Length of the timeline in milliseconds.
+    pub fn duration_ms(&self) -> u32 {
+        self.duration_ms
+    }
+
+    /// Current position within the timeline in milliseconds.
+    pub fn position_ms(&self) -> u32 {
+        let duration_ms = self.duration_ms as u64;
+
+        if self.elapsed_ms > duration_ms {
+            // playing backwards in ping pong mode
+            (2 * duration_ms - self.elapsed_ms) as u32
+        } else {
+            self.elapsed_ms as u32
+        }
+    }
+
+    /// Move to the given position within the timeline in milliseconds, playing forwards.
+    pub fn seek(&mut self, position_ms: u32) {
+        self.elapsed_ms = position_ms.min(self.duration_ms) as u64;
+        self.finished = false;
+    }
+
+    /// Check if the timeline has reached the end in [`PlaybackMode::Once`].
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Move the timeline back to the start.
+    pub fn reset(&mut self) {
+        self.elapsed_ms = 0;
+        self.finished = false;
+    }
+
+    /// Move the timeline on by the time elapsed, in milliseconds, since the last tick.
+    pub fn tick(&mut self, elapsed_ms: u32) {
+        if self.finished {
+            return;
+        }
+
+        if self.duration_ms == 0 {
+            self.finished = self.mode == PlaybackMode::Once;
+            return;
+        }
+
+        let duration_ms = self.duration_ms as u64;
+        let elapsed_ms = self.elapsed_ms + elapsed_ms as u64;
+
+        self.elapsed_ms = match self.mode {
+            PlaybackMode::Once => {
+                self.finished = elapsed_ms >= duration_ms;
+                elapsed_ms.min(duration_ms)
+            }
+            PlaybackMode::Loop => elapsed_ms % duration_ms,
+            PlaybackMode::PingPong => elapsed_ms % (2 * duration_ms),
+        };
+    }
+
+    /// Get the index of the keyframe at or before the current position.
+    pub fn index(&self) -> usize {
+        let position = self.position_ms();
+
+        self.keyframes
+            .iter()
+            .rposition(|keyframe| keyframe.time_ms <= position)
+            .unwrap_or(0)
+    }
+
+    /// Get the keyframe at or before the current position, providing the timeline has keyframes.
+    /// Useful for values that cannot be tweened, such as frames of graphics.
+    pub fn current(&self) -> Option<&'a Keyframe<T>> {
+        self.keyframes.get(self.index())
+    }
+}
+
+impl<T: Tween> Timeline<'_, T> {
+    /// Get the value at the current position, tweened between the keyframes either side.
+    /// The first and last keyframes are held before and after them.
+    pub fn value(&self) -> Option<T> {
+        let index = self.index();
+        let from = self.keyframes.get(index)?;
+
+        let to = match self.keyframes.get(index + 1) {
+            Some(to) => to,
+            None => return Some(from.value),
+        };
+
+        let position = self.position_ms();
+        if position < from.time_ms {
+            return Some(from.value);
+        }
+
+        let span = to.time_ms.saturating_sub(from.time_ms);
+        if span == 0 {
+            return Some(to.value);
+        }
+
+        let progress = ((position - from.time_ms) as u64 * 255 / span as u64) as u8;
+        Some(T::tween(from.value, to.value, from.easing.apply(progress)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EASINGS: [Easing; 7] = [
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+    ];
+
+    const KEYFRAMES: [Keyframe<i32>; 3] = [
+        Keyframe::new(0, 0),
+        Keyframe::with_easing(255, 255, Easing::Step),
+        Keyframe::new(510, 1000),
+    ];
+
+    #[test]
+    fn easing_endpoints() {
+        for easing in EASINGS {
+            assert_eq!(easing.apply(0), 0, "{easing:?}");
+            assert_eq!(easing.apply(255), 255, "{easing:?}");
+        }
+    }
+
+    #[test]
+    fn easing_never_goes_backwards() {
+        for easing in EASINGS {
+            for progress in 0..255 {
+                assert!(
+                    easing.apply(progress) <= easing.apply(progress + 1),
+                    "{easing:?} at {progress}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn easing_midpoints() {
+        assert_eq!(Easing::Linear.apply(128), 128);
+        assert_eq!(Easing::EaseIn.apply(128), 64);
+        assert_eq!(Easing::EaseOut.apply(128), 192);
+        assert_eq!(Easing::EaseInOut.apply(127), 126);
+        assert_eq!(Easing::EaseInOut.apply(128), 129);
+        assert_eq!(Easing::CubicIn.apply(128), 32);
+        assert_eq!((0..=255).map(|t| Easing::Step.apply(t)).max(), Some(0));
+    }
+
+    #[test]
+    fn once_holds_the_last_keyframe() {
+        let mut timeline = Timeline::new(&KEYFRAMES).with_mode(PlaybackMode::Once);
+        assert_eq!(timeline.duration_ms(), 510);
+        assert_eq!(timeline.value(), Some(0));
+
+        timeline.tick(50);
+        assert_eq!(timeline.position_ms(), 50);
+        assert_eq!(timeline.value(), Some(50));
+        assert!(!timeline.is_finished());
+
+        // the step easing holds the value until the next keyframe
+        timeline.tick(250);
+        assert_eq!(timeline.index(), 1);
+        assert_eq!(timeline.value(), Some(255));
+
+        timeline.tick(300);
+        assert!(timeline.is_finished());
+        assert_eq!(timeline.position_ms(), 510);
+        assert_eq!(timeline.value(), Some(1000));
+
+        timeline.tick(1000);
+        assert_eq!(timeline.position_ms(), 510);
+
+        timeline.reset();
+        assert_eq!(timeline.position_ms(), 0);
+        assert!(!timeline.is_finished());
+    }
+
+    #[test]
+    fn loop_wraps_to_the_start() {
+        let mut timeline = Timeline::new(&KEYFRAMES);
+
+        timeline.tick(600);
+        assert_eq!(timeline.position_ms(), 90);
+        assert_eq!(timeline.value(), Some(90));
+
+        timeline.tick(420);
+        assert_eq!(timeline.position_ms(), 0);
+        assert!(!timeline.is_finished());
+    }
+
+    #[test]
+    fn ping_pong_plays_backwards() {
+        let mut timeline = Timeline::new(&KEYFRAMES).with_mode(PlaybackMode::PingPong);
+
+        timeline.tick(400);
+        assert_eq!(timeline.position_ms(), 400);
+
+        timeline.tick(200);
+        assert_eq!(timeline.position_ms(), 420);
+        assert_eq!(timeline.value(), Some(255));
+
+        timeline.tick(400);
+        assert_eq!(timeline.position_ms(), 20);
+        assert_eq!(timeline.value(), Some(20));
+
+        timeline.tick(30);
+        assert_eq!(timeline.position_ms(), 10);
+    }
+
+    #[test]
+    fn long_durations_do_not_overflow() {
+        for mode in [
+            PlaybackMode::Once,
+            PlaybackMode::Loop,
+            PlaybackMode::PingPong,
+        ] {
+            let mut timeline = Timeline::new(&KEYFRAMES)
+                .with_mode(mode)
+                .with_duration(u32::MAX);
+
+            timeline.tick(u32::MAX - 10);
+            assert_eq!(timeline.position_ms(), u32::MAX - 10);
+
+            timeline.tick(20);
+            let expected = match mode {
+                PlaybackMode::Once => u32::MAX,
+                PlaybackMode::Loop => 10,
+                PlaybackMode::PingPong => u32::MAX - 10,
+            };
+            assert_eq!(timeline.position_ms(), expected, "{mode:?}");
+
+            timeline.tick(u32::MAX);
+            let expected = match mode {
+                PlaybackMode::Once => u32::MAX,
+                _ => 10,
+            };
+            assert_eq!(timeline.position_ms(), expected, "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn seek_and_empty_timelines() {
+        let mut timeline = Timeline::new(&KEYFRAMES);
+        timeline.seek(1000);
+        assert_eq!(timeline.position_ms(), 510);
+
+        let mut empty = Timeline::<i32>::new(&[]).with_mode(PlaybackMode::Once);
+        empty.tick(10);
+        assert!(empty.is_finished());
+        assert_eq!(empty.value(), None);
+        assert!(empty.current().is_none());
+    }
+}
@@ -0,0 +1,243 @@
+use embedded_graphics_core::{
+    pixelcolor::Rgb888,
+    prelude::{DrawTarget, OriginDimensions, RgbColor, Size},
+    Pixel,
+};
+
+use crate::UnicornGraphics;
+
+/// How a color being drawn is combined with the color already in the buffer.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum BlendMode {
+    /// The new color replaces the existing color.
+    #[default]
+    Normal,
+
+    /// The colors are added together, saturating at full brightness. Useful for glow.
+    Add,
+
+    /// The colors are multiplied together, always darkening. Useful for shading.
+    Multiply,
+
+    /// The inverse of the colors are multiplied together, always lightening.
+    Screen,
+
+    /// The brightest of each channel is kept.
+    Max,
+}
+
+impl BlendMode {
+    /// Blend the source color onto the destination color with the given alpha,
+    /// where 0 leaves the destination as is and 255 fully applies the blend mode.
+    pub fn blend(&self, dst: Rgb888, src: Rgb888, alpha: u8) -> Rgb888 {
+        if alpha == 0 {
+            return dst;
+        }
+
+        let blended = Rgb888::new(
+            self.blend_channel(dst.r(), src.r()),
+            self.blend_channel(dst.g(), src.g()),
+            self.blend_channel(dst.b(), src.b()),
+        );
+
+        if alpha == u8::MAX {
+            return blended;
+        }
+
+        lerp(dst, blended, alpha)
+    }
+
+    fn blend_channel(&self, dst: u8, src: u8) -> u8 {
+        match self {
+            BlendMode::Normal => src,
+            BlendMode::Add => dst.saturating_add(src),
+            BlendMode::Multiply => div_255(dst as u32 * src as u32),
+            BlendMode::Screen => 255 - div_255((255 - dst as u32) * (255 - src as u32)),
+            BlendMode::Max => dst.max(src),
+        }
+    }
+}
+
+/// A color with an alpha value, where 0 is fully transparent and 255 is fully opaque.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba {
+    /// Create a new color from r, g, b and alpha values.
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Create a new color from an `Rgb888` color and alpha value.
+    pub fn from_rgb(color: Rgb888, a: u8) -> Self {
+        Self::new(color.r(), color.g(), color.b(), a)
+    }
+
+    /// Get the color without the alpha value.
+    pub const fn rgb(&self) -> Rgb888 {
+        Rgb888::new(self.r, self.g, self.b)
+    }
+}
+
+impl From<Rgb888> for Rgba {
+    fn from(value: Rgb888) -> Self {
+        Self::from_rgb(value, u8::MAX)
+    }
+}
+
+/// A draw target that blends everything drawn onto the inner graphics,
+/// so embedded graphics primitives and text can be drawn with transparency.
+/// Created via [`UnicornGraphics::blended`].
+pub struct BlendedGraphics<'a, const W: usize, const H: usize> {
+    graphics: &'a mut UnicornGraphics<W, H>,
+
+    /// Alpha applied to every pixel drawn.
+    pub alpha: u8,
+
+    /// Blend mode applied to every pixel drawn.
+    pub mode: BlendMode,
+}
+
+impl<'a, const W: usize, const H: usize> BlendedGraphics<'a, W, H> {
+    /// Create a new blended draw target over the graphics.
+    pub fn new(graphics: &'a mut UnicornGraphics<W, H>, alpha: u8, mode: BlendMode) -> Self {
+        Self {
+            graphics,
+            alpha,
+            mode,
+        }
+    }
+}
+
+impl<'a, const W: usize, const H: usize> DrawTarget for BlendedGraphics<'a, W, H> {
+    type Color = Rgb888;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(pos, color) in pixels {
+            self.graphics.blend_pixel(pos, color, self.alpha, self.mode);
+        }
+        Ok(())
+    }
+}
+
+impl<'a, const W: usize, const H: usize> OriginDimensions for BlendedGraphics<'a, W, H> {
+    fn size(&self) -> Size {
+        self.graphics.size()
+    }
+}
+
+/// Linearly interpolate from one color to another, where an amount of 255 gives the `to` color.
+pub(crate) fn lerp(from: Rgb888, to: Rgb888, amount: u8) -> Rgb888 {
+    Rgb888::new(
+        lerp_channel(from.r(), to.r(), amount),
+        lerp_channel(from.g(), to.g(), amount),
+        lerp_channel(from.b(), to.b(), amount),
+    )
+}
+
+fn lerp_channel(from: u8, to: u8, amount: u8) -> u8 {
+    let amount = amount as u32;
+    div_255(from as u32 * (255 - amount) + to as u32 * amount)
+}
+
+/// Divide by 255 with rounding, for values up to `255 * 255`.
+fn div_255(value: u32) -> u8 {
+    ((value + 128 + ((value + 128) >> 8)) >> 8) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::prelude::Point;
+
+    use super::*;
+
+    const DST: Rgb888 = Rgb888::new(200, 128, 0);
+    const SRC: Rgb888 = Rgb888::new(100, 128, 255);
+
+    #[test]
+    fn blend_modes_at_full_alpha() {
+        let blend = |mode: BlendMode| mode.blend(DST, SRC, u8::MAX);
+
+        assert_eq!(blend(BlendMode::Normal), SRC);
+        assert_eq!(blend(BlendMode::Add), Rgb888::new(255, 255, 255));
+        assert_eq!(blend(BlendMode::Multiply), Rgb888::new(78, 64, 0));
+        assert_eq!(blend(BlendMode::Screen), Rgb888::new(222, 192, 255));
+        assert_eq!(blend(BlendMode::Max), Rgb888::new(200, 128, 255));
+    }
+
+    #[test]
+    fn zero_alpha_leaves_the_destination() {
+        for mode in [
+            BlendMode::Normal,
+            BlendMode::Add,
+            BlendMode::Multiply,
+            BlendMode::Screen,
+            BlendMode::Max,
+        ] {
+            assert_eq!(mode.blend(DST, SRC, 0), DST);
+        }
+    }
+
+    #[test]
+    fn partial_alpha_mixes_with_the_destination() {
+        let half = BlendMode::Normal.blend(Rgb888::BLACK, Rgb888::WHITE, 128);
+        assert_eq!(half, Rgb888::new(128, 128, 128));
+
+        let glow = BlendMode::Add.blend(Rgb888::new(100, 0, 0), Rgb888::new(100, 0, 0), 128);
+        assert_eq!(glow, Rgb888::new(150, 0, 0));
+    }
+
+    #[test]
+    fn lerp_reaches_both_ends() {
+        assert_eq!(lerp(DST, SRC, 0), DST);
+        assert_eq!(lerp(DST, SRC, 255), SRC);
+    }
+
+    #[test]
+    fn div_255_rounds_to_nearest() {
+        for value in 0..=255 * 255 {
+            assert_eq!(div_255(value) as u32, (value + 127) / 255, "{value}");
+        }
+    }
+
+    #[test]
+    fn rgba_conversions() {
+        let color = Rgba::from(DST);
+        assert_eq!(color, Rgba::new(200, 128, 0, 255));
+        assert_eq!(color.rgb(), DST);
+        assert_eq!(Rgba::from_rgb(SRC, 7), Rgba::new(100, 128, 255, 7));
+    }
+
+    #[test]
+    fn blended_graphics_blend_everything_drawn() {
+        let mut graphics = UnicornGraphics::<2, 1>::new();
+        graphics.set_pixel(Point::new(0, 0), Rgb888::new(100, 0, 0));
+
+        graphics
+            .blended(u8::MAX, BlendMode::Add)
+            .draw_iter([
+                Pixel(Point::new(0, 0), Rgb888::new(100, 50, 0)),
+                Pixel(Point::new(1, 0), Rgb888::new(0, 0, 30)),
+                Pixel(Point::new(5, 5), Rgb888::WHITE),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            graphics.get_item(Point::new(0, 0)),
+            Some(Rgb888::new(200, 50, 0))
+        );
+        assert_eq!(
+            graphics.get_item(Point::new(1, 0)),
+            Some(Rgb888::new(0, 0, 30))
+        );
+    }
+}
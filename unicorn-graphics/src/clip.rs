@@ -0,0 +1,530 @@
+//! A compact binary format for animations, decoded a frame at a time straight from its data.
+//!
+//! All values are little endian. The file starts with a header:
+//!
+//! | Offset | Size | Value                                                        |
+//! |--------|------|--------------------------------------------------------------|
+//! | 0      | 4    | `UCLP`                                                       |
+//! | 4      | 1    | Version, currently 1                                         |
+//! | 5      | 1    | Flags, bit 0 when there is a palette, bit 1 when it loops    |
+//! | 6      | 2    | Width in pixels                                              |
+//! | 8      | 2    | Height in pixels                                             |
+//! | 10     | 2    | Number of frames                                             |
+//!
+//! With a palette, the header is followed by the number of colors as 2 bytes,
+//! from 1 to 256, then the red, green and blue of each color.
+//!
+//! Each frame then has a header:
+//!
+//! | Offset | Size | Value                                                        |
+//! |--------|------|--------------------------------------------------------------|
+//! | 0      | 2    | Time to show the frame for in milliseconds                   |
+//! | 2      | 1    | Flags, bit 0 for a key frame, which starts from black rather than the frame before |
+//! | 3      | 4    | Length of the ops in bytes                                   |
+//!
+//! Followed by ops that change the pixels of the frame before, row by row from the top left.
+//! Each op is a byte with the kind in the top 2 bits and the number of pixels, minus 1, in the rest:
+//!
+//! - `00` skip: leave the pixels as they are
+//! - `01` run: a single color for all of the pixels
+//! - `10` literal: a color for each pixel
+//!
+//! Colors are a palette index of 1 byte, or the red, green and blue as 3 bytes without a palette.
+
+use core::fmt;
+
+use embedded_graphics_core::{pixelcolor::Rgb888, prelude::Point};
+
+use crate::UnicornGraphics;
+
+/// Signature at the start of every clip.
+pub const CLIP_MAGIC: [u8; 4] = *b"UCLP";
+
+/// Version of the format written by encoders.
+pub const CLIP_VERSION: u8 = 1;
+
+/// Size of the header before the palette.
+pub const CLIP_HEADER_LEN: usize = 12;
+
+/// Size of the header of each frame.
+pub const CLIP_FRAME_HEADER_LEN: usize = 7;
+
+/// Largest number of pixels changed by a single op.
+pub const CLIP_MAX_OP_PIXELS: usize = 64;
+
+/// Flag of the header set when the clip has a palette.
+pub const CLIP_FLAG_PALETTE: u8 = 0x01;
+
+/// Flag of the header set when the clip loops.
+pub const CLIP_FLAG_LOOP: u8 = 0x02;
+
+/// Flag of a frame set when it is a key frame.
+pub const CLIP_FRAME_KEY: u8 = 0x01;
+
+/// Kind of op that leaves pixels as they are.
+pub const CLIP_OP_SKIP: u8 = 0b00;
+
+/// Kind of op that sets pixels to a single color.
+pub const CLIP_OP_RUN: u8 = 0b01;
+
+/// Kind of op that sets pixels to a color each.
+pub const CLIP_OP_LITERAL: u8 = 0b10;
+
+/// Errors that can occur when reading a clip.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ClipError {
+    /// The data does not start with [`CLIP_MAGIC`].
+    InvalidSignature,
+
+    /// The clip was written by a newer version of the format.
+    UnsupportedVersion,
+
+    /// The data ends before the clip does.
+    Truncated,
+
+    /// The clip data is not valid.
+    Corrupt,
+}
+
+impl fmt::Display for ClipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClipError::InvalidSignature => write!(f, "invalid signature"),
+            ClipError::UnsupportedVersion => write!(f, "unsupported clip version"),
+            ClipError::Truncated => write!(f, "clip data is truncated"),
+            ClipError::Corrupt => write!(f, "clip data is corrupt"),
+        }
+    }
+}
+
+/// An animation in the clip format, read straight from its data such as from `include_bytes!`.
+#[derive(Copy, Clone, Debug)]
+pub struct Clip<'a> {
+    data: &'a [u8],
+    width: u16,
+    height: u16,
+    frame_count: u16,
+    looping: bool,
+    palette: Option<&'a [u8]>,
+
+    /// Offset of the first frame.
+    first_frame: usize,
+}
+
+impl<'a> Clip<'a> {
+    /// Read the clip from its data, checking every frame so it can be played without errors.
+    pub fn new(data: &'a [u8]) -> Result<Self, ClipError> {
+        let header = data.get(..CLIP_HEADER_LEN).ok_or(ClipError::Truncated)?;
+        if header[0..4] != CLIP_MAGIC {
+            return Err(ClipError::InvalidSignature);
+        }
+        if !(1..=CLIP_VERSION).contains(&header[4]) {
+            return Err(ClipError::UnsupportedVersion);
+        }
+
+        let flags = header[5];
+        let width = u16::from_le_bytes([header[6], header[7]]);
+        let height = u16::from_le_bytes([header[8], header[9]]);
+        let frame_count = u16::from_le_bytes([header[10], header[11]]);
+
+        let (palette, first_frame) = if flags & CLIP_FLAG_PALETTE != 0 {
+            let count = data
+                .get(CLIP_HEADER_LEN..CLIP_HEADER_LEN + 2)
+                .ok_or(ClipError::Truncated)?;
+            let count = u16::from_le_bytes([count[0], count[1]]) as usize;
+            if !(1..=256).contains(&count) {
+                return Err(ClipError::Corrupt);
+            }
+
+            let start = CLIP_HEADER_LEN + 2;
+            let palette = data
+                .get(start..start + count * 3)
+                .ok_or(ClipError::Truncated)?;
+            (Some(palette), start + count * 3)
+        } else {
+            (None, CLIP_HEADER_LEN)
+        };
+
+        let clip = Self {
+            data,
+            width,
+            height,
+            frame_count,
+            looping: flags & CLIP_FLAG_LOOP != 0,
+            palette,
+            first_frame,
+        };
+
+        let mut offset = first_frame;
+        for _ in 0..frame_count {
+            let frame = clip.frame(offset)?;
+            clip.apply(&frame, |_, _| {})?;
+            offset = frame.next;
+        }
+
+        Ok(clip)
+    }
+
+    /// Width of the clip in pixels.
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Height of the clip in pixels.
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Number of frames of the clip.
+    pub fn frame_count(&self) -> u16 {
+        self.frame_count
+    }
+
+    /// Check if the clip goes back to the first frame after the last.
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Read the header of the frame at the offset.
+    fn frame(&self, offset: usize) -> Result<RawFrame<'a>, ClipError> {
+        let header = self
+            .data
+            .get(offset..offset + CLIP_FRAME_HEADER_LEN)
+            .ok_or(ClipError::Truncated)?;
+
+        let len = u32::from_le_bytes([header[3], header[4], header[5], header[6]]) as usize;
+        let start = offset + CLIP_FRAME_HEADER_LEN;
+        let ops = self
+            .data
+            .get(start..start.checked_add(len).ok_or(ClipError::Truncated)?)
+            .ok_or(ClipError::Truncated)?;
+
+        Ok(RawFrame {
+            duration_ms: u16::from_le_bytes([header[0], header[1]]),
+            key: header[2] & CLIP_FRAME_KEY != 0,
+            ops,
+            next: start + len,
+        })
+    }
+
+    /// Run the ops of the frame, calling the function with the index of each pixel changed and its color.
+    fn apply<F>(&self, frame: &RawFrame, mut f: F) -> Result<(), ClipError>
+    where
+        F: FnMut(usize, Rgb888),
+    {
+        let pixel_count = self.width as usize * self.height as usize;
+        let mut ops = frame.ops.iter().copied();
+        let mut index = 0;
+
+        while let Some(op) = ops.next() {
+            let count = (op & 0x3f) as usize + 1;
+            if index + count > pixel_count {
+                return Err(ClipError::Corrupt);
+            }
+
+            match op >> 6 {
+                CLIP_OP_SKIP => {}
+                CLIP_OP_RUN => {
+                    let color = self.color(&mut ops)?;
+                    for pixel in index..index + count {
+                        f(pixel, color);
+                    }
+                }
+                CLIP_OP_LITERAL => {
+                    for pixel in index..index + count {
+                        f(pixel, self.color(&mut ops)?);
+                    }
+                }
+                _ => return Err(ClipError::Corrupt),
+            }
+
+            index += count;
+        }
+
+        Ok(())
+    }
+
+    /// Read the next color of the ops.
+    fn color(&self, ops: &mut impl Iterator<Item = u8>) -> Result<Rgb888, ClipError> {
+        let mut next = || ops.next().ok_or(ClipError::Truncated);
+
+        match self.palette {
+            Some(palette) => {
+                let start = next()? as usize * 3;
+                let rgb = palette.get(start..start + 3).ok_or(ClipError::Corrupt)?;
+                Ok(Rgb888::new(rgb[0], rgb[1], rgb[2]))
+            }
+            None => Ok(Rgb888::new(next()?, next()?, next()?)),
+        }
+    }
+}
+
+/// A frame as stored in the clip.
+struct RawFrame<'a> {
+    duration_ms: u16,
+    key: bool,
+    ops: &'a [u8],
+
+    /// Offset of the frame after this one.
+    next: usize,
+}
+
+/// A frame drawn by a [`ClipPlayer`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ClipFrame {
+    /// Index of the frame within the clip.
+    pub index: u16,
+
+    /// Time to show the frame for before the next, in milliseconds.
+    pub duration_ms: u16,
+}
+
+/// Plays a [`Clip`] onto graphics a frame at a time, using the graphics as the only state.
+#[derive(Copy, Clone, Debug)]
+pub struct ClipPlayer<'a> {
+    clip: Clip<'a>,
+
+    /// Position of the top left of the clip on the graphics.
+    pub position: Point,
+
+    /// Offset of the next frame.
+    offset: usize,
+
+    /// Index of the next frame.
+    index: u16,
+}
+
+impl<'a> ClipPlayer<'a> {
+    /// Create a new player of the clip, drawing with its top left at the origin.
+    pub fn new(clip: Clip<'a>) -> Self {
+        Self {
+            clip,
+            position: Point::zero(),
+            offset: clip.first_frame,
+            index: 0,
+        }
+    }
+
+    /// Set the position of the top left of the clip on the graphics.
+    pub fn with_position(mut self, position: Point) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Get the clip being played.
+    pub fn clip(&self) -> &Clip<'a> {
+        &self.clip
+    }
+
+    /// Start playing from the first frame again.
+    pub fn reset(&mut self) {
+        self.offset = self.clip.first_frame;
+        self.index = 0;
+    }
+
+    /// Draw the next frame onto the graphics, which must still hold the frame before unless it is a key frame.
+    /// Returns `None` once the clip has finished, going back to the first frame when it loops.
+    pub fn next_frame<const W: usize, const H: usize>(
+        &mut self,
+        graphics: &mut UnicornGraphics<W, H>,
+    ) -> Option<ClipFrame> {
+        if self.index == self.clip.frame_count {
+            if !self.clip.looping || self.clip.frame_count == 0 {
+                return None;
+            }
+            self.reset();
+        }
+
+        let frame = self
+            .clip
+            .frame(self.offset)
+            .expect("frames are checked when the clip is read");
+        let width = self.clip.width as usize;
+
+        let pixel = |index: usize| {
+            self.position + Point::new((index % width) as i32, (index / width) as i32)
+        };
+
+        if frame.key {
+            let pixel_count = width * self.clip.height as usize;
+            for index in 0..pixel_count {
+                graphics.clear_pixel(pixel(index));
+            }
+        }
+
+        self.clip
+            .apply(&frame, |index, color| {
+                graphics.set_pixel(pixel(index), color)
+            })
+            .expect("frames are checked when the clip is read");
+
+        let drawn = ClipFrame {
+            index: self.index,
+            duration_ms: frame.duration_ms,
+        };
+
+        self.offset = frame.next;
+        self.index += 1;
+
+        Some(drawn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::prelude::RgbColor;
+
+    use super::*;
+
+    /// Data of a clip, built up in a fixed buffer.
+    struct ClipData {
+        bytes: [u8; 64],
+        len: usize,
+    }
+
+    impl ClipData {
+        fn extend(&mut self, bytes: &[u8]) {
+            self.bytes[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+            self.len += bytes.len();
+        }
+
+        fn as_bytes(&self) -> &[u8] {
+            &self.bytes[..self.len]
+        }
+    }
+
+    /// A clip of 4x2 pixels with a palette of black, red and green, played once.
+    fn clip_data(frames: &[(u16, u8, &[u8])]) -> ClipData {
+        let mut data = ClipData {
+            bytes: [0; 64],
+            len: 0,
+        };
+        data.extend(&CLIP_MAGIC);
+        data.extend(&[CLIP_VERSION, CLIP_FLAG_PALETTE, 4, 0, 2, 0]);
+        data.extend(&(frames.len() as u16).to_le_bytes());
+        data.extend(&[3, 0, 0, 0, 0, 255, 0, 0, 0, 255, 0]);
+
+        for (duration_ms, flags, ops) in frames {
+            data.extend(&duration_ms.to_le_bytes());
+            data.extend(&[*flags]);
+            data.extend(&(ops.len() as u32).to_le_bytes());
+            data.extend(ops);
+        }
+        data
+    }
+
+    fn op(kind: u8, count: u8) -> u8 {
+        (kind << 6) | (count - 1)
+    }
+
+    #[test]
+    fn plays_skips_runs_and_literals() {
+        let data = clip_data(&[
+            (100, CLIP_FRAME_KEY, &[op(CLIP_OP_RUN, 8), 1]),
+            (200, 0, &[op(CLIP_OP_SKIP, 5), op(CLIP_OP_LITERAL, 2), 2, 0]),
+        ]);
+        let clip = Clip::new(data.as_bytes()).unwrap();
+        assert_eq!((clip.width(), clip.height(), clip.frame_count()), (4, 2, 2));
+        assert!(!clip.is_looping());
+
+        let mut player = ClipPlayer::new(clip).with_position(Point::new(1, 1));
+        let mut graphics = UnicornGraphics::<6, 4>::new();
+
+        let frame = player.next_frame(&mut graphics).unwrap();
+        assert_eq!(
+            frame,
+            ClipFrame {
+                index: 0,
+                duration_ms: 100
+            }
+        );
+        assert_eq!(graphics.get_item(Point::new(1, 1)), Some(Rgb888::RED));
+        assert_eq!(graphics.get_item(Point::new(4, 2)), Some(Rgb888::RED));
+        assert_eq!(graphics.get_item(Point::new(0, 0)), Some(Rgb888::BLACK));
+
+        let frame = player.next_frame(&mut graphics).unwrap();
+        assert_eq!(
+            frame,
+            ClipFrame {
+                index: 1,
+                duration_ms: 200
+            }
+        );
+        assert_eq!(graphics.get_item(Point::new(1, 2)), Some(Rgb888::RED));
+        assert_eq!(graphics.get_item(Point::new(2, 2)), Some(Rgb888::GREEN));
+        assert_eq!(graphics.get_item(Point::new(3, 2)), Some(Rgb888::BLACK));
+        assert_eq!(graphics.get_item(Point::new(4, 2)), Some(Rgb888::RED));
+
+        assert_eq!(player.next_frame(&mut graphics), None);
+    }
+
+    #[test]
+    fn key_frame_starts_from_black() {
+        let data = clip_data(&[
+            (10, CLIP_FRAME_KEY, &[op(CLIP_OP_RUN, 8), 1]),
+            (
+                10,
+                CLIP_FRAME_KEY,
+                &[op(CLIP_OP_SKIP, 1), op(CLIP_OP_RUN, 1), 2],
+            ),
+        ]);
+        let mut player = ClipPlayer::new(Clip::new(data.as_bytes()).unwrap());
+        let mut graphics = UnicornGraphics::<4, 2>::new();
+
+        player.next_frame(&mut graphics).unwrap();
+        player.next_frame(&mut graphics).unwrap();
+
+        assert_eq!(graphics.get_item(Point::new(0, 0)), Some(Rgb888::BLACK));
+        assert_eq!(graphics.get_item(Point::new(1, 0)), Some(Rgb888::GREEN));
+        assert_eq!(graphics.get_item(Point::new(3, 1)), Some(Rgb888::BLACK));
+    }
+
+    #[test]
+    fn invalid_clips_are_rejected() {
+        let data = clip_data(&[(10, CLIP_FRAME_KEY, &[op(CLIP_OP_RUN, 8), 1])]);
+        let valid = data.as_bytes();
+        assert!(Clip::new(valid).is_ok());
+
+        let mut signature = clip_data(&[(10, CLIP_FRAME_KEY, &[op(CLIP_OP_RUN, 8), 1])]);
+        signature.bytes[0] = b'X';
+        assert_eq!(
+            Clip::new(signature.as_bytes()).err(),
+            Some(ClipError::InvalidSignature)
+        );
+
+        let mut version = clip_data(&[(10, CLIP_FRAME_KEY, &[op(CLIP_OP_RUN, 8), 1])]);
+        version.bytes[4] = CLIP_VERSION + 1;
+        assert_eq!(
+            Clip::new(version.as_bytes()).err(),
+            Some(ClipError::UnsupportedVersion)
+        );
+
+        version.bytes[4] = 0;
+        assert_eq!(
+            Clip::new(version.as_bytes()).err(),
+            Some(ClipError::UnsupportedVersion)
+        );
+
+        for len in [0, CLIP_HEADER_LEN, valid.len() - 1] {
+            assert_eq!(Clip::new(&valid[..len]).err(), Some(ClipError::Truncated));
+        }
+
+        let too_many_pixels = clip_data(&[(10, 0, &[op(CLIP_OP_RUN, 9), 1])]);
+        assert_eq!(
+            Clip::new(too_many_pixels.as_bytes()).err(),
+            Some(ClipError::Corrupt)
+        );
+
+        let outside_palette = clip_data(&[(10, 0, &[op(CLIP_OP_RUN, 1), 3])]);
+        assert_eq!(
+            Clip::new(outside_palette.as_bytes()).err(),
+            Some(ClipError::Corrupt)
+        );
+
+        let unknown_op = clip_data(&[(10, 0, &[op(0b11, 1)])]);
+        assert_eq!(
+            Clip::new(unknown_op.as_bytes()).err(),
+            Some(ClipError::Corrupt)
+        );
+    }
+}
@@ -0,0 +1,290 @@
+use embedded_graphics_core::{pixelcolor::Rgb888, prelude::RgbColor};
+
+/// A color as hue, saturation and value, each from 0 to 255.
+///
+/// The hue goes once around the color wheel from 0 to 255, starting and ending at red,
+/// so it can be rotated with wrapping addition.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct Hsv {
+    pub h: u8,
+    pub s: u8,
+    pub v: u8,
+}
+
+impl Hsv {
+    /// Create a new color from hue, saturation and value.
+    pub const fn new(h: u8, s: u8, v: u8) -> Self {
+        Self { h, s, v }
+    }
+
+    /// Get the color with the hue rotated by the given amount, wrapping around the color wheel.
+    pub const fn rotate_hue(self, amount: u8) -> Self {
+        Self::new(self.h.wrapping_add(amount), self.s, self.v)
+    }
+}
+
+impl From<Hsv> for Rgb888 {
+    fn from(value: Hsv) -> Self {
+        let v = value.v as u32;
+        let s = value.s as u32;
+        let (sector, remainder) = hue_sector(value.h);
+
+        let p = v * (255 - s) / 255;
+        let q = v * (255 - s * remainder / 255) / 255;
+        let t = v * (255 - s * (255 - remainder) / 255) / 255;
+
+        let (r, g, b) = match sector {
+            0 => (v, t, p),
+            1 => (q, v, p),
+            2 => (p, v, t),
+            3 => (p, q, v),
+            4 => (t, p, v),
+            _ => (v, p, q),
+        };
+
+        Rgb888::new(r as u8, g as u8, b as u8)
+    }
+}
+
+impl From<Rgb888> for Hsv {
+    fn from(value: Rgb888) -> Self {
+        let max = value.r().max(value.g()).max(value.b());
+        let min = value.r().min(value.g()).min(value.b());
+
+        let s = if max == 0 {
+            0
+        } else {
+            ((max - min) as u32 * 255 / max as u32) as u8
+        };
+
+        Self::new(hue(value, max, min), s, max)
+    }
+}
+
+/// A color as hue, saturation and lightness, each from 0 to 255.
+///
+/// The hue is the same as [`Hsv`], while a lightness of 0 is black, 255 is white
+/// and 128 is the fully saturated color.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct Hsl {
+    pub h: u8,
+    pub s: u8,
+    pub l: u8,
+}
+
+impl Hsl {
+    /// Create a new color from hue, saturation and lightness.
+    pub const fn new(h: u8, s: u8, l: u8) -> Self {
+        Self { h, s, l }
+    }
+
+    /// Get the color with the hue rotated by the given amount, wrapping around the color wheel.
+    pub const fn rotate_hue(self, amount: u8) -> Self {
+        Self::new(self.h.wrapping_add(amount), self.s, self.l)
+    }
+}
+
+impl From<Hsl> for Rgb888 {
+    fn from(value: Hsl) -> Self {
+        let l = value.l as i32;
+        let s = value.s as i32;
+        let (sector, remainder) = hue_sector(value.h);
+
+        // chroma is greatest at half lightness, falling to nothing at black and white
+        let chroma = (255 - (2 * l - 255).abs()) * s / 255;
+        let rising = chroma * remainder as i32 / 255;
+        let falling = chroma - rising;
+        let m = l - chroma / 2;
+
+        let (r, g, b) = match sector {
+            0 => (chroma, rising, 0),
+            1 => (falling, chroma, 0),
+            2 => (0, chroma, rising),
+            3 => (0, falling, chroma),
+            4 => (rising, 0, chroma),
+            _ => (chroma, 0, falling),
+        };
+
+        let channel = |value: i32| (value + m).clamp(0, 255) as u8;
+        Rgb888::new(channel(r), channel(g), channel(b))
+    }
+}
+
+impl From<Rgb888> for Hsl {
+    fn from(value: Rgb888) -> Self {
+        let max = value.r().max(value.g()).max(value.b());
+        let min = value.r().min(value.g()).min(value.b());
+
+        let sum = max as i32 + min as i32;
+        let l = (sum / 2) as u8;
+
+        let s = if max == min {
+            0
+        } else {
+            let delta = (max - min) as i32;
+            (delta * 255 / (255 - (sum - 255).abs())).min(255) as u8
+        };
+
+        Self::new(hue(value, max, min), s, l)
+    }
+}
+
+impl From<Hsv> for Hsl {
+    fn from(value: Hsv) -> Self {
+        let mut hsl = Hsl::from(Rgb888::from(value));
+        // keep the hue when it is lost to grey
+        hsl.h = value.h;
+        hsl
+    }
+}
+
+impl From<Hsl> for Hsv {
+    fn from(value: Hsl) -> Self {
+        let mut hsv = Hsv::from(Rgb888::from(value));
+        // keep the hue when it is lost to grey
+        hsv.h = value.h;
+        hsv
+    }
+}
+
+/// Get the fully saturated color at the given position around the color wheel,
+/// going from red through green and blue back to red.
+pub fn color_wheel(position: u8) -> Rgb888 {
+    Hsv::new(position, u8::MAX, u8::MAX).into()
+}
+
+/// Split a hue into one of the six sectors of the color wheel,
+/// and the position within that sector from 0 to 255.
+fn hue_sector(hue: u8) -> (u32, u32) {
+    let scaled = hue as u32 * 6;
+    (scaled >> 8, scaled & 0xff)
+}
+
+/// Get the hue of the color, given its largest and smallest channels.
+fn hue(color: Rgb888, max: u8, min: u8) -> u8 {
+    if max == min {
+        return 0;
+    }
+
+    let delta = (max - min) as i32;
+    let (r, g, b) = (color.r() as i32, color.g() as i32, color.b() as i32);
+
+    // position within the sector from -256 to 256, then offset by the sector, as 256 per sector
+    let hue = if max == color.r() {
+        (g - b) * 256 / delta
+    } else if max == color.g() {
+        512 + (b - r) * 256 / delta
+    } else {
+        1024 + (r - g) * 256 / delta
+    };
+
+    // scale the six sectors down to a full turn of 256
+    (hue.rem_euclid(1536) / 6) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Largest difference between the channels of two colors.
+    fn distance(a: Rgb888, b: Rgb888) -> u8 {
+        a.r()
+            .abs_diff(b.r())
+            .max(a.g().abs_diff(b.g()))
+            .max(a.b().abs_diff(b.b()))
+    }
+
+    #[test]
+    fn hsv_of_known_colors() {
+        assert_eq!(Hsv::from(Rgb888::RED), Hsv::new(0, 255, 255));
+        assert_eq!(Hsv::from(Rgb888::YELLOW), Hsv::new(42, 255, 255));
+        assert_eq!(Hsv::from(Rgb888::GREEN), Hsv::new(85, 255, 255));
+        assert_eq!(Hsv::from(Rgb888::CYAN), Hsv::new(128, 255, 255));
+        assert_eq!(Hsv::from(Rgb888::BLUE), Hsv::new(170, 255, 255));
+        assert_eq!(Hsv::from(Rgb888::MAGENTA), Hsv::new(213, 255, 255));
+        assert_eq!(Hsv::from(Rgb888::new(128, 128, 128)), Hsv::new(0, 0, 128));
+        assert_eq!(Hsv::from(Rgb888::BLACK), Hsv::new(0, 0, 0));
+
+        assert_eq!(Rgb888::from(Hsv::new(0, 255, 255)), Rgb888::RED);
+        assert_eq!(Rgb888::from(Hsv::new(128, 255, 255)), Rgb888::CYAN);
+        assert_eq!(
+            Rgb888::from(Hsv::new(100, 0, 200)),
+            Rgb888::new(200, 200, 200)
+        );
+        assert_eq!(Rgb888::from(Hsv::new(100, 255, 0)), Rgb888::BLACK);
+    }
+
+    #[test]
+    fn hsl_of_known_colors() {
+        assert_eq!(Hsl::from(Rgb888::RED), Hsl::new(0, 255, 127));
+        assert_eq!(Hsl::from(Rgb888::GREEN), Hsl::new(85, 255, 127));
+        assert_eq!(Hsl::from(Rgb888::BLUE), Hsl::new(170, 255, 127));
+        assert_eq!(Hsl::from(Rgb888::WHITE), Hsl::new(0, 0, 255));
+        assert_eq!(Hsl::from(Rgb888::new(128, 128, 128)), Hsl::new(0, 0, 128));
+
+        assert_eq!(Rgb888::from(Hsl::new(50, 255, 0)), Rgb888::BLACK);
+        assert_eq!(Rgb888::from(Hsl::new(50, 255, 255)), Rgb888::WHITE);
+        assert_eq!(
+            Rgb888::from(Hsl::new(50, 0, 100)),
+            Rgb888::new(100, 100, 100)
+        );
+        assert!(distance(Rgb888::from(Hsl::new(0, 255, 128)), Rgb888::RED) <= 1);
+    }
+
+    #[test]
+    fn rgb_round_trips() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let color = Rgb888::new(r, g, b);
+
+                    // 256 hues lose a little precision around the wheel
+                    assert!(
+                        distance(Rgb888::from(Hsv::from(color)), color) <= 8,
+                        "{color:?}"
+                    );
+                    assert!(
+                        distance(Rgb888::from(Hsl::from(color)), color) <= 8,
+                        "{color:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hsv_and_hsl_keep_the_hue() {
+        assert_eq!(Hsl::from(Hsv::new(77, 0, 200)).h, 77);
+        assert_eq!(Hsv::from(Hsl::new(77, 0, 200)).h, 77);
+        assert_eq!(Hsl::from(Hsv::new(0, 255, 255)), Hsl::new(0, 255, 127));
+        assert_eq!(Hsv::from(Hsl::new(0, 255, 127)).v, 254);
+    }
+
+    #[test]
+    fn rotate_hue_wraps() {
+        assert_eq!(Hsv::new(250, 10, 20).rotate_hue(10), Hsv::new(4, 10, 20));
+        assert_eq!(Hsl::new(100, 10, 20).rotate_hue(28), Hsl::new(128, 10, 20));
+    }
+
+    #[test]
+    fn color_wheel_goes_through_the_primaries() {
+        assert_eq!(color_wheel(0), Rgb888::RED);
+        assert!(distance(color_wheel(85), Rgb888::GREEN) <= 1);
+        assert!(distance(color_wheel(170), Rgb888::BLUE) <= 3);
+        assert_eq!(color_wheel(128), Rgb888::CYAN);
+
+        for position in 0..=255 {
+            let color = color_wheel(position);
+            assert_eq!(color.r().max(color.g()).max(color.b()), 255);
+            assert_eq!(color.r().min(color.g()).min(color.b()), 0);
+        }
+    }
+
+    #[test]
+    fn hue_of_each_sector() {
+        assert_eq!(hue(Rgb888::new(255, 128, 0), 255, 0), 21);
+        assert_eq!(hue(Rgb888::new(0, 128, 255), 255, 0), 149);
+        assert_eq!(hue(Rgb888::new(255, 0, 128), 255, 0), 234);
+        assert_eq!(hue(Rgb888::new(90, 90, 90), 90, 90), 0);
+    }
+}
@@ -0,0 +1,312 @@
+use embedded_graphics_core::{
+    pixelcolor::Rgb888,
+    prelude::{Point, RgbColor},
+};
+
+use crate::{BlendMode, UnicornGraphics};
+
+/// A single layer of the compositor.
+#[derive(Copy, Clone)]
+pub struct Layer<const W: usize, const H: usize> {
+    /// The pixels of this layer.
+    pub graphics: UnicornGraphics<W, H>,
+
+    /// Opacity of the whole layer, where 0 is invisible and 255 is fully opaque.
+    pub opacity: u8,
+
+    /// Offset of the layer from the top left of the output.
+    /// Pixels moved outside of the output are not drawn.
+    pub offset: Point,
+
+    /// If the layer is drawn.
+    pub visible: bool,
+
+    /// How the layer is combined with the layers below it.
+    pub mode: BlendMode,
+
+    /// Pixels of this color are not drawn, letting the layers below show through.
+    /// Defaults to `embedded_graphics_core::pixelcolor::Rgb888::BLACK`.
+    pub transparent_color: Option<Rgb888>,
+}
+
+impl<const W: usize, const H: usize> Layer<W, H> {
+    /// Create a new empty, visible and fully opaque layer.
+    pub fn new() -> Self {
+        Self {
+            graphics: UnicornGraphics::new(),
+            opacity: u8::MAX,
+            offset: Point::zero(),
+            visible: true,
+            mode: BlendMode::Normal,
+            transparent_color: Some(Rgb888::BLACK),
+        }
+    }
+
+    /// Draw this layer onto the output.
+    fn draw(&self, output: &mut UnicornGraphics<W, H>) {
+        if !self.visible || self.opacity == 0 {
+            return;
+        }
+
+        for (y, row) in self.graphics.pixels.iter().enumerate() {
+            for (x, color) in row.iter().enumerate() {
+                if self.transparent_color == Some(*color) {
+                    continue;
+                }
+
+                let coord = Point::new(x as i32, y as i32) + self.offset;
+                output.blend_pixel(coord, *color, self.opacity, self.mode);
+            }
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Default for Layer<W, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An ordered stack of `N` layers, which are flattened into a single buffer for the display.
+/// Layer 0 is at the bottom of the stack and drawn first.
+#[derive(Copy, Clone)]
+pub struct Compositor<const W: usize, const H: usize, const N: usize> {
+    /// Color shown where no layer is drawn.
+    pub background: Rgb888,
+
+    layers: [Layer<W, H>; N],
+}
+
+impl<const W: usize, const H: usize, const N: usize> Compositor<W, H, N> {
+    /// Create a new compositor of empty layers.
+    /// Defaults to `embedded_graphics_core::pixelcolor::Rgb888::BLACK` for the background.
+    pub fn new() -> Self {
+        Self {
+            background: Rgb888::BLACK,
+            layers: [Layer::new(); N],
+        }
+    }
+
+    /// Number of layers in the stack.
+    pub fn len(&self) -> usize {
+        N
+    }
+
+    /// Check if the stack has no layers.
+    pub fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Get the layer at the given index, providing the index is within the stack.
+    pub fn layer(&self, index: usize) -> Option<&Layer<W, H>> {
+        self.layers.get(index)
+    }
+
+    /// Get the layer at the given index to change it, providing the index is within the stack.
+    pub fn layer_mut(&mut self, index: usize) -> Option<&mut Layer<W, H>> {
+        self.layers.get_mut(index)
+    }
+
+    /// Get the graphics of the layer at the given index to draw on.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is not within the stack.
+    pub fn graphics_mut(&mut self, index: usize) -> &mut UnicornGraphics<W, H> {
+        &mut self.layers[index].graphics
+    }
+
+    /// Get all layers, from the bottom of the stack to the top.
+    pub fn layers(&self) -> &[Layer<W, H>; N] {
+        &self.layers
+    }
+
+    /// Get all layers to change them, from the bottom of the stack to the top.
+    pub fn layers_mut(&mut self) -> &mut [Layer<W, H>; N] {
+        &mut self.layers
+    }
+
+    /// Set the opacity of the layer at the given index.
+    pub fn set_opacity(&mut self, index: usize, opacity: u8) {
+        if let Some(layer) = self.layers.get_mut(index) {
+            layer.opacity = opacity;
+        }
+    }
+
+    /// Set the offset of the layer at the given index.
+    pub fn set_offset(&mut self, index: usize, offset: Point) {
+        if let Some(layer) = self.layers.get_mut(index) {
+            layer.offset = offset;
+        }
+    }
+
+    /// Set if the layer at the given index is drawn.
+    pub fn set_visible(&mut self, index: usize, visible: bool) {
+        if let Some(layer) = self.layers.get_mut(index) {
+            layer.visible = visible;
+        }
+    }
+
+    /// Set the blend mode of the layer at the given index.
+    pub fn set_mode(&mut self, index: usize, mode: BlendMode) {
+        if let Some(layer) = self.layers.get_mut(index) {
+            layer.mode = mode;
+        }
+    }
+
+    /// Swap the positions of two layers in the stack.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is not within the stack.
+    pub fn swap_layers(&mut self, a: usize, b: usize) {
+        self.layers.swap(a, b);
+    }
+
+    /// Move the layer at the given index to a new position in the stack,
+    /// shifting the layers in between.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is not within the stack.
+    pub fn move_layer(&mut self, from: usize, to: usize) {
+        if from < to {
+            self.layers[from..=to].rotate_left(1);
+        } else {
+            self.layers[to..=from].rotate_right(1);
+        }
+    }
+
+    /// Flatten all visible layers into a new buffer.
+    pub fn flatten(&self) -> UnicornGraphics<W, H> {
+        let mut output = UnicornGraphics::new();
+        self.flatten_into(&mut output);
+        output
+    }
+
+    /// Flatten all visible layers into the given buffer, overwriting what it holds.
+    pub fn flatten_into(&self, output: &mut UnicornGraphics<W, H>) {
+        output.fill(self.background);
+
+        for layer in self.layers.iter() {
+            layer.draw(output);
+        }
+    }
+}
+
+impl<const W: usize, const H: usize, const N: usize> Default for Compositor<W, H, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn color_at<const W: usize, const H: usize>(
+        graphics: &UnicornGraphics<W, H>,
+        x: i32,
+        y: i32,
+    ) -> Rgb888 {
+        graphics.get_item(Point::new(x, y)).unwrap()
+    }
+
+    /// A compositor with a red pixel at the top left of layer 0 and a blue pixel at the top right of layer 1.
+    fn compositor() -> Compositor<2, 2, 3> {
+        let mut compositor = Compositor::new();
+        compositor
+            .graphics_mut(0)
+            .set_pixel(Point::new(0, 0), Rgb888::RED);
+        compositor
+            .graphics_mut(1)
+            .set_pixel(Point::new(1, 0), Rgb888::BLUE);
+        compositor
+    }
+
+    #[test]
+    fn transparent_pixels_show_the_layers_below() {
+        let mut compositor = compositor();
+        compositor.background = Rgb888::GREEN;
+
+        let output = compositor.flatten();
+
+        assert_eq!(color_at(&output, 0, 0), Rgb888::RED);
+        assert_eq!(color_at(&output, 1, 0), Rgb888::BLUE);
+        assert_eq!(color_at(&output, 0, 1), Rgb888::GREEN);
+    }
+
+    #[test]
+    fn top_layer_is_drawn_last() {
+        let mut compositor = compositor();
+        compositor
+            .graphics_mut(2)
+            .set_pixel(Point::new(0, 0), Rgb888::WHITE);
+
+        assert_eq!(color_at(&compositor.flatten(), 0, 0), Rgb888::WHITE);
+
+        compositor.move_layer(2, 0);
+        assert_eq!(color_at(&compositor.flatten(), 0, 0), Rgb888::RED);
+
+        compositor.swap_layers(0, 1);
+        assert_eq!(color_at(&compositor.flatten(), 0, 0), Rgb888::WHITE);
+        let bottom = &compositor.layer(0).unwrap().graphics;
+        assert_eq!(color_at(bottom, 0, 0), Rgb888::RED);
+    }
+
+    #[test]
+    fn hidden_and_invisible_layers_are_not_drawn() {
+        let mut compositor = compositor();
+
+        compositor.set_visible(0, false);
+        compositor.set_opacity(1, 0);
+        let output = compositor.flatten();
+
+        assert_eq!(color_at(&output, 0, 0), Rgb888::BLACK);
+        assert_eq!(color_at(&output, 1, 0), Rgb888::BLACK);
+    }
+
+    #[test]
+    fn opacity_and_mode_blend_with_the_layers_below() {
+        let mut compositor = compositor();
+        compositor.set_offset(1, Point::new(-1, 0));
+
+        compositor.set_mode(1, BlendMode::Add);
+        assert_eq!(
+            color_at(&compositor.flatten(), 0, 0),
+            Rgb888::new(255, 0, 255)
+        );
+
+        compositor.set_mode(1, BlendMode::Normal);
+        compositor.set_opacity(1, 128);
+        assert_eq!(
+            color_at(&compositor.flatten(), 0, 0),
+            Rgb888::new(127, 0, 128)
+        );
+    }
+
+    #[test]
+    fn offset_layers_are_clipped_to_the_output() {
+        let mut compositor = compositor();
+        compositor.set_offset(0, Point::new(1, 1));
+        compositor.set_offset(1, Point::new(1, 0));
+
+        let output = compositor.flatten();
+
+        assert_eq!(color_at(&output, 0, 0), Rgb888::BLACK);
+        assert_eq!(color_at(&output, 1, 1), Rgb888::RED);
+        assert_eq!(color_at(&output, 1, 0), Rgb888::BLACK);
+    }
+
+    #[test]
+    fn layer_changes_outside_the_stack_are_ignored() {
+        let mut compositor = compositor();
+        compositor.set_opacity(3, 0);
+        compositor.set_visible(3, false);
+
+        assert_eq!(compositor.len(), 3);
+        assert!(compositor.layer(3).is_none());
+        assert!(compositor.layer_mut(3).is_none());
+        assert!(Compositor::<2, 2, 0>::new().is_empty());
+    }
+}
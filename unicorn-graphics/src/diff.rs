@@ -0,0 +1,131 @@
+use embedded_graphics_core::{pixelcolor::Rgb888, prelude::Point, primitives::Rectangle, Pixel};
+
+use crate::UnicornGraphics;
+
+/// Get the pixels that differ between two buffers, with their color in the second buffer.
+///
+/// Useful for sending only what changed between frames, such as when encoding for the display.
+pub fn diff<'a, const W: usize, const H: usize>(
+    a: &'a UnicornGraphics<W, H>,
+    b: &'a UnicornGraphics<W, H>,
+) -> Diff<'a, W, H> {
+    Diff { a, b, index: 0 }
+}
+
+/// Get the smallest area holding every pixel that differs between two buffers,
+/// or `None` when they are the same.
+pub fn diff_area<const W: usize, const H: usize>(
+    a: &UnicornGraphics<W, H>,
+    b: &UnicornGraphics<W, H>,
+) -> Option<Rectangle> {
+    let mut corners: Option<(Point, Point)> = None;
+
+    for Pixel(point, _) in diff(a, b) {
+        corners = Some(match corners {
+            Some((top_left, bottom_right)) => (
+                top_left.component_min(point),
+                bottom_right.component_max(point),
+            ),
+            None => (point, point),
+        });
+    }
+
+    corners.map(|(top_left, bottom_right)| Rectangle::with_corners(top_left, bottom_right))
+}
+
+/// Iterator over the pixels that differ between two buffers, created by [`diff`].
+pub struct Diff<'a, const W: usize, const H: usize> {
+    a: &'a UnicornGraphics<W, H>,
+    b: &'a UnicornGraphics<W, H>,
+
+    /// Index of the next pixel to compare, row by row from the top left.
+    index: usize,
+}
+
+impl<'a, const W: usize, const H: usize> Iterator for Diff<'a, W, H> {
+    type Item = Pixel<Rgb888>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < W * H {
+            let (x, y) = (self.index % W, self.index / W);
+            self.index += 1;
+
+            let color = self.b.pixels[y][x];
+            if self.a.pixels[y][x] != color {
+                return Some(Pixel(Point::new(x as i32, y as i32), color));
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::prelude::RgbColor;
+
+    use super::*;
+
+    #[test]
+    fn same_buffers_have_no_diff() {
+        let mut a = UnicornGraphics::<4, 3>::new();
+        a.fill(Rgb888::RED);
+        let b = a;
+
+        assert_eq!(diff(&a, &b).count(), 0);
+    }
+
+    #[test]
+    fn changed_pixels_in_row_order_with_new_color() {
+        let a = UnicornGraphics::<4, 3>::new();
+        let mut b = a;
+        b.set_pixel(Point::new(3, 2), Rgb888::BLUE);
+        b.set_pixel(Point::new(1, 0), Rgb888::RED);
+        b.set_pixel(Point::new(0, 2), Rgb888::GREEN);
+
+        let mut changes = diff(&a, &b);
+        assert_eq!(changes.next(), Some(Pixel(Point::new(1, 0), Rgb888::RED)));
+        assert_eq!(changes.next(), Some(Pixel(Point::new(0, 2), Rgb888::GREEN)));
+        assert_eq!(changes.next(), Some(Pixel(Point::new(3, 2), Rgb888::BLUE)));
+        assert_eq!(changes.next(), None);
+        assert_eq!(changes.next(), None);
+    }
+
+    #[test]
+    fn every_pixel_changed() {
+        let a = UnicornGraphics::<5, 2>::new();
+        let mut b = a;
+        b.fill(Rgb888::WHITE);
+
+        assert_eq!(diff(&a, &b).count(), 10);
+        assert!(diff(&b, &a).all(|Pixel(_, color)| color == Rgb888::BLACK));
+    }
+
+    #[test]
+    fn diff_area_holds_every_changed_pixel() {
+        let a = UnicornGraphics::<6, 4>::new();
+        let mut b = a;
+        assert_eq!(diff_area(&a, &b), None);
+
+        b.set_pixel(Point::new(4, 0), Rgb888::RED);
+        b.set_pixel(Point::new(1, 2), Rgb888::RED);
+        b.set_pixel(Point::new(2, 3), Rgb888::RED);
+
+        let area = Rectangle::with_corners(Point::new(1, 0), Point::new(4, 3));
+        assert_eq!(diff_area(&a, &b), Some(area));
+        assert_eq!(diff_area(&b, &a), Some(area));
+    }
+
+    #[test]
+    fn diff_area_ignores_the_dirty_area() {
+        let mut a = UnicornGraphics::<6, 4>::new();
+        a.set_pixel(Point::new(0, 0), Rgb888::RED);
+
+        // a fresh buffer is clean, but still differs from the pixels of the other
+        let b = UnicornGraphics::<6, 4>::new();
+        assert!(!b.is_dirty());
+
+        let area = Rectangle::with_corners(Point::zero(), Point::zero());
+        assert_eq!(diff_area(&a, &b), Some(area));
+    }
+}
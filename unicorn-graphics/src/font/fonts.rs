@@ -0,0 +1,921 @@
+//! Generated by `cargo run --example generate_fonts > src/font/fonts.rs`, do not edit by hand.
+//!
+//! Glyphs are taken from the public domain X11 misc-fixed fonts.
+
+use super::{BitmapFont, Glyph, KerningPair};
+
+/// Monospaced font with 3×5 pixel capitals and a one pixel descender.
+pub const FONT_3X5: BitmapFont = BitmapFont {
+    height: 6,
+    baseline: 4,
+    letter_spacing: 0,
+    glyphs: GLYPHS_3X5,
+    kerning: KERNING_3X5,
+    replacement: '?',
+};
+
+const GLYPHS_3X5: &[Glyph] = &[
+    Glyph::new(' ', &[0x000, 0x000, 0x000, 0x000]),
+    Glyph::new('!', &[0x000, 0x017, 0x000, 0x000]),
+    Glyph::new('"', &[0x003, 0x000, 0x003, 0x000]),
+    Glyph::new('#', &[0x01f, 0x00a, 0x01f, 0x00a]),
+    Glyph::new('$', &[0x016, 0x037, 0x01a, 0x000]),
+    Glyph::new('%', &[0x009, 0x004, 0x012, 0x000]),
+    Glyph::new('&', &[0x00a, 0x015, 0x00a, 0x010]),
+    Glyph::new('\'', &[0x000, 0x003, 0x000, 0x000]),
+    Glyph::new('(', &[0x000, 0x01e, 0x021, 0x000]),
+    Glyph::new(')', &[0x021, 0x01e, 0x000, 0x000]),
+    Glyph::new('*', &[0x015, 0x00e, 0x015, 0x000]),
+    Glyph::new('+', &[0x004, 0x01f, 0x004, 0x000]),
+    Glyph::new(',', &[0x020, 0x010, 0x000, 0x000]),
+    Glyph::new('-', &[0x004, 0x004, 0x004, 0x000]),
+    Glyph::new('.', &[0x000, 0x010, 0x000, 0x000]),
+    Glyph::new('/', &[0x018, 0x004, 0x003, 0x000]),
+    Glyph::new('0', &[0x00e, 0x015, 0x00e, 0x000]),
+    Glyph::new('1', &[0x012, 0x01f, 0x010, 0x000]),
+    Glyph::new('2', &[0x012, 0x019, 0x016, 0x000]),
+    Glyph::new('3', &[0x011, 0x015, 0x00b, 0x000]),
+    Glyph::new('4', &[0x007, 0x004, 0x01f, 0x000]),
+    Glyph::new('5', &[0x017, 0x015, 0x009, 0x000]),
+    Glyph::new('6', &[0x00e, 0x015, 0x009, 0x000]),
+    Glyph::new('7', &[0x019, 0x005, 0x003, 0x000]),
+    Glyph::new('8', &[0x01a, 0x015, 0x00b, 0x000]),
+    Glyph::new('9', &[0x012, 0x015, 0x00e, 0x000]),
+    Glyph::new(':', &[0x000, 0x012, 0x000, 0x000]),
+    Glyph::new(';', &[0x020, 0x012, 0x000, 0x000]),
+    Glyph::new('<', &[0x004, 0x00a, 0x011, 0x000]),
+    Glyph::new('=', &[0x00a, 0x00a, 0x00a, 0x000]),
+    Glyph::new('>', &[0x011, 0x00a, 0x004, 0x000]),
+    Glyph::new('?', &[0x001, 0x015, 0x002, 0x000]),
+    Glyph::new('@', &[0x00e, 0x011, 0x017, 0x000]),
+    Glyph::new('A', &[0x01e, 0x005, 0x01e, 0x000]),
+    Glyph::new('B', &[0x01f, 0x015, 0x00a, 0x000]),
+    Glyph::new('C', &[0x00e, 0x011, 0x00a, 0x000]),
+    Glyph::new('D', &[0x01f, 0x011, 0x00e, 0x000]),
+    Glyph::new('E', &[0x01f, 0x015, 0x011, 0x000]),
+    Glyph::new('F', &[0x01f, 0x005, 0x001, 0x000]),
+    Glyph::new('G', &[0x00e, 0x011, 0x01d, 0x000]),
+    Glyph::new('H', &[0x01f, 0x004, 0x01f, 0x000]),
+    Glyph::new('I', &[0x011, 0x01f, 0x011, 0x000]),
+    Glyph::new('J', &[0x008, 0x010, 0x00f, 0x000]),
+    Glyph::new('K', &[0x01f, 0x004, 0x01b, 0x000]),
+    Glyph::new('L', &[0x01f, 0x010, 0x010, 0x000]),
+    Glyph::new('M', &[0x01f, 0x006, 0x01f, 0x000]),
+    Glyph::new('N', &[0x01e, 0x004, 0x00f, 0x000]),
+    Glyph::new('O', &[0x00e, 0x011, 0x00e, 0x000]),
+    Glyph::new('P', &[0x01f, 0x005, 0x002, 0x000]),
+    Glyph::new('Q', &[0x00e, 0x011, 0x02e, 0x000]),
+    Glyph::new('R', &[0x01f, 0x005, 0x01a, 0x000]),
+    Glyph::new('S', &[0x012, 0x015, 0x009, 0x000]),
+    Glyph::new('T', &[0x001, 0x01f, 0x001, 0x000]),
+    Glyph::new('U', &[0x01f, 0x010, 0x01f, 0x000]),
+    Glyph::new('V', &[0x00f, 0x018, 0x00f, 0x000]),
+    Glyph::new('W', &[0x01f, 0x00c, 0x01f, 0x000]),
+    Glyph::new('X', &[0x01b, 0x004, 0x01b, 0x000]),
+    Glyph::new('Y', &[0x003, 0x01c, 0x003, 0x000]),
+    Glyph::new('Z', &[0x019, 0x015, 0x013, 0x000]),
+    Glyph::new('[', &[0x000, 0x01f, 0x011, 0x000]),
+    Glyph::new('\\', &[0x003, 0x004, 0x018, 0x000]),
+    Glyph::new(']', &[0x011, 0x01f, 0x000, 0x000]),
+    Glyph::new('^', &[0x002, 0x001, 0x002, 0x000]),
+    Glyph::new('_', &[0x020, 0x020, 0x020, 0x000]),
+    Glyph::new('`', &[0x000, 0x001, 0x002, 0x000]),
+    Glyph::new('a', &[0x00c, 0x012, 0x01e, 0x000]),
+    Glyph::new('b', &[0x01f, 0x012, 0x00c, 0x000]),
+    Glyph::new('c', &[0x00c, 0x012, 0x012, 0x000]),
+    Glyph::new('d', &[0x00c, 0x012, 0x01f, 0x000]),
+    Glyph::new('e', &[0x00c, 0x01a, 0x014, 0x000]),
+    Glyph::new('f', &[0x004, 0x01e, 0x005, 0x000]),
+    Glyph::new('g', &[0x024, 0x02a, 0x01e, 0x000]),
+    Glyph::new('h', &[0x01f, 0x002, 0x01c, 0x000]),
+    Glyph::new('i', &[0x014, 0x01d, 0x010, 0x000]),
+    Glyph::new('j', &[0x020, 0x020, 0x01d, 0x000]),
+    Glyph::new('k', &[0x01f, 0x004, 0x01a, 0x000]),
+    Glyph::new('l', &[0x011, 0x01f, 0x010, 0x000]),
+    Glyph::new('m', &[0x01e, 0x004, 0x01e, 0x000]),
+    Glyph::new('n', &[0x01e, 0x002, 0x01c, 0x000]),
+    Glyph::new('o', &[0x00c, 0x012, 0x00c, 0x000]),
+    Glyph::new('p', &[0x03e, 0x00a, 0x004, 0x000]),
+    Glyph::new('q', &[0x00c, 0x012, 0x03e, 0x000]),
+    Glyph::new('r', &[0x01e, 0x004, 0x002, 0x000]),
+    Glyph::new('s', &[0x014, 0x016, 0x00a, 0x000]),
+    Glyph::new('t', &[0x002, 0x00f, 0x012, 0x000]),
+    Glyph::new('u', &[0x00e, 0x010, 0x01e, 0x000]),
+    Glyph::new('v', &[0x00e, 0x010, 0x00e, 0x000]),
+    Glyph::new('w', &[0x01e, 0x008, 0x01e, 0x000]),
+    Glyph::new('x', &[0x012, 0x00c, 0x012, 0x000]),
+    Glyph::new('y', &[0x026, 0x028, 0x01e, 0x000]),
+    Glyph::new('z', &[0x012, 0x01a, 0x016, 0x000]),
+    Glyph::new('{', &[0x004, 0x01e, 0x021, 0x000]),
+    Glyph::new('|', &[0x000, 0x01f, 0x000, 0x000]),
+    Glyph::new('}', &[0x021, 0x01e, 0x004, 0x000]),
+    Glyph::new('~', &[0x002, 0x001, 0x002, 0x001]),
+    Glyph::new('\u{a0}', &[0x000, 0x000, 0x000, 0x000]),
+    Glyph::new('¡', &[0x000, 0x01d, 0x000, 0x000]),
+    Glyph::new('¢', &[0x00e, 0x01b, 0x00a, 0x000]),
+    Glyph::new('£', &[0x010, 0x00e, 0x015, 0x000]),
+    Glyph::new('¤', &[0x012, 0x00c, 0x00c, 0x012]),
+    Glyph::new('¥', &[0x005, 0x01e, 0x005, 0x000]),
+    Glyph::new('¦', &[0x000, 0x01b, 0x000, 0x000]),
+    Glyph::new('§', &[0x026, 0x02b, 0x01d, 0x000]),
+    Glyph::new('¨', &[0x001, 0x000, 0x001, 0x000]),
+    Glyph::new('©', &[0x01e, 0x02d, 0x021, 0x01e]),
+    Glyph::new('ª', &[0x012, 0x015, 0x017, 0x000]),
+    Glyph::new('«', &[0x004, 0x00a, 0x004, 0x00a]),
+    Glyph::new('¬', &[0x004, 0x004, 0x00c, 0x000]),
+    Glyph::new('\u{ad}', &[0x004, 0x004, 0x004, 0x000]),
+    Glyph::new('®', &[0x006, 0x00f, 0x00b, 0x006]),
+    Glyph::new('¯', &[0x001, 0x001, 0x001, 0x000]),
+    Glyph::new('°', &[0x002, 0x005, 0x002, 0x000]),
+    Glyph::new('±', &[0x012, 0x017, 0x012, 0x000]),
+    Glyph::new('²', &[0x00d, 0x00b, 0x000, 0x000]),
+    Glyph::new('³', &[0x015, 0x00b, 0x000, 0x000]),
+    Glyph::new('´', &[0x000, 0x002, 0x001, 0x000]),
+    Glyph::new('µ', &[0x03e, 0x010, 0x00e, 0x000]),
+    Glyph::new('¶', &[0x006, 0x01f, 0x001, 0x01f]),
+    Glyph::new('·', &[0x000, 0x004, 0x000, 0x000]),
+    Glyph::new('¸', &[0x000, 0x010, 0x008, 0x000]),
+    Glyph::new('¹', &[0x002, 0x00f, 0x000, 0x000]),
+    Glyph::new('º', &[0x012, 0x015, 0x012, 0x000]),
+    Glyph::new('»', &[0x00a, 0x004, 0x00a, 0x004]),
+    Glyph::new('¼', &[0x007, 0x018, 0x010, 0x038]),
+    Glyph::new('½', &[0x007, 0x000, 0x034, 0x02c]),
+    Glyph::new('¾', &[0x015, 0x00b, 0x010, 0x038]),
+    Glyph::new('¿', &[0x008, 0x015, 0x010, 0x000]),
+    Glyph::new('À', &[0x01d, 0x00a, 0x01c, 0x000]),
+    Glyph::new('Á', &[0x01c, 0x00a, 0x01d, 0x000]),
+    Glyph::new('Â', &[0x01d, 0x00b, 0x01c, 0x000]),
+    Glyph::new('Ã', &[0x01e, 0x00b, 0x01d, 0x000]),
+    Glyph::new('Ä', &[0x01d, 0x00a, 0x01d, 0x000]),
+    Glyph::new('Å', &[0x01c, 0x00b, 0x01c, 0x000]),
+    Glyph::new('Æ', &[0x01e, 0x005, 0x01f, 0x015]),
+    Glyph::new('Ç', &[0x02e, 0x011, 0x00a, 0x000]),
+    Glyph::new('È', &[0x01f, 0x016, 0x012, 0x000]),
+    Glyph::new('É', &[0x01e, 0x016, 0x013, 0x000]),
+    Glyph::new('Ê', &[0x01e, 0x017, 0x013, 0x000]),
+    Glyph::new('Ë', &[0x01f, 0x016, 0x013, 0x000]),
+    Glyph::new('Ì', &[0x013, 0x01e, 0x012, 0x000]),
+    Glyph::new('Í', &[0x012, 0x01e, 0x013, 0x000]),
+    Glyph::new('Î', &[0x012, 0x01f, 0x012, 0x000]),
+    Glyph::new('Ï', &[0x011, 0x01e, 0x011, 0x000]),
+    Glyph::new('Ð', &[0x015, 0x01f, 0x011, 0x00e]),
+    Glyph::new('Ñ', &[0x01e, 0x00d, 0x01e, 0x001]),
+    Glyph::new('Ò', &[0x00d, 0x012, 0x00c, 0x000]),
+    Glyph::new('Ó', &[0x00c, 0x012, 0x00d, 0x000]),
+    Glyph::new('Ô', &[0x00c, 0x013, 0x00c, 0x000]),
+    Glyph::new('Õ', &[0x00e, 0x013, 0x00f, 0x001]),
+    Glyph::new('Ö', &[0x00d, 0x012, 0x00d, 0x000]),
+    Glyph::new('×', &[0x00a, 0x004, 0x00a, 0x000]),
+    Glyph::new('Ø', &[0x01e, 0x015, 0x00f, 0x000]),
+    Glyph::new('Ù', &[0x01d, 0x012, 0x01c, 0x000]),
+    Glyph::new('Ú', &[0x01c, 0x012, 0x01d, 0x000]),
+    Glyph::new('Û', &[0x01c, 0x011, 0x01c, 0x000]),
+    Glyph::new('Ü', &[0x01d, 0x010, 0x01d, 0x000]),
+    Glyph::new('Ý', &[0x004, 0x018, 0x005, 0x000]),
+    Glyph::new('Þ', &[0x01f, 0x00a, 0x004, 0x000]),
+    Glyph::new('ß', &[0x03e, 0x015, 0x01a, 0x000]),
+    Glyph::new('à', &[0x009, 0x016, 0x01c, 0x000]),
+    Glyph::new('á', &[0x008, 0x016, 0x01d, 0x000]),
+    Glyph::new('â', &[0x008, 0x015, 0x01d, 0x000]),
+    Glyph::new('ã', &[0x00a, 0x015, 0x01e, 0x001]),
+    Glyph::new('ä', &[0x009, 0x014, 0x01d, 0x000]),
+    Glyph::new('å', &[0x008, 0x015, 0x01c, 0x000]),
+    Glyph::new('æ', &[0x00c, 0x012, 0x01e, 0x016]),
+    Glyph::new('ç', &[0x00c, 0x032, 0x014, 0x000]),
+    Glyph::new('è', &[0x00d, 0x01a, 0x014, 0x000]),
+    Glyph::new('é', &[0x00c, 0x01a, 0x015, 0x000]),
+    Glyph::new('ê', &[0x00d, 0x01b, 0x014, 0x000]),
+    Glyph::new('ë', &[0x00d, 0x01a, 0x015, 0x000]),
+    Glyph::new('ì', &[0x011, 0x01e, 0x010, 0x000]),
+    Glyph::new('í', &[0x012, 0x01e, 0x011, 0x000]),
+    Glyph::new('î', &[0x012, 0x01d, 0x012, 0x000]),
+    Glyph::new('ï', &[0x015, 0x01c, 0x011, 0x000]),
+    Glyph::new('ð', &[0x009, 0x016, 0x00d, 0x000]),
+    Glyph::new('ñ', &[0x01e, 0x005, 0x01a, 0x001]),
+    Glyph::new('ò', &[0x009, 0x016, 0x008, 0x000]),
+    Glyph::new('ó', &[0x008, 0x016, 0x009, 0x000]),
+    Glyph::new('ô', &[0x008, 0x015, 0x008, 0x000]),
+    Glyph::new('õ', &[0x009, 0x015, 0x009, 0x000]),
+    Glyph::new('ö', &[0x009, 0x014, 0x009, 0x000]),
+    Glyph::new('÷', &[0x004, 0x015, 0x004, 0x000]),
+    Glyph::new('ø', &[0x01c, 0x012, 0x00e, 0x000]),
+    Glyph::new('ù', &[0x00d, 0x012, 0x01c, 0x000]),
+    Glyph::new('ú', &[0x00c, 0x012, 0x01d, 0x000]),
+    Glyph::new('û', &[0x00c, 0x011, 0x01c, 0x000]),
+    Glyph::new('ü', &[0x00d, 0x010, 0x01d, 0x000]),
+    Glyph::new('ý', &[0x02c, 0x02a, 0x01d, 0x000]),
+    Glyph::new('þ', &[0x03f, 0x014, 0x008, 0x000]),
+    Glyph::new('ÿ', &[0x02d, 0x028, 0x01d, 0x000]),
+];
+
+const KERNING_3X5: &[KerningPair] = &[];
+
+/// Monospaced font with 4×6 pixel capitals and a one pixel descender.
+pub const FONT_4X7: BitmapFont = BitmapFont {
+    height: 7,
+    baseline: 5,
+    letter_spacing: 0,
+    glyphs: GLYPHS_4X7,
+    kerning: KERNING_4X7,
+    replacement: '?',
+};
+
+const GLYPHS_4X7: &[Glyph] = &[
+    Glyph::new(' ', &[0x000, 0x000, 0x000, 0x000, 0x000]),
+    Glyph::new('!', &[0x000, 0x000, 0x02f, 0x000, 0x000]),
+    Glyph::new('"', &[0x000, 0x007, 0x000, 0x007, 0x000]),
+    Glyph::new('#', &[0x014, 0x03e, 0x014, 0x03e, 0x014]),
+    Glyph::new('$', &[0x004, 0x02a, 0x03e, 0x02a, 0x010]),
+    Glyph::new('%', &[0x013, 0x008, 0x004, 0x032, 0x000]),
+    Glyph::new('&', &[0x014, 0x02a, 0x014, 0x020, 0x000]),
+    Glyph::new('\'', &[0x000, 0x000, 0x007, 0x000, 0x000]),
+    Glyph::new('(', &[0x000, 0x01e, 0x021, 0x000, 0x000]),
+    Glyph::new(')', &[0x000, 0x021, 0x01e, 0x000, 0x000]),
+    Glyph::new('*', &[0x000, 0x02a, 0x01c, 0x02a, 0x000]),
+    Glyph::new('+', &[0x008, 0x008, 0x03e, 0x008, 0x008]),
+    Glyph::new(',', &[0x000, 0x040, 0x030, 0x010, 0x000]),
+    Glyph::new('-', &[0x008, 0x008, 0x008, 0x008, 0x000]),
+    Glyph::new('.', &[0x000, 0x030, 0x030, 0x000, 0x000]),
+    Glyph::new('/', &[0x010, 0x008, 0x004, 0x002, 0x000]),
+    Glyph::new('0', &[0x000, 0x01e, 0x021, 0x01e, 0x000]),
+    Glyph::new('1', &[0x000, 0x022, 0x03f, 0x020, 0x000]),
+    Glyph::new('2', &[0x022, 0x031, 0x029, 0x026, 0x000]),
+    Glyph::new('3', &[0x011, 0x025, 0x025, 0x01b, 0x000]),
+    Glyph::new('4', &[0x00c, 0x00a, 0x03f, 0x008, 0x000]),
+    Glyph::new('5', &[0x017, 0x025, 0x025, 0x019, 0x000]),
+    Glyph::new('6', &[0x01e, 0x025, 0x025, 0x018, 0x000]),
+    Glyph::new('7', &[0x001, 0x031, 0x00d, 0x003, 0x000]),
+    Glyph::new('8', &[0x01a, 0x025, 0x025, 0x01a, 0x000]),
+    Glyph::new('9', &[0x006, 0x029, 0x029, 0x01e, 0x000]),
+    Glyph::new(':', &[0x000, 0x036, 0x036, 0x000, 0x000]),
+    Glyph::new(';', &[0x040, 0x036, 0x016, 0x000, 0x000]),
+    Glyph::new('<', &[0x000, 0x008, 0x014, 0x022, 0x000]),
+    Glyph::new('=', &[0x014, 0x014, 0x014, 0x014, 0x000]),
+    Glyph::new('>', &[0x000, 0x022, 0x014, 0x008, 0x000]),
+    Glyph::new('?', &[0x000, 0x002, 0x029, 0x006, 0x000]),
+    Glyph::new('@', &[0x01e, 0x021, 0x02d, 0x00e, 0x000]),
+    Glyph::new('A', &[0x03e, 0x009, 0x009, 0x03e, 0x000]),
+    Glyph::new('B', &[0x03f, 0x025, 0x025, 0x01a, 0x000]),
+    Glyph::new('C', &[0x01e, 0x021, 0x021, 0x012, 0x000]),
+    Glyph::new('D', &[0x03f, 0x021, 0x021, 0x01e, 0x000]),
+    Glyph::new('E', &[0x03f, 0x025, 0x025, 0x021, 0x000]),
+    Glyph::new('F', &[0x03f, 0x005, 0x005, 0x001, 0x000]),
+    Glyph::new('G', &[0x01e, 0x021, 0x029, 0x03a, 0x000]),
+    Glyph::new('H', &[0x03f, 0x004, 0x004, 0x03f, 0x000]),
+    Glyph::new('I', &[0x000, 0x021, 0x03f, 0x021, 0x000]),
+    Glyph::new('J', &[0x010, 0x020, 0x020, 0x01f, 0x000]),
+    Glyph::new('K', &[0x03f, 0x00c, 0x012, 0x021, 0x000]),
+    Glyph::new('L', &[0x03f, 0x020, 0x020, 0x020, 0x000]),
+    Glyph::new('M', &[0x03f, 0x006, 0x006, 0x03f, 0x000]),
+    Glyph::new('N', &[0x03f, 0x006, 0x018, 0x03f, 0x000]),
+    Glyph::new('O', &[0x01e, 0x021, 0x021, 0x01e, 0x000]),
+    Glyph::new('P', &[0x03f, 0x009, 0x009, 0x006, 0x000]),
+    Glyph::new('Q', &[0x01e, 0x031, 0x021, 0x05e, 0x000]),
+    Glyph::new('R', &[0x03f, 0x009, 0x019, 0x026, 0x000]),
+    Glyph::new('S', &[0x012, 0x025, 0x029, 0x012, 0x000]),
+    Glyph::new('T', &[0x000, 0x001, 0x03f, 0x001, 0x000]),
+    Glyph::new('U', &[0x01f, 0x020, 0x020, 0x01f, 0x000]),
+    Glyph::new('V', &[0x00f, 0x030, 0x030, 0x00f, 0x000]),
+    Glyph::new('W', &[0x03f, 0x018, 0x018, 0x03f, 0x000]),
+    Glyph::new('X', &[0x033, 0x00c, 0x00c, 0x033, 0x000]),
+    Glyph::new('Y', &[0x000, 0x007, 0x038, 0x007, 0x000]),
+    Glyph::new('Z', &[0x031, 0x029, 0x025, 0x023, 0x000]),
+    Glyph::new('[', &[0x000, 0x03f, 0x021, 0x021, 0x000]),
+    Glyph::new('\\', &[0x002, 0x004, 0x008, 0x010, 0x000]),
+    Glyph::new(']', &[0x000, 0x021, 0x021, 0x03f, 0x000]),
+    Glyph::new('^', &[0x000, 0x002, 0x001, 0x002, 0x000]),
+    Glyph::new('_', &[0x020, 0x020, 0x020, 0x020, 0x000]),
+    Glyph::new('`', &[0x000, 0x001, 0x002, 0x000, 0x000]),
+    Glyph::new('a', &[0x018, 0x024, 0x014, 0x03c, 0x000]),
+    Glyph::new('b', &[0x03f, 0x024, 0x024, 0x018, 0x000]),
+    Glyph::new('c', &[0x018, 0x024, 0x024, 0x000, 0x000]),
+    Glyph::new('d', &[0x018, 0x024, 0x024, 0x03f, 0x000]),
+    Glyph::new('e', &[0x018, 0x034, 0x02c, 0x008, 0x000]),
+    Glyph::new('f', &[0x008, 0x03e, 0x009, 0x002, 0x000]),
+    Glyph::new('g', &[0x028, 0x054, 0x054, 0x04c, 0x000]),
+    Glyph::new('h', &[0x03f, 0x004, 0x004, 0x038, 0x000]),
+    Glyph::new('i', &[0x000, 0x024, 0x03d, 0x020, 0x000]),
+    Glyph::new('j', &[0x000, 0x020, 0x040, 0x03d, 0x000]),
+    Glyph::new('k', &[0x03f, 0x008, 0x014, 0x020, 0x000]),
+    Glyph::new('l', &[0x000, 0x021, 0x03f, 0x020, 0x000]),
+    Glyph::new('m', &[0x03c, 0x008, 0x00c, 0x038, 0x000]),
+    Glyph::new('n', &[0x03c, 0x004, 0x004, 0x038, 0x000]),
+    Glyph::new('o', &[0x018, 0x024, 0x024, 0x018, 0x000]),
+    Glyph::new('p', &[0x07c, 0x024, 0x024, 0x018, 0x000]),
+    Glyph::new('q', &[0x018, 0x024, 0x024, 0x07c, 0x000]),
+    Glyph::new('r', &[0x03c, 0x004, 0x004, 0x008, 0x000]),
+    Glyph::new('s', &[0x028, 0x02c, 0x034, 0x014, 0x000]),
+    Glyph::new('t', &[0x004, 0x01f, 0x024, 0x020, 0x000]),
+    Glyph::new('u', &[0x01c, 0x020, 0x020, 0x03c, 0x000]),
+    Glyph::new('v', &[0x000, 0x01c, 0x020, 0x01c, 0x000]),
+    Glyph::new('w', &[0x03c, 0x030, 0x030, 0x03c, 0x000]),
+    Glyph::new('x', &[0x024, 0x018, 0x018, 0x024, 0x000]),
+    Glyph::new('y', &[0x00c, 0x050, 0x020, 0x01c, 0x000]),
+    Glyph::new('z', &[0x024, 0x034, 0x02c, 0x024, 0x000]),
+    Glyph::new('{', &[0x000, 0x004, 0x01e, 0x021, 0x000]),
+    Glyph::new('|', &[0x000, 0x000, 0x03f, 0x000, 0x000]),
+    Glyph::new('}', &[0x000, 0x021, 0x01e, 0x004, 0x000]),
+    Glyph::new('~', &[0x002, 0x001, 0x002, 0x001, 0x000]),
+    Glyph::new('\u{a0}', &[0x000, 0x000, 0x000, 0x000, 0x000]),
+    Glyph::new('¡', &[0x000, 0x000, 0x03d, 0x000, 0x000]),
+    Glyph::new('¢', &[0x018, 0x024, 0x07e, 0x024, 0x000]),
+    Glyph::new('£', &[0x028, 0x01c, 0x02a, 0x022, 0x000]),
+    Glyph::new('¤', &[0x022, 0x01c, 0x014, 0x01c, 0x022]),
+    Glyph::new('¥', &[0x000, 0x00b, 0x03c, 0x00b, 0x000]),
+    Glyph::new('¦', &[0x000, 0x000, 0x036, 0x000, 0x000]),
+    Glyph::new('§', &[0x000, 0x04e, 0x055, 0x039, 0x000]),
+    Glyph::new('¨', &[0x000, 0x001, 0x000, 0x001, 0x000]),
+    Glyph::new('©', &[0x03e, 0x049, 0x055, 0x041, 0x03e]),
+    Glyph::new('ª', &[0x002, 0x005, 0x007, 0x000, 0x000]),
+    Glyph::new('«', &[0x008, 0x014, 0x000, 0x008, 0x014]),
+    Glyph::new('¬', &[0x008, 0x008, 0x008, 0x018, 0x000]),
+    Glyph::new('\u{ad}', &[0x000, 0x008, 0x008, 0x008, 0x000]),
+    Glyph::new('®', &[0x03e, 0x05d, 0x045, 0x041, 0x03e]),
+    Glyph::new('¯', &[0x001, 0x001, 0x001, 0x001, 0x000]),
+    Glyph::new('°', &[0x000, 0x002, 0x005, 0x002, 0x000]),
+    Glyph::new('±', &[0x024, 0x024, 0x03f, 0x024, 0x024]),
+    Glyph::new('²', &[0x000, 0x00d, 0x00b, 0x000, 0x000]),
+    Glyph::new('³', &[0x000, 0x00b, 0x00f, 0x000, 0x000]),
+    Glyph::new('´', &[0x000, 0x002, 0x001, 0x000, 0x000]),
+    Glyph::new('µ', &[0x07c, 0x020, 0x020, 0x01c, 0x000]),
+    Glyph::new('¶', &[0x006, 0x03f, 0x001, 0x03f, 0x000]),
+    Glyph::new('·', &[0x000, 0x00c, 0x00c, 0x000, 0x000]),
+    Glyph::new('¸', &[0x000, 0x040, 0x020, 0x000, 0x000]),
+    Glyph::new('¹', &[0x000, 0x00a, 0x00f, 0x008, 0x000]),
+    Glyph::new('º', &[0x002, 0x005, 0x002, 0x000, 0x000]),
+    Glyph::new('»', &[0x014, 0x008, 0x000, 0x014, 0x008]),
+    Glyph::new('¼', &[0x00f, 0x020, 0x030, 0x078, 0x000]),
+    Glyph::new('½', &[0x00f, 0x000, 0x068, 0x058, 0x000]),
+    Glyph::new('¾', &[0x00b, 0x02f, 0x030, 0x078, 0x000]),
+    Glyph::new('¿', &[0x000, 0x018, 0x025, 0x010, 0x000]),
+    Glyph::new('À', &[0x03e, 0x009, 0x009, 0x03e, 0x000]),
+    Glyph::new('Á', &[0x03e, 0x009, 0x009, 0x03e, 0x000]),
+    Glyph::new('Â', &[0x03e, 0x009, 0x009, 0x03e, 0x000]),
+    Glyph::new('Ã', &[0x03e, 0x009, 0x009, 0x03e, 0x000]),
+    Glyph::new('Ä', &[0x03d, 0x00a, 0x00a, 0x03d, 0x000]),
+    Glyph::new('Å', &[0x03c, 0x00b, 0x00b, 0x03c, 0x000]),
+    Glyph::new('Æ', &[0x03e, 0x009, 0x03f, 0x025, 0x000]),
+    Glyph::new('Ç', &[0x01e, 0x061, 0x021, 0x012, 0x000]),
+    Glyph::new('È', &[0x03f, 0x025, 0x025, 0x021, 0x000]),
+    Glyph::new('É', &[0x03f, 0x025, 0x025, 0x021, 0x000]),
+    Glyph::new('Ê', &[0x03f, 0x025, 0x025, 0x021, 0x000]),
+    Glyph::new('Ë', &[0x03f, 0x025, 0x025, 0x021, 0x000]),
+    Glyph::new('Ì', &[0x000, 0x021, 0x03f, 0x021, 0x000]),
+    Glyph::new('Í', &[0x000, 0x021, 0x03f, 0x021, 0x000]),
+    Glyph::new('Î', &[0x000, 0x021, 0x03f, 0x021, 0x000]),
+    Glyph::new('Ï', &[0x000, 0x021, 0x03f, 0x021, 0x000]),
+    Glyph::new('Ð', &[0x025, 0x03f, 0x021, 0x01e, 0x000]),
+    Glyph::new('Ñ', &[0x03f, 0x004, 0x019, 0x03f, 0x000]),
+    Glyph::new('Ò', &[0x01e, 0x021, 0x021, 0x01e, 0x000]),
+    Glyph::new('Ó', &[0x01e, 0x021, 0x021, 0x01e, 0x000]),
+    Glyph::new('Ô', &[0x01e, 0x021, 0x021, 0x01e, 0x000]),
+    Glyph::new('Õ', &[0x01e, 0x021, 0x021, 0x01e, 0x000]),
+    Glyph::new('Ö', &[0x01d, 0x022, 0x022, 0x01d, 0x000]),
+    Glyph::new('×', &[0x024, 0x018, 0x018, 0x024, 0x000]),
+    Glyph::new('Ø', &[0x03e, 0x039, 0x027, 0x01f, 0x000]),
+    Glyph::new('Ù', &[0x01f, 0x020, 0x020, 0x01f, 0x000]),
+    Glyph::new('Ú', &[0x01f, 0x020, 0x020, 0x01f, 0x000]),
+    Glyph::new('Û', &[0x01f, 0x020, 0x020, 0x01f, 0x000]),
+    Glyph::new('Ü', &[0x01d, 0x020, 0x020, 0x01d, 0x000]),
+    Glyph::new('Ý', &[0x000, 0x007, 0x038, 0x007, 0x000]),
+    Glyph::new('Þ', &[0x03f, 0x00a, 0x00a, 0x004, 0x000]),
+    Glyph::new('ß', &[0x03e, 0x001, 0x025, 0x01a, 0x000]),
+    Glyph::new('à', &[0x018, 0x025, 0x016, 0x03c, 0x000]),
+    Glyph::new('á', &[0x018, 0x026, 0x015, 0x03c, 0x000]),
+    Glyph::new('â', &[0x018, 0x026, 0x015, 0x03e, 0x000]),
+    Glyph::new('ã', &[0x01a, 0x025, 0x016, 0x03d, 0x000]),
+    Glyph::new('ä', &[0x018, 0x025, 0x014, 0x03d, 0x000]),
+    Glyph::new('å', &[0x018, 0x027, 0x017, 0x03c, 0x000]),
+    Glyph::new('æ', &[0x018, 0x024, 0x03c, 0x02c, 0x000]),
+    Glyph::new('ç', &[0x000, 0x018, 0x064, 0x024, 0x000]),
+    Glyph::new('è', &[0x018, 0x035, 0x02e, 0x008, 0x000]),
+    Glyph::new('é', &[0x018, 0x036, 0x02d, 0x008, 0x000]),
+    Glyph::new('ê', &[0x01a, 0x035, 0x02e, 0x008, 0x000]),
+    Glyph::new('ë', &[0x019, 0x034, 0x02d, 0x008, 0x000]),
+    Glyph::new('ì', &[0x000, 0x025, 0x03e, 0x020, 0x000]),
+    Glyph::new('í', &[0x000, 0x026, 0x03d, 0x020, 0x000]),
+    Glyph::new('î', &[0x000, 0x026, 0x03d, 0x022, 0x000]),
+    Glyph::new('ï', &[0x000, 0x025, 0x03c, 0x021, 0x000]),
+    Glyph::new('ð', &[0x018, 0x025, 0x026, 0x01a, 0x000]),
+    Glyph::new('ñ', &[0x03e, 0x005, 0x006, 0x039, 0x000]),
+    Glyph::new('ò', &[0x018, 0x025, 0x026, 0x018, 0x000]),
+    Glyph::new('ó', &[0x018, 0x026, 0x025, 0x018, 0x000]),
+    Glyph::new('ô', &[0x018, 0x025, 0x025, 0x018, 0x000]),
+    Glyph::new('õ', &[0x01a, 0x025, 0x026, 0x019, 0x000]),
+    Glyph::new('ö', &[0x018, 0x025, 0x024, 0x019, 0x000]),
+    Glyph::new('÷', &[0x008, 0x02a, 0x02a, 0x008, 0x000]),
+    Glyph::new('ø', &[0x038, 0x034, 0x02c, 0x01c, 0x000]),
+    Glyph::new('ù', &[0x01c, 0x021, 0x022, 0x03c, 0x000]),
+    Glyph::new('ú', &[0x01c, 0x022, 0x021, 0x03c, 0x000]),
+    Glyph::new('û', &[0x01c, 0x021, 0x021, 0x03c, 0x000]),
+    Glyph::new('ü', &[0x01c, 0x021, 0x020, 0x03d, 0x000]),
+    Glyph::new('ý', &[0x00c, 0x052, 0x021, 0x01c, 0x000]),
+    Glyph::new('þ', &[0x07e, 0x024, 0x024, 0x018, 0x000]),
+    Glyph::new('ÿ', &[0x00c, 0x051, 0x020, 0x01d, 0x000]),
+];
+
+const KERNING_4X7: &[KerningPair] = &[];
+
+/// Monospaced font with 5×6 pixel capitals, room for accents above and a two pixel descender.
+pub const FONT_5X9: BitmapFont = BitmapFont {
+    height: 9,
+    baseline: 6,
+    letter_spacing: 0,
+    glyphs: GLYPHS_5X9,
+    kerning: KERNING_5X9,
+    replacement: '?',
+};
+
+const GLYPHS_5X9: &[Glyph] = &[
+    Glyph::new(' ', &[0x000, 0x000, 0x000, 0x000, 0x000, 0x000]),
+    Glyph::new('!', &[0x000, 0x000, 0x05e, 0x000, 0x000, 0x000]),
+    Glyph::new('"', &[0x000, 0x00e, 0x000, 0x00e, 0x000, 0x000]),
+    Glyph::new('#', &[0x028, 0x0fe, 0x028, 0x0fe, 0x028, 0x000]),
+    Glyph::new('$', &[0x04c, 0x092, 0x1ff, 0x092, 0x064, 0x000]),
+    Glyph::new('%', &[0x002, 0x065, 0x012, 0x048, 0x0a6, 0x040]),
+    Glyph::new('&', &[0x06c, 0x092, 0x092, 0x06c, 0x0a0, 0x000]),
+    Glyph::new('\'', &[0x000, 0x000, 0x00e, 0x000, 0x000, 0x000]),
+    Glyph::new('(', &[0x000, 0x000, 0x07c, 0x082, 0x000, 0x000]),
+    Glyph::new(')', &[0x000, 0x000, 0x082, 0x07c, 0x000, 0x000]),
+    Glyph::new('*', &[0x054, 0x038, 0x010, 0x038, 0x054, 0x000]),
+    Glyph::new('+', &[0x010, 0x010, 0x07c, 0x010, 0x010, 0x000]),
+    Glyph::new(',', &[0x000, 0x000, 0x120, 0x0e0, 0x000, 0x000]),
+    Glyph::new('-', &[0x010, 0x010, 0x010, 0x010, 0x010, 0x000]),
+    Glyph::new('.', &[0x000, 0x000, 0x060, 0x060, 0x000, 0x000]),
+    Glyph::new('/', &[0x000, 0x060, 0x010, 0x008, 0x006, 0x000]),
+    Glyph::new('0', &[0x000, 0x03c, 0x042, 0x042, 0x03c, 0x000]),
+    Glyph::new('1', &[0x000, 0x044, 0x07e, 0x040, 0x000, 0x000]),
+    Glyph::new('2', &[0x000, 0x044, 0x062, 0x052, 0x04c, 0x000]),
+    Glyph::new('3', &[0x000, 0x042, 0x04a, 0x04e, 0x032, 0x000]),
+    Glyph::new('4', &[0x030, 0x028, 0x024, 0x07e, 0x020, 0x000]),
+    Glyph::new('5', &[0x000, 0x04e, 0x04a, 0x04a, 0x032, 0x000]),
+    Glyph::new('6', &[0x000, 0x03c, 0x04a, 0x04a, 0x030, 0x000]),
+    Glyph::new('7', &[0x000, 0x002, 0x062, 0x012, 0x00e, 0x000]),
+    Glyph::new('8', &[0x000, 0x034, 0x04a, 0x04a, 0x034, 0x000]),
+    Glyph::new('9', &[0x000, 0x00c, 0x052, 0x052, 0x03c, 0x000]),
+    Glyph::new(':', &[0x000, 0x000, 0x06c, 0x06c, 0x000, 0x000]),
+    Glyph::new(';', &[0x000, 0x000, 0x12c, 0x0ec, 0x000, 0x000]),
+    Glyph::new('<', &[0x010, 0x028, 0x028, 0x044, 0x044, 0x000]),
+    Glyph::new('=', &[0x028, 0x028, 0x028, 0x028, 0x028, 0x000]),
+    Glyph::new('>', &[0x044, 0x044, 0x028, 0x028, 0x010, 0x000]),
+    Glyph::new('?', &[0x000, 0x002, 0x059, 0x009, 0x006, 0x000]),
+    Glyph::new('@', &[0x03c, 0x042, 0x05a, 0x056, 0x008, 0x000]),
+    Glyph::new('A', &[0x078, 0x014, 0x012, 0x014, 0x078, 0x000]),
+    Glyph::new('B', &[0x07e, 0x04a, 0x04a, 0x04a, 0x034, 0x000]),
+    Glyph::new('C', &[0x000, 0x03c, 0x042, 0x042, 0x024, 0x000]),
+    Glyph::new('D', &[0x000, 0x07e, 0x042, 0x042, 0x03c, 0x000]),
+    Glyph::new('E', &[0x000, 0x07e, 0x04a, 0x04a, 0x042, 0x000]),
+    Glyph::new('F', &[0x000, 0x07e, 0x00a, 0x00a, 0x002, 0x000]),
+    Glyph::new('G', &[0x000, 0x03c, 0x042, 0x052, 0x034, 0x000]),
+    Glyph::new('H', &[0x000, 0x07e, 0x008, 0x008, 0x07e, 0x000]),
+    Glyph::new('I', &[0x000, 0x042, 0x07e, 0x042, 0x000, 0x000]),
+    Glyph::new('J', &[0x020, 0x040, 0x042, 0x03e, 0x002, 0x000]),
+    Glyph::new('K', &[0x000, 0x07e, 0x008, 0x014, 0x062, 0x000]),
+    Glyph::new('L', &[0x000, 0x07e, 0x040, 0x040, 0x040, 0x000]),
+    Glyph::new('M', &[0x07e, 0x004, 0x018, 0x004, 0x07e, 0x000]),
+    Glyph::new('N', &[0x000, 0x07e, 0x004, 0x008, 0x07e, 0x000]),
+    Glyph::new('O', &[0x03c, 0x042, 0x042, 0x042, 0x03c, 0x000]),
+    Glyph::new('P', &[0x000, 0x07e, 0x012, 0x012, 0x00c, 0x000]),
+    Glyph::new('Q', &[0x000, 0x03c, 0x052, 0x062, 0x0bc, 0x000]),
+    Glyph::new('R', &[0x000, 0x07e, 0x012, 0x012, 0x06c, 0x000]),
+    Glyph::new('S', &[0x000, 0x024, 0x04a, 0x052, 0x024, 0x000]),
+    Glyph::new('T', &[0x002, 0x002, 0x07e, 0x002, 0x002, 0x000]),
+    Glyph::new('U', &[0x000, 0x03e, 0x040, 0x040, 0x03e, 0x000]),
+    Glyph::new('V', &[0x000, 0x01e, 0x070, 0x070, 0x01e, 0x000]),
+    Glyph::new('W', &[0x07e, 0x020, 0x018, 0x020, 0x07e, 0x000]),
+    Glyph::new('X', &[0x042, 0x024, 0x018, 0x024, 0x042, 0x000]),
+    Glyph::new('Y', &[0x006, 0x008, 0x070, 0x008, 0x006, 0x000]),
+    Glyph::new('Z', &[0x000, 0x062, 0x052, 0x04a, 0x046, 0x000]),
+    Glyph::new('[', &[0x000, 0x07e, 0x042, 0x042, 0x000, 0x000]),
+    Glyph::new('\\', &[0x000, 0x006, 0x008, 0x010, 0x060, 0x000]),
+    Glyph::new(']', &[0x000, 0x042, 0x042, 0x07e, 0x000, 0x000]),
+    Glyph::new('^', &[0x008, 0x004, 0x002, 0x004, 0x008, 0x000]),
+    Glyph::new('_', &[0x100, 0x100, 0x100, 0x100, 0x100, 0x000]),
+    Glyph::new('`', &[0x000, 0x000, 0x002, 0x004, 0x000, 0x000]),
+    Glyph::new('a', &[0x000, 0x030, 0x048, 0x048, 0x078, 0x000]),
+    Glyph::new('b', &[0x000, 0x07e, 0x048, 0x048, 0x030, 0x000]),
+    Glyph::new('c', &[0x000, 0x030, 0x048, 0x048, 0x048, 0x000]),
+    Glyph::new('d', &[0x000, 0x030, 0x048, 0x048, 0x07e, 0x000]),
+    Glyph::new('e', &[0x000, 0x030, 0x068, 0x058, 0x050, 0x000]),
+    Glyph::new('f', &[0x000, 0x010, 0x07c, 0x012, 0x004, 0x000]),
+    Glyph::new('g', &[0x000, 0x030, 0x148, 0x148, 0x0f0, 0x000]),
+    Glyph::new('h', &[0x000, 0x07e, 0x008, 0x008, 0x070, 0x000]),
+    Glyph::new('i', &[0x000, 0x048, 0x07a, 0x040, 0x000, 0x000]),
+    Glyph::new('j', &[0x000, 0x080, 0x108, 0x0fa, 0x000, 0x000]),
+    Glyph::new('k', &[0x000, 0x07e, 0x010, 0x028, 0x040, 0x000]),
+    Glyph::new('l', &[0x000, 0x042, 0x07e, 0x040, 0x000, 0x000]),
+    Glyph::new('m', &[0x078, 0x008, 0x030, 0x008, 0x070, 0x000]),
+    Glyph::new('n', &[0x000, 0x078, 0x008, 0x008, 0x070, 0x000]),
+    Glyph::new('o', &[0x000, 0x030, 0x048, 0x048, 0x030, 0x000]),
+    Glyph::new('p', &[0x000, 0x1f8, 0x048, 0x048, 0x030, 0x000]),
+    Glyph::new('q', &[0x000, 0x030, 0x048, 0x048, 0x1f8, 0x000]),
+    Glyph::new('r', &[0x000, 0x078, 0x010, 0x008, 0x010, 0x000]),
+    Glyph::new('s', &[0x000, 0x050, 0x058, 0x068, 0x028, 0x000]),
+    Glyph::new('t', &[0x000, 0x008, 0x03e, 0x048, 0x020, 0x000]),
+    Glyph::new('u', &[0x000, 0x038, 0x040, 0x040, 0x078, 0x000]),
+    Glyph::new('v', &[0x000, 0x018, 0x060, 0x060, 0x018, 0x000]),
+    Glyph::new('w', &[0x038, 0x040, 0x030, 0x040, 0x038, 0x000]),
+    Glyph::new('x', &[0x000, 0x048, 0x030, 0x030, 0x048, 0x000]),
+    Glyph::new('y', &[0x000, 0x0b8, 0x140, 0x140, 0x0f8, 0x000]),
+    Glyph::new('z', &[0x000, 0x048, 0x068, 0x058, 0x048, 0x000]),
+    Glyph::new('{', &[0x000, 0x008, 0x036, 0x041, 0x000, 0x000]),
+    Glyph::new('|', &[0x000, 0x000, 0x0fe, 0x000, 0x000, 0x000]),
+    Glyph::new('}', &[0x000, 0x041, 0x036, 0x008, 0x000, 0x000]),
+    Glyph::new('~', &[0x000, 0x008, 0x004, 0x008, 0x004, 0x000]),
+    Glyph::new('\u{a0}', &[0x000, 0x000, 0x000, 0x000, 0x000, 0x000]),
+    Glyph::new('¡', &[0x000, 0x000, 0x07a, 0x000, 0x000, 0x000]),
+    Glyph::new('¢', &[0x000, 0x030, 0x048, 0x0fc, 0x048, 0x000]),
+    Glyph::new('£', &[0x090, 0x0fc, 0x092, 0x092, 0x084, 0x000]),
+    Glyph::new('¤', &[0x054, 0x028, 0x044, 0x028, 0x054, 0x000]),
+    Glyph::new('¥', &[0x02a, 0x02c, 0x078, 0x02c, 0x02a, 0x000]),
+    Glyph::new('¦', &[0x000, 0x000, 0x0ee, 0x000, 0x000, 0x000]),
+    Glyph::new('§', &[0x000, 0x094, 0x0aa, 0x0aa, 0x052, 0x000]),
+    Glyph::new('¨', &[0x000, 0x002, 0x000, 0x002, 0x000, 0x000]),
+    Glyph::new('©', &[0x03e, 0x041, 0x049, 0x055, 0x041, 0x03e]),
+    Glyph::new('ª', &[0x000, 0x024, 0x02a, 0x02e, 0x000, 0x000]),
+    Glyph::new('«', &[0x010, 0x028, 0x054, 0x028, 0x044, 0x000]),
+    Glyph::new('¬', &[0x000, 0x010, 0x010, 0x010, 0x070, 0x000]),
+    Glyph::new('\u{ad}', &[0x000, 0x010, 0x010, 0x010, 0x010, 0x000]),
+    Glyph::new('®', &[0x03e, 0x041, 0x05d, 0x045, 0x041, 0x03e]),
+    Glyph::new('¯', &[0x000, 0x002, 0x002, 0x002, 0x002, 0x000]),
+    Glyph::new('°', &[0x000, 0x008, 0x014, 0x014, 0x008, 0x000]),
+    Glyph::new('±', &[0x088, 0x088, 0x0be, 0x088, 0x088, 0x000]),
+    Glyph::new('²', &[0x000, 0x024, 0x032, 0x02c, 0x000, 0x000]),
+    Glyph::new('³', &[0x000, 0x022, 0x02a, 0x014, 0x000, 0x000]),
+    Glyph::new('´', &[0x000, 0x000, 0x004, 0x002, 0x000, 0x000]),
+    Glyph::new('µ', &[0x000, 0x0f8, 0x040, 0x020, 0x078, 0x000]),
+    Glyph::new('¶', &[0x00c, 0x01e, 0x07e, 0x002, 0x07e, 0x000]),
+    Glyph::new('·', &[0x000, 0x000, 0x010, 0x000, 0x000, 0x000]),
+    Glyph::new('¸', &[0x000, 0x000, 0x100, 0x080, 0x000, 0x000]),
+    Glyph::new('¹', &[0x000, 0x024, 0x03e, 0x020, 0x000, 0x000]),
+    Glyph::new('º', &[0x000, 0x024, 0x02a, 0x024, 0x000, 0x000]),
+    Glyph::new('»', &[0x044, 0x028, 0x054, 0x028, 0x010, 0x000]),
+    Glyph::new('¼', &[0x002, 0x05f, 0x070, 0x0f8, 0x040, 0x000]),
+    Glyph::new('½', &[0x002, 0x01f, 0x090, 0x0c8, 0x0b0, 0x000]),
+    Glyph::new('¾', &[0x011, 0x055, 0x07a, 0x0f8, 0x040, 0x000]),
+    Glyph::new('¿', &[0x000, 0x030, 0x048, 0x04d, 0x020, 0x000]),
+    Glyph::new('À', &[0x060, 0x019, 0x016, 0x018, 0x060, 0x000]),
+    Glyph::new('Á', &[0x060, 0x018, 0x016, 0x019, 0x060, 0x000]),
+    Glyph::new('Â', &[0x060, 0x01a, 0x015, 0x01a, 0x060, 0x000]),
+    Glyph::new('Ã', &[0x060, 0x01a, 0x015, 0x01a, 0x061, 0x000]),
+    Glyph::new('Ä', &[0x060, 0x019, 0x014, 0x019, 0x060, 0x000]),
+    Glyph::new('Å', &[0x060, 0x01a, 0x015, 0x01a, 0x060, 0x000]),
+    Glyph::new('Æ', &[0x07c, 0x00a, 0x07e, 0x04a, 0x042, 0x000]),
+    Glyph::new('Ç', &[0x000, 0x03c, 0x142, 0x0c2, 0x024, 0x000]),
+    Glyph::new('È', &[0x000, 0x07c, 0x055, 0x056, 0x044, 0x000]),
+    Glyph::new('É', &[0x000, 0x07c, 0x056, 0x055, 0x044, 0x000]),
+    Glyph::new('Ê', &[0x000, 0x07c, 0x056, 0x055, 0x046, 0x000]),
+    Glyph::new('Ë', &[0x000, 0x07c, 0x055, 0x054, 0x045, 0x000]),
+    Glyph::new('Ì', &[0x000, 0x045, 0x07e, 0x044, 0x000, 0x000]),
+    Glyph::new('Í', &[0x000, 0x044, 0x07e, 0x045, 0x000, 0x000]),
+    Glyph::new('Î', &[0x000, 0x046, 0x07d, 0x046, 0x000, 0x000]),
+    Glyph::new('Ï', &[0x000, 0x045, 0x07c, 0x045, 0x000, 0x000]),
+    Glyph::new('Ð', &[0x008, 0x07e, 0x04a, 0x042, 0x03c, 0x000]),
+    Glyph::new('Ñ', &[0x000, 0x07e, 0x009, 0x012, 0x07d, 0x000]),
+    Glyph::new('Ò', &[0x000, 0x038, 0x045, 0x046, 0x038, 0x000]),
+    Glyph::new('Ó', &[0x000, 0x038, 0x046, 0x045, 0x038, 0x000]),
+    Glyph::new('Ô', &[0x000, 0x038, 0x046, 0x045, 0x03a, 0x000]),
+    Glyph::new('Õ', &[0x000, 0x03a, 0x045, 0x046, 0x039, 0x000]),
+    Glyph::new('Ö', &[0x000, 0x038, 0x045, 0x044, 0x039, 0x000]),
+    Glyph::new('×', &[0x044, 0x028, 0x010, 0x028, 0x044, 0x000]),
+    Glyph::new('Ø', &[0x000, 0x0fc, 0x072, 0x04e, 0x03f, 0x000]),
+    Glyph::new('Ù', &[0x000, 0x03c, 0x041, 0x042, 0x03c, 0x000]),
+    Glyph::new('Ú', &[0x000, 0x03c, 0x042, 0x041, 0x03c, 0x000]),
+    Glyph::new('Û', &[0x000, 0x03c, 0x042, 0x041, 0x03e, 0x000]),
+    Glyph::new('Ü', &[0x000, 0x03c, 0x041, 0x040, 0x03d, 0x000]),
+    Glyph::new('Ý', &[0x004, 0x008, 0x072, 0x009, 0x004, 0x000]),
+    Glyph::new('Þ', &[0x000, 0x07e, 0x024, 0x024, 0x018, 0x000]),
+    Glyph::new('ß', &[0x000, 0x07c, 0x002, 0x05a, 0x024, 0x000]),
+    Glyph::new('à', &[0x000, 0x030, 0x049, 0x04a, 0x078, 0x000]),
+    Glyph::new('á', &[0x000, 0x030, 0x04a, 0x049, 0x078, 0x000]),
+    Glyph::new('â', &[0x000, 0x030, 0x04a, 0x049, 0x07a, 0x000]),
+    Glyph::new('ã', &[0x000, 0x032, 0x049, 0x04a, 0x079, 0x000]),
+    Glyph::new('ä', &[0x000, 0x030, 0x04a, 0x048, 0x07a, 0x000]),
+    Glyph::new('å', &[0x000, 0x030, 0x04a, 0x04d, 0x07a, 0x000]),
+    Glyph::new('æ', &[0x030, 0x048, 0x078, 0x068, 0x050, 0x000]),
+    Glyph::new('ç', &[0x000, 0x030, 0x148, 0x0c8, 0x048, 0x000]),
+    Glyph::new('è', &[0x000, 0x030, 0x069, 0x05a, 0x050, 0x000]),
+    Glyph::new('é', &[0x000, 0x030, 0x06a, 0x059, 0x050, 0x000]),
+    Glyph::new('ê', &[0x000, 0x030, 0x06a, 0x059, 0x052, 0x000]),
+    Glyph::new('ë', &[0x000, 0x030, 0x06a, 0x058, 0x052, 0x000]),
+    Glyph::new('ì', &[0x000, 0x049, 0x07a, 0x040, 0x000, 0x000]),
+    Glyph::new('í', &[0x000, 0x048, 0x07a, 0x041, 0x000, 0x000]),
+    Glyph::new('î', &[0x000, 0x04a, 0x079, 0x042, 0x000, 0x000]),
+    Glyph::new('ï', &[0x000, 0x04a, 0x078, 0x042, 0x000, 0x000]),
+    Glyph::new('ð', &[0x000, 0x030, 0x04a, 0x04b, 0x03d, 0x000]),
+    Glyph::new('ñ', &[0x000, 0x07a, 0x009, 0x00a, 0x071, 0x000]),
+    Glyph::new('ò', &[0x000, 0x030, 0x049, 0x04a, 0x030, 0x000]),
+    Glyph::new('ó', &[0x000, 0x030, 0x04a, 0x049, 0x030, 0x000]),
+    Glyph::new('ô', &[0x000, 0x030, 0x04a, 0x049, 0x032, 0x000]),
+    Glyph::new('õ', &[0x000, 0x032, 0x049, 0x04a, 0x031, 0x000]),
+    Glyph::new('ö', &[0x000, 0x030, 0x04a, 0x048, 0x032, 0x000]),
+    Glyph::new('÷', &[0x010, 0x010, 0x054, 0x010, 0x010, 0x000]),
+    Glyph::new('ø', &[0x000, 0x070, 0x068, 0x058, 0x038, 0x000]),
+    Glyph::new('ù', &[0x000, 0x038, 0x041, 0x042, 0x078, 0x000]),
+    Glyph::new('ú', &[0x000, 0x038, 0x042, 0x041, 0x078, 0x000]),
+    Glyph::new('û', &[0x000, 0x038, 0x042, 0x041, 0x07a, 0x000]),
+    Glyph::new('ü', &[0x000, 0x038, 0x042, 0x040, 0x07a, 0x000]),
+    Glyph::new('ý', &[0x000, 0x0b8, 0x142, 0x141, 0x0f8, 0x000]),
+    Glyph::new('þ', &[0x000, 0x1fe, 0x048, 0x048, 0x030, 0x000]),
+    Glyph::new('ÿ', &[0x000, 0x0b8, 0x142, 0x140, 0x0fa, 0x000]),
+];
+
+const KERNING_5X9: &[KerningPair] = &[];
+
+/// Proportional font with the glyphs of [`FONT_4X7`], fitting the most text on the display.
+pub const FONT_PROPORTIONAL: BitmapFont = BitmapFont {
+    height: 7,
+    baseline: 5,
+    letter_spacing: 1,
+    glyphs: GLYPHS_PROPORTIONAL,
+    kerning: KERNING_PROPORTIONAL,
+    replacement: '?',
+};
+
+const GLYPHS_PROPORTIONAL: &[Glyph] = &[
+    Glyph::new(' ', &[0x000, 0x000]),
+    Glyph::new('!', &[0x02f]),
+    Glyph::new('"', &[0x007, 0x000, 0x007]),
+    Glyph::new('#', &[0x014, 0x03e, 0x014, 0x03e, 0x014]),
+    Glyph::new('$', &[0x004, 0x02a, 0x03e, 0x02a, 0x010]),
+    Glyph::new('%', &[0x013, 0x008, 0x004, 0x032]),
+    Glyph::new('&', &[0x014, 0x02a, 0x014, 0x020]),
+    Glyph::new('\'', &[0x007]),
+    Glyph::new('(', &[0x01e, 0x021]),
+    Glyph::new(')', &[0x021, 0x01e]),
+    Glyph::new('*', &[0x02a, 0x01c, 0x02a]),
+    Glyph::new('+', &[0x008, 0x008, 0x03e, 0x008, 0x008]),
+    Glyph::new(',', &[0x040, 0x030, 0x010]),
+    Glyph::new('-', &[0x008, 0x008, 0x008, 0x008]),
+    Glyph::new('.', &[0x030, 0x030]),
+    Glyph::new('/', &[0x010, 0x008, 0x004, 0x002]),
+    Glyph::new('0', &[0x01e, 0x021, 0x01e]),
+    Glyph::new('1', &[0x022, 0x03f, 0x020]),
+    Glyph::new('2', &[0x022, 0x031, 0x029, 0x026]),
+    Glyph::new('3', &[0x011, 0x025, 0x025, 0x01b]),
+    Glyph::new('4', &[0x00c, 0x00a, 0x03f, 0x008]),
+    Glyph::new('5', &[0x017, 0x025, 0x025, 0x019]),
+    Glyph::new('6', &[0x01e, 0x025, 0x025, 0x018]),
+    Glyph::new('7', &[0x001, 0x031, 0x00d, 0x003]),
+    Glyph::new('8', &[0x01a, 0x025, 0x025, 0x01a]),
+    Glyph::new('9', &[0x006, 0x029, 0x029, 0x01e]),
+    Glyph::new(':', &[0x036, 0x036]),
+    Glyph::new(';', &[0x040, 0x036, 0x016]),
+    Glyph::new('<', &[0x008, 0x014, 0x022]),
+    Glyph::new('=', &[0x014, 0x014, 0x014, 0x014]),
+    Glyph::new('>', &[0x022, 0x014, 0x008]),
+    Glyph::new('?', &[0x002, 0x029, 0x006]),
+    Glyph::new('@', &[0x01e, 0x021, 0x02d, 0x00e]),
+    Glyph::new('A', &[0x03e, 0x009, 0x009, 0x03e]),
+    Glyph::new('B', &[0x03f, 0x025, 0x025, 0x01a]),
+    Glyph::new('C', &[0x01e, 0x021, 0x021, 0x012]),
+    Glyph::new('D', &[0x03f, 0x021, 0x021, 0x01e]),
+    Glyph::new('E', &[0x03f, 0x025, 0x025, 0x021]),
+    Glyph::new('F', &[0x03f, 0x005, 0x005, 0x001]),
+    Glyph::new('G', &[0x01e, 0x021, 0x029, 0x03a]),
+    Glyph::new('H', &[0x03f, 0x004, 0x004, 0x03f]),
+    Glyph::new('I', &[0x021, 0x03f, 0x021]),
+    Glyph::new('J', &[0x010, 0x020, 0x020, 0x01f]),
+    Glyph::new('K', &[0x03f, 0x00c, 0x012, 0x021]),
+    Glyph::new('L', &[0x03f, 0x020, 0x020, 0x020]),
+    Glyph::new('M', &[0x03f, 0x006, 0x006, 0x03f]),
+    Glyph::new('N', &[0x03f, 0x006, 0x018, 0x03f]),
+    Glyph::new('O', &[0x01e, 0x021, 0x021, 0x01e]),
+    Glyph::new('P', &[0x03f, 0x009, 0x009, 0x006]),
+    Glyph::new('Q', &[0x01e, 0x031, 0x021, 0x05e]),
+    Glyph::new('R', &[0x03f, 0x009, 0x019, 0x026]),
+    Glyph::new('S', &[0x012, 0x025, 0x029, 0x012]),
+    Glyph::new('T', &[0x001, 0x03f, 0x001]),
+    Glyph::new('U', &[0x01f, 0x020, 0x020, 0x01f]),
+    Glyph::new('V', &[0x00f, 0x030, 0x030, 0x00f]),
+    Glyph::new('W', &[0x03f, 0x018, 0x018, 0x03f]),
+    Glyph::new('X', &[0x033, 0x00c, 0x00c, 0x033]),
+    Glyph::new('Y', &[0x007, 0x038, 0x007]),
+    Glyph::new('Z', &[0x031, 0x029, 0x025, 0x023]),
+    Glyph::new('[', &[0x03f, 0x021, 0x021]),
+    Glyph::new('\\', &[0x002, 0x004, 0x008, 0x010]),
+    Glyph::new(']', &[0x021, 0x021, 0x03f]),
+    Glyph::new('^', &[0x002, 0x001, 0x002]),
+    Glyph::new('_', &[0x020, 0x020, 0x020, 0x020]),
+    Glyph::new('`', &[0x001, 0x002]),
+    Glyph::new('a', &[0x018, 0x024, 0x014, 0x03c]),
+    Glyph::new('b', &[0x03f, 0x024, 0x024, 0x018]),
+    Glyph::new('c', &[0x018, 0x024, 0x024]),
+    Glyph::new('d', &[0x018, 0x024, 0x024, 0x03f]),
+    Glyph::new('e', &[0x018, 0x034, 0x02c, 0x008]),
+    Glyph::new('f', &[0x008, 0x03e, 0x009, 0x002]),
+    Glyph::new('g', &[0x028, 0x054, 0x054, 0x04c]),
+    Glyph::new('h', &[0x03f, 0x004, 0x004, 0x038]),
+    Glyph::new('i', &[0x024, 0x03d, 0x020]),
+    Glyph::new('j', &[0x020, 0x040, 0x03d]),
+    Glyph::new('k', &[0x03f, 0x008, 0x014, 0x020]),
+    Glyph::new('l', &[0x021, 0x03f, 0x020]),
+    Glyph::new('m', &[0x03c, 0x008, 0x00c, 0x038]),
+    Glyph::new('n', &[0x03c, 0x004, 0x004, 0x038]),
+    Glyph::new('o', &[0x018, 0x024, 0x024, 0x018]),
+    Glyph::new('p', &[0x07c, 0x024, 0x024, 0x018]),
+    Glyph::new('q', &[0x018, 0x024, 0x024, 0x07c]),
+    Glyph::new('r', &[0x03c, 0x004, 0x004, 0x008]),
+    Glyph::new('s', &[0x028, 0x02c, 0x034, 0x014]),
+    Glyph::new('t', &[0x004, 0x01f, 0x024, 0x020]),
+    Glyph::new('u', &[0x01c, 0x020, 0x020, 0x03c]),
+    Glyph::new('v', &[0x01c, 0x020, 0x01c]),
+    Glyph::new('w', &[0x03c, 0x030, 0x030, 0x03c]),
+    Glyph::new('x', &[0x024, 0x018, 0x018, 0x024]),
+    Glyph::new('y', &[0x00c, 0x050, 0x020, 0x01c]),
+    Glyph::new('z', &[0x024, 0x034, 0x02c, 0x024]),
+    Glyph::new('{', &[0x004, 0x01e, 0x021]),
+    Glyph::new('|', &[0x03f]),
+    Glyph::new('}', &[0x021, 0x01e, 0x004]),
+    Glyph::new('~', &[0x002, 0x001, 0x002, 0x001]),
+    Glyph::new('\u{a0}', &[0x000, 0x000]),
+    Glyph::new('¡', &[0x03d]),
+    Glyph::new('¢', &[0x018, 0x024, 0x07e, 0x024]),
+    Glyph::new('£', &[0x028, 0x01c, 0x02a, 0x022]),
+    Glyph::new('¤', &[0x022, 0x01c, 0x014, 0x01c, 0x022]),
+    Glyph::new('¥', &[0x00b, 0x03c, 0x00b]),
+    Glyph::new('¦', &[0x036]),
+    Glyph::new('§', &[0x04e, 0x055, 0x039]),
+    Glyph::new('¨', &[0x001, 0x000, 0x001]),
+    Glyph::new('©', &[0x03e, 0x049, 0x055, 0x041, 0x03e]),
+    Glyph::new('ª', &[0x002, 0x005, 0x007]),
+    Glyph::new('«', &[0x008, 0x014, 0x000, 0x008, 0x014]),
+    Glyph::new('¬', &[0x008, 0x008, 0x008, 0x018]),
+    Glyph::new('\u{ad}', &[0x008, 0x008, 0x008]),
+    Glyph::new('®', &[0x03e, 0x05d, 0x045, 0x041, 0x03e]),
+    Glyph::new('¯', &[0x001, 0x001, 0x001, 0x001]),
+    Glyph::new('°', &[0x002, 0x005, 0x002]),
+    Glyph::new('±', &[0x024, 0x024, 0x03f, 0x024, 0x024]),
+    Glyph::new('²', &[0x00d, 0x00b]),
+    Glyph::new('³', &[0x00b, 0x00f]),
+    Glyph::new('´', &[0x002, 0x001]),
+    Glyph::new('µ', &[0x07c, 0x020, 0x020, 0x01c]),
+    Glyph::new('¶', &[0x006, 0x03f, 0x001, 0x03f]),
+    Glyph::new('·', &[0x00c, 0x00c]),
+    Glyph::new('¸', &[0x040, 0x020]),
+    Glyph::new('¹', &[0x00a, 0x00f, 0x008]),
+    Glyph::new('º', &[0x002, 0x005, 0x002]),
+    Glyph::new('»', &[0x014, 0x008, 0x000, 0x014, 0x008]),
+    Glyph::new('¼', &[0x00f, 0x020, 0x030, 0x078]),
+    Glyph::new('½', &[0x00f, 0x000, 0x068, 0x058]),
+    Glyph::new('¾', &[0x00b, 0x02f, 0x030, 0x078]),
+    Glyph::new('¿', &[0x018, 0x025, 0x010]),
+    Glyph::new('À', &[0x03e, 0x009, 0x009, 0x03e]),
+    Glyph::new('Á', &[0x03e, 0x009, 0x009, 0x03e]),
+    Glyph::new('Â', &[0x03e, 0x009, 0x009, 0x03e]),
+    Glyph::new('Ã', &[0x03e, 0x009, 0x009, 0x03e]),
+    Glyph::new('Ä', &[0x03d, 0x00a, 0x00a, 0x03d]),
+    Glyph::new('Å', &[0x03c, 0x00b, 0x00b, 0x03c]),
+    Glyph::new('Æ', &[0x03e, 0x009, 0x03f, 0x025]),
+    Glyph::new('Ç', &[0x01e, 0x061, 0x021, 0x012]),
+    Glyph::new('È', &[0x03f, 0x025, 0x025, 0x021]),
+    Glyph::new('É', &[0x03f, 0x025, 0x025, 0x021]),
+    Glyph::new('Ê', &[0x03f, 0x025, 0x025, 0x021]),
+    Glyph::new('Ë', &[0x03f, 0x025, 0x025, 0x021]),
+    Glyph::new('Ì', &[0x021, 0x03f, 0x021]),
+    Glyph::new('Í', &[0x021, 0x03f, 0x021]),
+    Glyph::new('Î', &[0x021, 0x03f, 0x021]),
+    Glyph::new('Ï', &[0x021, 0x03f, 0x021]),
+    Glyph::new('Ð', &[0x025, 0x03f, 0x021, 0x01e]),
+    Glyph::new('Ñ', &[0x03f, 0x004, 0x019, 0x03f]),
+    Glyph::new('Ò', &[0x01e, 0x021, 0x021, 0x01e]),
+    Glyph::new('Ó', &[0x01e, 0x021, 0x021, 0x01e]),
+    Glyph::new('Ô', &[0x01e, 0x021, 0x021, 0x01e]),
+    Glyph::new('Õ', &[0x01e, 0x021, 0x021, 0x01e]),
+    Glyph::new('Ö', &[0x01d, 0x022, 0x022, 0x01d]),
+    Glyph::new('×', &[0x024, 0x018, 0x018, 0x024]),
+    Glyph::new('Ø', &[0x03e, 0x039, 0x027, 0x01f]),
+    Glyph::new('Ù', &[0x01f, 0x020, 0x020, 0x01f]),
+    Glyph::new('Ú', &[0x01f, 0x020, 0x020, 0x01f]),
+    Glyph::new('Û', &[0x01f, 0x020, 0x020, 0x01f]),
+    Glyph::new('Ü', &[0x01d, 0x020, 0x020, 0x01d]),
+    Glyph::new('Ý', &[0x007, 0x038, 0x007]),
+    Glyph::new('Þ', &[0x03f, 0x00a, 0x00a, 0x004]),
+    Glyph::new('ß', &[0x03e, 0x001, 0x025, 0x01a]),
+    Glyph::new('à', &[0x018, 0x025, 0x016, 0x03c]),
+    Glyph::new('á', &[0x018, 0x026, 0x015, 0x03c]),
+    Glyph::new('â', &[0x018, 0x026, 0x015, 0x03e]),
+    Glyph::new('ã', &[0x01a, 0x025, 0x016, 0x03d]),
+    Glyph::new('ä', &[0x018, 0x025, 0x014, 0x03d]),
+    Glyph::new('å', &[0x018, 0x027, 0x017, 0x03c]),
+    Glyph::new('æ', &[0x018, 0x024, 0x03c, 0x02c]),
+    Glyph::new('ç', &[0x018, 0x064, 0x024]),
+    Glyph::new('è', &[0x018, 0x035, 0x02e, 0x008]),
+    Glyph::new('é', &[0x018, 0x036, 0x02d, 0x008]),
+    Glyph::new('ê', &[0x01a, 0x035, 0x02e, 0x008]),
+    Glyph::new('ë', &[0x019, 0x034, 0x02d, 0x008]),
+    Glyph::new('ì', &[0x025, 0x03e, 0x020]),
+    Glyph::new('í', &[0x026, 0x03d, 0x020]),
+    Glyph::new('î', &[0x026, 0x03d, 0x022]),
+    Glyph::new('ï', &[0x025, 0x03c, 0x021]),
+    Glyph::new('ð', &[0x018, 0x025, 0x026, 0x01a]),
+    Glyph::new('ñ', &[0x03e, 0x005, 0x006, 0x039]),
+    Glyph::new('ò', &[0x018, 0x025, 0x026, 0x018]),
+    Glyph::new('ó', &[0x018, 0x026, 0x025, 0x018]),
+    Glyph::new('ô', &[0x018, 0x025, 0x025, 0x018]),
+    Glyph::new('õ', &[0x01a, 0x025, 0x026, 0x019]),
+    Glyph::new('ö', &[0x018, 0x025, 0x024, 0x019]),
+    Glyph::new('÷', &[0x008, 0x02a, 0x02a, 0x008]),
+    Glyph::new('ø', &[0x038, 0x034, 0x02c, 0x01c]),
+    Glyph::new('ù', &[0x01c, 0x021, 0x022, 0x03c]),
+    Glyph::new('ú', &[0x01c, 0x022, 0x021, 0x03c]),
+    Glyph::new('û', &[0x01c, 0x021, 0x021, 0x03c]),
+    Glyph::new('ü', &[0x01c, 0x021, 0x020, 0x03d]),
+    Glyph::new('ý', &[0x00c, 0x052, 0x021, 0x01c]),
+    Glyph::new('þ', &[0x07e, 0x024, 0x024, 0x018]),
+    Glyph::new('ÿ', &[0x00c, 0x051, 0x020, 0x01d]),
+];
+
+const KERNING_PROPORTIONAL: &[KerningPair] = &[
+    KerningPair::new('"', ',', -1),
+    KerningPair::new('"', '.', -1),
+    KerningPair::new('"', 'J', -1),
+    KerningPair::new('"', 'j', -1),
+    KerningPair::new('\'', ',', -1),
+    KerningPair::new('\'', '.', -1),
+    KerningPair::new('\'', 'J', -1),
+    KerningPair::new('\'', 'j', -1),
+    KerningPair::new('F', ',', -1),
+    KerningPair::new('F', '.', -1),
+    KerningPair::new('F', 'J', -1),
+    KerningPair::new('F', 'a', -1),
+    KerningPair::new('F', 'c', -1),
+    KerningPair::new('F', 'd', -1),
+    KerningPair::new('F', 'e', -1),
+    KerningPair::new('F', 'g', -1),
+    KerningPair::new('F', 'j', -1),
+    KerningPair::new('F', 'm', -1),
+    KerningPair::new('F', 'n', -1),
+    KerningPair::new('F', 'o', -1),
+    KerningPair::new('F', 'p', -1),
+    KerningPair::new('F', 'q', -1),
+    KerningPair::new('F', 'r', -1),
+    KerningPair::new('F', 's', -1),
+    KerningPair::new('F', 'u', -1),
+    KerningPair::new('F', 'v', -1),
+    KerningPair::new('F', 'w', -1),
+    KerningPair::new('F', 'x', -1),
+    KerningPair::new('F', 'y', -1),
+    KerningPair::new('F', 'z', -1),
+    KerningPair::new('L', '"', -1),
+    KerningPair::new('L', '\'', -1),
+    KerningPair::new('L', 'T', -1),
+    KerningPair::new('L', 'V', -1),
+    KerningPair::new('L', 'Y', -1),
+    KerningPair::new('L', 'y', -1),
+    KerningPair::new('P', ',', -1),
+    KerningPair::new('P', '.', -1),
+    KerningPair::new('P', 'J', -1),
+    KerningPair::new('P', 'j', -1),
+    KerningPair::new('T', ',', -1),
+    KerningPair::new('T', '.', -1),
+    KerningPair::new('T', 'J', -1),
+    KerningPair::new('T', 'a', -1),
+    KerningPair::new('T', 'c', -1),
+    KerningPair::new('T', 'd', -1),
+    KerningPair::new('T', 'e', -1),
+    KerningPair::new('T', 'g', -1),
+    KerningPair::new('T', 'j', -1),
+    KerningPair::new('T', 'm', -1),
+    KerningPair::new('T', 'n', -1),
+    KerningPair::new('T', 'o', -1),
+    KerningPair::new('T', 'p', -1),
+    KerningPair::new('T', 'q', -1),
+    KerningPair::new('T', 'r', -1),
+    KerningPair::new('T', 's', -1),
+    KerningPair::new('T', 'u', -1),
+    KerningPair::new('T', 'v', -1),
+    KerningPair::new('T', 'w', -1),
+    KerningPair::new('T', 'x', -1),
+    KerningPair::new('T', 'y', -1),
+    KerningPair::new('T', 'z', -1),
+    KerningPair::new('V', ',', -1),
+    KerningPair::new('V', 'j', -1),
+    KerningPair::new('Y', ',', -1),
+    KerningPair::new('Y', '.', -1),
+    KerningPair::new('Y', 'J', -1),
+    KerningPair::new('Y', 'j', -1),
+    KerningPair::new('f', ',', -1),
+    KerningPair::new('f', '.', -1),
+    KerningPair::new('f', 'J', -1),
+    KerningPair::new('f', 'a', -1),
+    KerningPair::new('f', 'c', -1),
+    KerningPair::new('f', 'd', -1),
+    KerningPair::new('f', 'e', -1),
+    KerningPair::new('f', 'g', -1),
+    KerningPair::new('f', 'j', -1),
+    KerningPair::new('f', 'o', -1),
+    KerningPair::new('f', 'q', -1),
+    KerningPair::new('f', 's', -1),
+    KerningPair::new('r', ',', -1),
+    KerningPair::new('r', 'T', -1),
+    KerningPair::new('r', 'j', -1),
+    KerningPair::new('t', '"', -1),
+    KerningPair::new('t', '\'', -1),
+    KerningPair::new('t', 'T', -1),
+    KerningPair::new('t', 'V', -1),
+    KerningPair::new('t', 'Y', -1),
+    KerningPair::new('t', 'y', -1),
+    KerningPair::new('v', ',', -1),
+    KerningPair::new('v', 'T', -1),
+];
@@ -0,0 +1,398 @@
+use embedded_graphics::{
+    prelude::{PixelColor, Point, Size},
+    primitives::Rectangle,
+    text::{
+        renderer::{CharacterStyle, TextMetrics, TextRenderer},
+        Baseline,
+    },
+};
+use embedded_graphics_core::{prelude::DrawTarget, Pixel};
+
+mod fonts;
+
+pub use fonts::{FONT_3X5, FONT_4X7, FONT_5X9, FONT_PROPORTIONAL};
+
+/// A single character of a bitmap font.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Glyph {
+    /// The character this glyph draws.
+    pub character: char,
+
+    /// Pixels of each column from left to right, with the top row in the lowest bit.
+    pub columns: &'static [u16],
+}
+
+impl Glyph {
+    /// Create a new glyph.
+    pub const fn new(character: char, columns: &'static [u16]) -> Self {
+        Self { character, columns }
+    }
+
+    /// Width of the glyph in pixels.
+    pub fn width(&self) -> u32 {
+        self.columns.len() as u32
+    }
+}
+
+/// An adjustment to the space between two characters, so their shapes sit together evenly.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct KerningPair {
+    pub left: char,
+    pub right: char,
+
+    /// Pixels added to the space between the characters, negative values move them closer.
+    pub offset: i8,
+}
+
+impl KerningPair {
+    /// Create a new kerning pair.
+    pub const fn new(left: char, right: char, offset: i8) -> Self {
+        Self {
+            left,
+            right,
+            offset,
+        }
+    }
+}
+
+/// A compact bitmap font, covering the printable characters of Latin-1.
+///
+/// Glyphs can be of different widths, so the same type is used for both
+/// monospaced and proportional fonts.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct BitmapFont {
+    /// Height of every glyph, and of a line of text, in pixels.
+    pub height: u32,
+
+    /// Row of the alphabetic baseline, counted from the top.
+    pub baseline: u32,
+
+    /// Space between glyphs in pixels.
+    pub letter_spacing: u32,
+
+    /// Glyphs of the font, sorted by character.
+    pub glyphs: &'static [Glyph],
+
+    /// Kerning pairs of the font, sorted by left then right character.
+    pub kerning: &'static [KerningPair],
+
+    /// Character drawn in place of characters not in the font.
+    pub replacement: char,
+}
+
+impl BitmapFont {
+    /// Gets the glyph for the character, providing it is in the font.
+    pub fn get_glyph(&self, character: char) -> Option<&Glyph> {
+        self.glyphs
+            .binary_search_by_key(&character, |glyph| glyph.character)
+            .ok()
+            .map(|index| &self.glyphs[index])
+    }
+
+    /// Gets the glyph for the character, or the replacement glyph if it is not in the font.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the replacement character is not in the font either.
+    pub fn glyph(&self, character: char) -> &Glyph {
+        self.get_glyph(character)
+            .or_else(|| self.get_glyph(self.replacement))
+            .expect("replacement character missing from font")
+    }
+
+    /// Gets the kerning offset between two characters, 0 if the pair has no kerning.
+    pub fn kerning(&self, left: char, right: char) -> i32 {
+        self.kerning
+            .binary_search_by(|pair| (pair.left, pair.right).cmp(&(left, right)))
+            .map_or(0, |index| self.kerning[index].offset as i32)
+    }
+
+    /// Width of the text in pixels when drawn with this font.
+    pub fn measure(&self, text: &str) -> u32 {
+        // the spacing after the last glyph is not part of the text
+        let width = self.advance(text) - self.letter_spacing as i32;
+        width.max(0) as u32
+    }
+
+    /// Draw the text with its top left at the given position.
+    /// Returns the position after the text, where following text would start.
+    pub fn draw<C, D>(
+        &self,
+        text: &str,
+        position: Point,
+        color: C,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        C: PixelColor,
+        D: DrawTarget<Color = C>,
+    {
+        let mut pixels = [Pixel(Point::zero(), color); 16];
+        let mut result = Ok(());
+
+        let advance = self.layout(text, |x, glyph| {
+            if result.is_err() {
+                return;
+            }
+
+            for (column_x, column) in glyph.columns.iter().enumerate() {
+                let mut count = 0;
+                for y in 0..self.height.min(16) {
+                    if column & (1 << y) != 0 {
+                        let point = position + Point::new(x + column_x as i32, y as i32);
+                        pixels[count] = Pixel(point, color);
+                        count += 1;
+                    }
+                }
+
+                if count > 0 {
+                    result = target.draw_iter(pixels[..count].iter().copied());
+                }
+            }
+        });
+
+        result?;
+        Ok(position + Point::new(advance, 0))
+    }
+
+    /// Call `place` with the x offset of each glyph of the text, returning the total advance.
+    fn layout<F>(&self, text: &str, mut place: F) -> i32
+    where
+        F: FnMut(i32, &Glyph),
+    {
+        let mut x = 0;
+        let mut previous = None;
+
+        for character in text.chars() {
+            let glyph = self.glyph(character);
+
+            if let Some(previous) = previous {
+                x += self.kerning(previous, glyph.character);
+            }
+
+            place(x, glyph);
+
+            x += (glyph.width() + self.letter_spacing) as i32;
+            previous = Some(glyph.character);
+        }
+
+        x
+    }
+
+    fn advance(&self, text: &str) -> i32 {
+        self.layout(text, |_, _| {})
+    }
+}
+
+/// Style for drawing text with a [`BitmapFont`] through `embedded_graphics::text::Text`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct BitmapTextStyle<'a, C> {
+    /// The font to draw with.
+    pub font: &'a BitmapFont,
+
+    /// Color of the text, which is not drawn when `None`.
+    pub text_color: Option<C>,
+
+    /// Color behind the text, which is left as is when `None`.
+    pub background_color: Option<C>,
+}
+
+impl<'a, C: PixelColor> BitmapTextStyle<'a, C> {
+    /// Create a new style with the font and text color, without a background.
+    pub fn new(font: &'a BitmapFont, text_color: C) -> Self {
+        Self {
+            font,
+            text_color: Some(text_color),
+            background_color: None,
+        }
+    }
+
+    /// Vertical offset from the text position to the top of the glyphs.
+    fn baseline_offset(&self, baseline: Baseline) -> i32 {
+        let height = self.font.height as i32;
+
+        match baseline {
+            Baseline::Top => 0,
+            Baseline::Bottom => height - 1,
+            Baseline::Middle => (height - 1) / 2,
+            Baseline::Alphabetic => self.font.baseline as i32,
+        }
+    }
+}
+
+impl<C: PixelColor> TextRenderer for BitmapTextStyle<'_, C> {
+    type Color = C;
+
+    fn draw_string<D>(
+        &self,
+        text: &str,
+        position: Point,
+        baseline: Baseline,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let offset = Point::new(0, self.baseline_offset(baseline));
+        let top_left = position - offset;
+
+        if let Some(background_color) = self.background_color {
+            let size = Size::new(self.font.advance(text).max(0) as u32, self.font.height);
+            target.fill_solid(&Rectangle::new(top_left, size), background_color)?;
+        }
+
+        let next = match self.text_color {
+            Some(text_color) => self.font.draw(text, top_left, text_color, target)?,
+            None => top_left + Point::new(self.font.advance(text), 0),
+        };
+
+        Ok(next + offset)
+    }
+
+    fn draw_whitespace<D>(
+        &self,
+        width: u32,
+        position: Point,
+        baseline: Baseline,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let top_left = position - Point::new(0, self.baseline_offset(baseline));
+
+        if let Some(background_color) = self.background_color {
+            let size = Size::new(width, self.font.height);
+            target.fill_solid(&Rectangle::new(top_left, size), background_color)?;
+        }
+
+        Ok(position + Point::new(width as i32, 0))
+    }
+
+    fn measure_string(&self, text: &str, position: Point, baseline: Baseline) -> TextMetrics {
+        let top_left = position - Point::new(0, self.baseline_offset(baseline));
+        let size = Size::new(self.font.measure(text), self.font.height);
+
+        TextMetrics {
+            bounding_box: Rectangle::new(top_left, size),
+            next_position: position + Point::new(self.font.advance(text), 0),
+        }
+    }
+
+    fn line_height(&self) -> u32 {
+        self.font.height
+    }
+}
+
+impl<C: PixelColor> CharacterStyle for BitmapTextStyle<'_, C> {
+    type Color = C;
+
+    fn set_text_color(&mut self, text_color: Option<Self::Color>) {
+        self.text_color = text_color;
+    }
+
+    fn set_background_color(&mut self, background_color: Option<Self::Color>) {
+        self.background_color = background_color;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{pixelcolor::Rgb888, prelude::RgbColor, text::Text, Drawable};
+
+    use super::*;
+    use crate::UnicornGraphics;
+
+    const FONT: BitmapFont = FONT_PROPORTIONAL;
+
+    /// The monospaced fonts, with the advance of every character and the height of a line.
+    const MONOSPACED: [(BitmapFont, u32, u32); 3] =
+        [(FONT_3X5, 4, 6), (FONT_4X7, 5, 7), (FONT_5X9, 6, 9)];
+
+    #[test]
+    fn glyphs_are_sorted_and_cover_latin_1() {
+        let fonts = MONOSPACED.map(|(font, _, _)| font);
+
+        for font in fonts.iter().chain([&FONT]) {
+            assert!(font
+                .glyphs
+                .windows(2)
+                .all(|pair| pair[0].character < pair[1].character));
+            assert!(font
+                .kerning
+                .windows(2)
+                .all(|pair| { (pair[0].left, pair[0].right) < (pair[1].left, pair[1].right) }));
+
+            for character in (0x20_u8..=0x7e).chain(0xa0..=0xff).map(char::from) {
+                assert!(font.get_glyph(character).is_some(), "{character:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn measure_monospaced() {
+        for (font, advance, height) in MONOSPACED {
+            assert_eq!(font.height, height);
+            assert!(font.kerning.is_empty());
+
+            assert_eq!(font.measure(""), 0);
+            assert_eq!(font.measure("i"), advance);
+            assert_eq!(font.measure("Wi"), 2 * advance);
+            assert_eq!(font.measure("Hello, ÿ!"), 9 * advance);
+        }
+    }
+
+    #[test]
+    fn measure_with_kerning() {
+        let width = |character| FONT.glyph(character).width();
+
+        assert_eq!(FONT.measure(""), 0);
+        assert_eq!(FONT.measure("T"), 3);
+        assert_eq!(FONT.measure("TA"), 3 + 1 + 4);
+
+        assert_eq!(FONT.kerning('T', 'a'), -1);
+        assert_eq!(FONT.kerning('a', 'T'), 0);
+        assert_eq!(FONT.measure("Ta"), 3 + width('a'));
+    }
+
+    #[test]
+    fn missing_characters_use_the_replacement() {
+        assert_eq!(FONT.get_glyph('\u{4e2d}'), None);
+        assert_eq!(FONT.glyph('\u{4e2d}').character, '?');
+        assert_eq!(FONT.measure("\u{4e2d}"), FONT.measure("?"));
+    }
+
+    #[test]
+    fn draw_glyph_columns() {
+        let mut graphics = UnicornGraphics::<8, 8>::new();
+        let next = FONT
+            .draw("T", Point::new(1, 1), Rgb888::WHITE, &mut graphics)
+            .unwrap();
+
+        assert_eq!(next, Point::new(5, 1));
+        for y in 0..8 {
+            for x in 0..8 {
+                let expected = (y == 1 && (1..=3).contains(&x)) || (x == 2 && (1..=6).contains(&y));
+                assert_eq!(
+                    graphics.is_colored(Point::new(x, y)),
+                    expected,
+                    "pixel {x}, {y}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn draw_text_on_the_alphabetic_baseline() {
+        let mut graphics = UnicornGraphics::<8, 8>::new();
+        let style = BitmapTextStyle::new(&FONT, Rgb888::WHITE);
+        let next = Text::new("T", Point::new(0, 5), style)
+            .draw(&mut graphics)
+            .unwrap();
+
+        assert_eq!(next, Point::new(4, 5));
+        assert!(graphics.is_colored(Point::new(0, 0)));
+        assert!(graphics.is_colored(Point::new(1, 5)));
+        assert!(!graphics.is_colored(Point::new(1, 6)));
+    }
+}
@@ -0,0 +1,376 @@
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics_core::{
+    pixelcolor::Rgb888,
+    prelude::{DrawTarget, Point, PointsIter, RgbColor},
+};
+
+use crate::blend::lerp;
+
+/// A color at a position along a gradient, from 0 at the start to 255 at the end.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ColorStop {
+    pub position: u8,
+    pub color: Rgb888,
+}
+
+impl ColorStop {
+    /// Create a new color stop.
+    pub const fn new(position: u8, color: Rgb888) -> Self {
+        Self { position, color }
+    }
+}
+
+/// Get the color at the given position along the stops, which must be sorted by position.
+///
+/// Colors are interpolated between the stops either side of the position,
+/// and held at the first and last stops beyond them. Black is returned when there are no stops.
+pub fn sample(stops: &[ColorStop], position: u8) -> Rgb888 {
+    let (first, last) = match (stops.first(), stops.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Rgb888::BLACK,
+    };
+
+    if position <= first.position {
+        return first.color;
+    }
+
+    for pair in stops.windows(2) {
+        let (from, to) = (pair[0], pair[1]);
+        if position <= to.position {
+            let span = (to.position - from.position) as u32;
+            let amount = (position - from.position) as u32 * 255 / span;
+            return lerp(from.color, to.color, amount as u8);
+        }
+    }
+
+    last.color
+}
+
+/// A gradient that gives a color for every point.
+pub trait Gradient {
+    /// Get the color of the gradient at the given point.
+    fn color_at(&self, point: Point) -> Rgb888;
+
+    /// Draw the gradient over the given area of the target.
+    fn draw<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb888>,
+    {
+        target.fill_contiguous(area, area.points().map(|point| self.color_at(point)))
+    }
+}
+
+/// A gradient along the line from the start point to the end point.
+/// Points before the start or beyond the end take the color of the first or last stop.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct LinearGradient<'a> {
+    pub start: Point,
+    pub end: Point,
+    pub stops: &'a [ColorStop],
+}
+
+impl<'a> LinearGradient<'a> {
+    /// Create a new linear gradient.
+    pub const fn new(start: Point, end: Point, stops: &'a [ColorStop]) -> Self {
+        Self { start, end, stops }
+    }
+}
+
+impl Gradient for LinearGradient<'_> {
+    fn color_at(&self, point: Point) -> Rgb888 {
+        // in 64 bits so long gradients and points far from the line do not overflow,
+        // falling back to 128 bits for points billions of pixels apart
+        let direction = offset(self.start, self.end);
+        let projected = offset(self.start, point);
+
+        let dot =
+            |a: (i64, i64), b: (i64, i64)| a.0.checked_mul(b.0)?.checked_add(a.1.checked_mul(b.1)?);
+        let wide_dot =
+            |a: (i64, i64), b: (i64, i64)| a.0 as i128 * b.0 as i128 + a.1 as i128 * b.1 as i128;
+
+        // project the point onto the line, as a fraction of its length
+        let along = dot(projected, direction).and_then(|along| along.checked_mul(255));
+        let position = match (dot(direction, direction), along) {
+            (Some(0), _) => 0,
+            (Some(length_squared), Some(along)) => along / length_squared,
+            _ => (wide_dot(projected, direction) * 255 / wide_dot(direction, direction)) as i64,
+        };
+
+        sample(self.stops, position.clamp(0, 255) as u8)
+    }
+}
+
+/// A gradient outwards from the center point to the radius.
+/// Points beyond the radius take the color of the last stop.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct RadialGradient<'a> {
+    pub center: Point,
+    pub radius: u32,
+    pub stops: &'a [ColorStop],
+}
+
+impl<'a> RadialGradient<'a> {
+    /// Create a new radial gradient.
+    pub const fn new(center: Point, radius: u32, stops: &'a [ColorStop]) -> Self {
+        Self {
+            center,
+            radius,
+            stops,
+        }
+    }
+}
+
+impl Gradient for RadialGradient<'_> {
+    fn color_at(&self, point: Point) -> Rgb888 {
+        if self.radius == 0 {
+            return sample(self.stops, u8::MAX);
+        }
+
+        // in 64 bits so large radii and coordinates far from the center do not overflow
+        let (dx, dy) = offset(self.center, point);
+        let (dx, dy) = (dx.unsigned_abs(), dy.unsigned_abs());
+        let distance_squared = (dx * dx).saturating_add(dy * dy);
+        let radius_squared = u64::from(self.radius) * u64::from(self.radius);
+
+        if distance_squared >= radius_squared {
+            return sample(self.stops, u8::MAX);
+        }
+
+        // distance as a fraction of the radius, scaled up before the square root to keep precision
+        let scaled = match distance_squared.checked_mul(255 * 255) {
+            Some(scaled) => scaled / radius_squared,
+            // only for radii above 2^24, where dividing the radius first loses no precision
+            None => distance_squared / (radius_squared / (255 * 255)),
+        };
+        let position = isqrt(scaled.min(255 * 255) as u32);
+
+        sample(self.stops, position.min(255) as u8)
+    }
+}
+
+/// A gradient around the center point, like the hands of a clock.
+///
+/// A position of 0 is to the right of the center, and positions increase clockwise
+/// with 64 below, 128 to the left and 192 above. The whole gradient is rotated clockwise by the angle.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ConicGradient<'a> {
+    pub center: Point,
+    pub angle: u8,
+    pub stops: &'a [ColorStop],
+}
+
+impl<'a> ConicGradient<'a> {
+    /// Create a new conic gradient.
+    pub const fn new(center: Point, angle: u8, stops: &'a [ColorStop]) -> Self {
+        Self {
+            center,
+            angle,
+            stops,
+        }
+    }
+}
+
+impl Gradient for ConicGradient<'_> {
+    fn color_at(&self, point: Point) -> Rgb888 {
+        let (x, y) = offset(self.center, point);
+        let position = angle(x, y).wrapping_sub(self.angle);
+
+        sample(self.stops, position)
+    }
+}
+
+/// Get the offset from one point to another in 64 bits, which cannot overflow.
+fn offset(from: Point, to: Point) -> (i64, i64) {
+    (
+        i64::from(to.x) - i64::from(from.x),
+        i64::from(to.y) - i64::from(from.y),
+    )
+}
+
+/// Integer square root, rounded down.
+fn isqrt(value: u32) -> u32 {
+    let mut root = 0;
+    let mut bit = 1 << 30;
+
+    while bit > value {
+        bit >>= 2;
+    }
+
+    let mut remainder = value;
+    while bit != 0 {
+        if remainder >= root + bit {
+            remainder -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+
+    root
+}
+
+/// Angle of the vector clockwise from the positive x axis, where 256 is a full turn.
+fn angle(x: i64, y: i64) -> u8 {
+    if x == 0 && y == 0 {
+        return 0;
+    }
+
+    let (ax, ay) = (x.abs(), y.abs());
+
+    // atan of the smaller over the larger side, from 0 to 32 for the first eighth of a turn,
+    // approximated as 32z + 11.1z(1 - z) with z in 1/256ths
+    let (small, large) = (ax.min(ay), ax.max(ay));
+    let z = small * 256 / large;
+    let octant = (32 * z + 111 * z * (256 - z) / 2560) / 256;
+
+    let quadrant = if ax >= ay { octant } else { 64 - octant };
+
+    let turn = match (x >= 0, y >= 0) {
+        (true, true) => quadrant,
+        (false, true) => 128 - quadrant,
+        (false, false) => 128 + quadrant,
+        (true, false) => 256 - quadrant,
+    };
+
+    (turn & 0xff) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Stops with the red channel following the position along the gradient.
+    const STOPS: [ColorStop; 2] = [
+        ColorStop::new(0, Rgb888::new(0, 0, 0)),
+        ColorStop::new(255, Rgb888::new(255, 0, 0)),
+    ];
+
+    fn position_at(gradient: &RadialGradient, x: i32, y: i32) -> u8 {
+        gradient.color_at(Point::new(x, y)).r()
+    }
+
+    #[test]
+    fn radial_position_follows_the_distance() {
+        let gradient = RadialGradient::new(Point::new(2, 2), 10, &STOPS);
+
+        assert_eq!(position_at(&gradient, 2, 2), 0);
+        assert_eq!(position_at(&gradient, 7, 2), 127);
+        assert_eq!(position_at(&gradient, 5, 6), 127);
+        assert_eq!(position_at(&gradient, 12, 2), 255);
+        assert_eq!(position_at(&gradient, -20, 2), 255);
+    }
+
+    #[test]
+    fn radial_zero_radius_is_the_last_stop() {
+        let gradient = RadialGradient::new(Point::zero(), 0, &STOPS);
+
+        assert_eq!(position_at(&gradient, 0, 0), 255);
+    }
+
+    #[test]
+    fn radial_large_radius_does_not_overflow() {
+        let gradient = RadialGradient::new(Point::zero(), 100_000, &STOPS);
+        assert_eq!(position_at(&gradient, 50_000, 0), 127);
+        assert_eq!(position_at(&gradient, 0, -100_000), 255);
+
+        let gradient = RadialGradient::new(Point::zero(), u32::MAX, &STOPS);
+        assert_eq!(position_at(&gradient, 0, 0), 0);
+        assert_eq!(position_at(&gradient, i32::MAX, 0), 127);
+        assert_eq!(position_at(&gradient, i32::MIN, i32::MIN), 180);
+    }
+
+    #[test]
+    fn radial_far_coordinates_do_not_overflow() {
+        let gradient = RadialGradient::new(Point::new(i32::MIN, i32::MIN), 10, &STOPS);
+
+        assert_eq!(position_at(&gradient, i32::MAX, i32::MAX), 255);
+        assert_eq!(position_at(&gradient, i32::MIN, i32::MIN), 0);
+    }
+
+    fn linear_at(gradient: &LinearGradient, x: i32, y: i32) -> u8 {
+        gradient.color_at(Point::new(x, y)).r()
+    }
+
+    #[test]
+    fn sample_between_and_beyond_the_stops() {
+        let stops = [
+            ColorStop::new(64, Rgb888::new(100, 0, 0)),
+            ColorStop::new(128, Rgb888::new(200, 50, 0)),
+        ];
+
+        assert_eq!(sample(&[], 100), Rgb888::BLACK);
+        assert_eq!(sample(&stops[..1], 200), Rgb888::new(100, 0, 0));
+        assert_eq!(sample(&stops, 0), Rgb888::new(100, 0, 0));
+        assert_eq!(sample(&stops, 96), Rgb888::new(150, 25, 0));
+        assert_eq!(sample(&stops, 128), Rgb888::new(200, 50, 0));
+        assert_eq!(sample(&stops, 255), Rgb888::new(200, 50, 0));
+    }
+
+    #[test]
+    fn linear_position_follows_the_projection() {
+        let gradient = LinearGradient::new(Point::new(0, 0), Point::new(10, 0), &STOPS);
+
+        assert_eq!(linear_at(&gradient, 0, 0), 0);
+        assert_eq!(linear_at(&gradient, 5, 0), 127);
+        assert_eq!(linear_at(&gradient, 5, -7), 127);
+        assert_eq!(linear_at(&gradient, 10, 0), 255);
+        assert_eq!(linear_at(&gradient, -3, 0), 0);
+        assert_eq!(linear_at(&gradient, 20, 3), 255);
+
+        let diagonal = LinearGradient::new(Point::new(0, 0), Point::new(4, 4), &STOPS);
+        assert_eq!(linear_at(&diagonal, 4, 0), 127);
+        assert_eq!(linear_at(&diagonal, 4, 4), 255);
+    }
+
+    #[test]
+    fn linear_zero_length_is_the_first_stop() {
+        let gradient = LinearGradient::new(Point::new(3, 3), Point::new(3, 3), &STOPS);
+
+        assert_eq!(linear_at(&gradient, 10, 10), 0);
+    }
+
+    #[test]
+    fn linear_long_and_far_does_not_overflow() {
+        let gradient = LinearGradient::new(Point::zero(), Point::new(3000, 0), &STOPS);
+        assert_eq!(linear_at(&gradient, 1500, 0), 127);
+
+        let gradient = LinearGradient::new(Point::zero(), Point::new(53, 0), &STOPS);
+        assert_eq!(linear_at(&gradient, 160_000, 0), 255);
+        assert_eq!(linear_at(&gradient, -160_000, 5), 0);
+        assert_eq!(linear_at(&gradient, 26, 160_000), 125);
+
+        let gradient = LinearGradient::new(
+            Point::new(i32::MIN, i32::MIN),
+            Point::new(i32::MAX, i32::MAX),
+            &STOPS,
+        );
+        assert_eq!(linear_at(&gradient, 0, 0), 127);
+        assert_eq!(linear_at(&gradient, i32::MAX, i32::MAX), 255);
+    }
+
+    #[test]
+    fn conic_position_turns_clockwise() {
+        let gradient = ConicGradient::new(Point::zero(), 0, &STOPS);
+        let at = |x, y| gradient.color_at(Point::new(x, y)).r();
+
+        assert_eq!(at(0, 0), 0);
+        assert_eq!(at(5, 0), 0);
+        assert_eq!(at(5, 5), 32);
+        assert_eq!(at(0, 5), 64);
+        assert_eq!(at(-5, 0), 128);
+        assert_eq!(at(0, -5), 192);
+
+        let rotated = ConicGradient::new(Point::zero(), 64, &STOPS);
+        assert_eq!(rotated.color_at(Point::new(0, 5)).r(), 0);
+        assert_eq!(rotated.color_at(Point::new(5, 0)).r(), 192);
+    }
+
+    #[test]
+    fn conic_far_coordinates_do_not_overflow() {
+        let gradient = ConicGradient::new(Point::new(i32::MIN, 0), 0, &STOPS);
+
+        assert_eq!(gradient.color_at(Point::new(i32::MAX, 0)).r(), 0);
+        assert_eq!(gradient.color_at(Point::new(i32::MIN, i32::MAX)).r(), 64);
+    }
+}
@@ -1,13 +1,38 @@
 #![no_std]
 
-use core::usize;
-
 use embedded_graphics_core::{
     pixelcolor::Rgb888,
     prelude::{Dimensions, DrawTarget, OriginDimensions, Point, RgbColor, Size},
+    primitives::Rectangle,
     Pixel,
 };
 
+pub mod animation;
+pub mod blend;
+pub mod clip;
+pub mod color;
+pub mod compositor;
+pub mod diff;
+pub mod font;
+pub mod gradient;
+pub mod marquee;
+pub mod palette;
+pub mod sprite;
+pub mod transform;
+
+pub use animation::{Easing, Keyframe, PlaybackMode, Timeline, Tween};
+pub use blend::{BlendMode, BlendedGraphics, Rgba};
+pub use clip::{Clip, ClipError, ClipFrame, ClipPlayer};
+pub use color::{color_wheel, Hsl, Hsv};
+pub use compositor::{Compositor, Layer};
+pub use diff::{diff, diff_area, Diff};
+pub use font::{BitmapFont, BitmapTextStyle};
+pub use gradient::{ColorStop, ConicGradient, Gradient, LinearGradient, RadialGradient};
+pub use marquee::{Marquee, MarqueeMode};
+pub use palette::{indexed_buffer_len, IndexedGraphics, Palette, PaletteIndex};
+pub use sprite::{key_frame, Rotation, Sprite, SpriteFrame};
+pub use transform::{ScaleFilter, ScrollEdge};
+
 pub type UnicornGraphicsPixels<const W: usize, const H: usize> = [[Rgb888; W]; H];
 
 #[derive(Copy, Clone)]
@@ -15,6 +40,9 @@ pub struct UnicornGraphics<const W: usize, const H: usize> {
     /// The current pixels held in this buffer.
     /// Accessed via height, then width e.g. `pixels[y][x]`.
     pixels: UnicornGraphicsPixels<W, H>,
+
+    /// The top left and bottom right corners of the pixels changed since the dirty area was last cleared.
+    dirty: Option<(Point, Point)>,
 }
 
 impl<const W: usize, const H: usize> UnicornGraphics<W, H> {
@@ -23,6 +51,7 @@ impl<const W: usize, const H: usize> UnicornGraphics<W, H> {
     pub fn new() -> Self {
         Self {
             pixels: [[Rgb888::BLACK; W]; H],
+            dirty: None,
         }
     }
 
@@ -33,7 +62,11 @@ impl<const W: usize, const H: usize> UnicornGraphics<W, H> {
 
     /// Overwrite the pixel buffer to the new pixel buffer.
     pub fn set_pixels(&mut self, pixels: UnicornGraphicsPixels<W, H>) {
-        self.pixels = pixels;
+        for (y, row) in pixels.iter().enumerate() {
+            for (x, color) in row.iter().enumerate() {
+                self.write_pixel(x, y, *color);
+            }
+        }
     }
 
     /// Set a pixel at the given point the Rgb888 value.
@@ -45,7 +78,55 @@ impl<const W: usize, const H: usize> UnicornGraphics<W, H> {
             return;
         }
 
+        self.write_pixel(x, y, color);
+    }
+
+    /// Write the color to the pixel, growing the dirty area if it changes.
+    fn write_pixel(&mut self, x: usize, y: usize, color: Rgb888) {
+        if self.pixels[y][x] == color {
+            return;
+        }
         self.pixels[y][x] = color;
+
+        let point = Point::new(x as i32, y as i32);
+        self.dirty = Some(match self.dirty {
+            Some((top_left, bottom_right)) => (
+                top_left.component_min(point),
+                bottom_right.component_max(point),
+            ),
+            None => (point, point),
+        });
+    }
+
+    /// Get the smallest area holding every pixel changed since the dirty area was last cleared,
+    /// or `None` if nothing has changed.
+    pub fn dirty_area(&self) -> Option<Rectangle> {
+        self.dirty
+            .map(|(top_left, bottom_right)| Rectangle::with_corners(top_left, bottom_right))
+    }
+
+    /// Check if any pixel has changed since the dirty area was last cleared.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.is_some()
+    }
+
+    /// Clear the dirty area, such as once the buffer has been sent to the display.
+    pub fn clear_dirty(&mut self) {
+        self.dirty = None;
+    }
+
+    /// Get the dirty area and clear it.
+    pub fn take_dirty_area(&mut self) -> Option<Rectangle> {
+        let area = self.dirty_area();
+        self.clear_dirty();
+        area
+    }
+
+    /// Mark every pixel as dirty, such as when the display needs to be sent the whole buffer again.
+    pub fn mark_all_dirty(&mut self) {
+        if W > 0 && H > 0 {
+            self.dirty = Some((Point::zero(), Point::new(W as i32 - 1, H as i32 - 1)));
+        }
     }
 
     /// Set a pixel at the given point to the value of r, g, b.
@@ -54,6 +135,46 @@ impl<const W: usize, const H: usize> UnicornGraphics<W, H> {
         self.set_pixel(coord, color);
     }
 
+    /// Blend a color onto the pixel at the given point with the alpha value and blend mode.
+    /// An alpha of 255 fully applies the blend mode, an alpha of 0 leaves the pixel as is.
+    pub fn blend_pixel(&mut self, coord: Point, color: Rgb888, alpha: u8, mode: BlendMode) {
+        if let Some(existing) = self.get_item(coord) {
+            self.set_pixel(coord, mode.blend(existing, color, alpha));
+        }
+    }
+
+    /// Draw a color over the pixel at the given point with the alpha value.
+    pub fn set_pixel_alpha(&mut self, coord: Point, color: Rgb888, alpha: u8) {
+        self.blend_pixel(coord, color, alpha, BlendMode::Normal);
+    }
+
+    /// Draw the Rgba color over the pixel at the given point.
+    pub fn set_pixel_rgba(&mut self, coord: Point, color: Rgba) {
+        self.blend_pixel_rgba(coord, color, BlendMode::Normal);
+    }
+
+    /// Blend the Rgba color onto the pixel at the given point with the blend mode.
+    pub fn blend_pixel_rgba(&mut self, coord: Point, color: Rgba, mode: BlendMode) {
+        self.blend_pixel(coord, color.rgb(), color.a, mode);
+    }
+
+    /// Get a draw target which blends everything drawn with the alpha value and blend mode.
+    /// Allows embedded graphics primitives and text to be drawn with transparency.
+    pub fn blended(&mut self, alpha: u8, mode: BlendMode) -> BlendedGraphics<'_, W, H> {
+        BlendedGraphics::new(self, alpha, mode)
+    }
+
+    /// Fade all pixels towards black by the given amount, where 255 clears the buffer.
+    /// Calling this each frame before drawing leaves trails behind moving pixels.
+    pub fn fade(&mut self, amount: u8) {
+        for y in 0..H {
+            for x in 0..W {
+                let color = blend::lerp(self.pixels[y][x], Rgb888::BLACK, amount);
+                self.write_pixel(x, y, color);
+            }
+        }
+    }
+
     /// Clear all pixels in the buffer via [`self::clear_pixel(point)`].
     pub fn clear_all(&mut self) {
         for y in 0..H {
@@ -145,6 +266,12 @@ impl<const W: usize, const H: usize> UnicornGraphics<W, H> {
     }
 }
 
+impl<const W: usize, const H: usize> Default for UnicornGraphics<W, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<const W: usize, const H: usize> DrawTarget for UnicornGraphics<W, H> {
     type Color = Rgb888;
     type Error = core::convert::Infallible;
@@ -213,6 +340,48 @@ impl<const W: usize, const H: usize> UnicornGraphicsPenned<W, H> {
         self.inner_graphics.set_pixel(coord, self.pen);
     }
 
+    /// Blend the pen onto the pixel at the given point with the alpha value and blend mode.
+    pub fn blend_pixel(&mut self, coord: Point, alpha: u8, mode: BlendMode) {
+        self.inner_graphics
+            .blend_pixel(coord, self.pen, alpha, mode);
+    }
+
+    /// Draw the pen over the pixel at the given point with the alpha value.
+    pub fn set_pixel_alpha(&mut self, coord: Point, alpha: u8) {
+        self.inner_graphics.set_pixel_alpha(coord, self.pen, alpha);
+    }
+
+    /// Fade all pixels towards black by the given amount, where 255 clears the buffer.
+    pub fn fade(&mut self, amount: u8) {
+        self.inner_graphics.fade(amount);
+    }
+
+    /// Get the smallest area holding every pixel changed since the dirty area was last cleared,
+    /// or `None` if nothing has changed.
+    pub fn dirty_area(&self) -> Option<Rectangle> {
+        self.inner_graphics.dirty_area()
+    }
+
+    /// Check if any pixel has changed since the dirty area was last cleared.
+    pub fn is_dirty(&self) -> bool {
+        self.inner_graphics.is_dirty()
+    }
+
+    /// Clear the dirty area, such as once the buffer has been sent to the display.
+    pub fn clear_dirty(&mut self) {
+        self.inner_graphics.clear_dirty();
+    }
+
+    /// Get the dirty area and clear it.
+    pub fn take_dirty_area(&mut self) -> Option<Rectangle> {
+        self.inner_graphics.take_dirty_area()
+    }
+
+    /// Mark every pixel as dirty, such as when the display needs to be sent the whole buffer again.
+    pub fn mark_all_dirty(&mut self) {
+        self.inner_graphics.mark_all_dirty();
+    }
+
     /// Clear all pixels in the buffer via [`self::clear_pixel(point)`].
     pub fn clear_all(&mut self) {
         for y in 0..H {
@@ -295,6 +464,12 @@ impl<const W: usize, const H: usize> UnicornGraphicsPenned<W, H> {
     }
 }
 
+impl<const W: usize, const H: usize> Default for UnicornGraphicsPenned<W, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<const W: usize, const H: usize> DrawTarget for UnicornGraphicsPenned<W, H> {
     type Color = Rgb888;
     type Error = core::convert::Infallible;
@@ -321,3 +496,102 @@ impl<const W: usize, const H: usize> From<UnicornGraphics<W, H>> for UnicornGrap
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(x: i32, y: i32, width: u32, height: u32) -> Option<Rectangle> {
+        Some(Rectangle::new(Point::new(x, y), Size::new(width, height)))
+    }
+
+    #[test]
+    fn new_graphics_are_clean() {
+        let graphics = UnicornGraphics::<8, 4>::new();
+
+        assert!(!graphics.is_dirty());
+        assert_eq!(graphics.dirty_area(), None);
+    }
+
+    #[test]
+    fn dirty_area_grows_to_hold_changed_pixels() {
+        let mut graphics = UnicornGraphics::<8, 4>::new();
+
+        graphics.set_pixel(Point::new(3, 2), Rgb888::RED);
+        assert_eq!(graphics.dirty_area(), area(3, 2, 1, 1));
+
+        graphics.set_pixel(Point::new(5, 1), Rgb888::RED);
+        assert_eq!(graphics.dirty_area(), area(3, 1, 3, 2));
+
+        graphics.set_pixel(Point::new(1, 3), Rgb888::GREEN);
+        assert_eq!(graphics.dirty_area(), area(1, 1, 5, 3));
+    }
+
+    #[test]
+    fn unchanged_and_outside_pixels_are_not_dirty() {
+        let mut graphics = UnicornGraphics::<8, 4>::new();
+
+        graphics.set_pixel(Point::new(2, 2), Rgb888::BLACK);
+        graphics.set_pixel(Point::new(8, 0), Rgb888::RED);
+        graphics.set_pixel(Point::new(-1, 0), Rgb888::RED);
+        graphics.clear_all();
+        assert_eq!(graphics.dirty_area(), None);
+
+        graphics.fill(Rgb888::BLUE);
+        graphics.clear_dirty();
+        graphics.fill(Rgb888::BLUE);
+        assert!(!graphics.is_dirty());
+    }
+
+    #[test]
+    fn take_dirty_area_clears_it() {
+        let mut graphics = UnicornGraphics::<8, 4>::new();
+        graphics.set_pixel(Point::new(7, 3), Rgb888::RED);
+
+        assert_eq!(graphics.take_dirty_area(), area(7, 3, 1, 1));
+        assert_eq!(graphics.take_dirty_area(), None);
+
+        graphics.set_pixel(Point::new(0, 0), Rgb888::RED);
+        assert_eq!(graphics.take_dirty_area(), area(0, 0, 1, 1));
+    }
+
+    #[test]
+    fn mark_all_dirty() {
+        let mut graphics = UnicornGraphics::<8, 4>::new();
+        graphics.set_pixel(Point::new(2, 2), Rgb888::RED);
+        graphics.mark_all_dirty();
+        assert_eq!(graphics.dirty_area(), area(0, 0, 8, 4));
+
+        let mut empty = UnicornGraphics::<0, 0>::new();
+        empty.mark_all_dirty();
+        assert_eq!(empty.dirty_area(), None);
+    }
+
+    #[test]
+    fn whole_buffer_changes_are_dirty() {
+        let mut graphics = UnicornGraphics::<8, 4>::new();
+        graphics.set_pixel(Point::new(0, 1), Rgb888::RED);
+        graphics.clear_dirty();
+
+        graphics.flip_horizontal();
+        assert_eq!(graphics.take_dirty_area(), area(0, 1, 8, 1));
+
+        graphics.fade(255);
+        assert_eq!(graphics.take_dirty_area(), area(7, 1, 1, 1));
+
+        let mut pixels = graphics.get_pixels();
+        pixels[3][4] = Rgb888::WHITE;
+        graphics.set_pixels(pixels);
+        assert_eq!(graphics.take_dirty_area(), area(4, 3, 1, 1));
+    }
+
+    #[test]
+    fn penned_graphics_track_the_dirty_area() {
+        let mut graphics = UnicornGraphicsPenned::<8, 4>::new();
+        graphics.set_pen(Rgb888::RED);
+        graphics.set_pixel(Point::new(6, 0));
+
+        assert_eq!(graphics.take_dirty_area(), area(6, 0, 1, 1));
+        assert!(!graphics.is_dirty());
+    }
+}
@@ -0,0 +1,381 @@
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    text::{renderer::TextRenderer, Baseline},
+};
+use embedded_graphics_core::{
+    pixelcolor::Rgb888,
+    prelude::{DrawTarget, Point},
+};
+
+/// How the marquee moves once the text has scrolled across the display.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum MarqueeMode {
+    /// Scroll in from the right edge and off the left edge, then start again from the right.
+    #[default]
+    Loop,
+
+    /// Scroll in from the right edge and off the left edge, then stop.
+    Once,
+
+    /// Scroll back and forth between the text touching the left and right edges.
+    Bounce,
+}
+
+/// Scrolling text for a display `W` pixels wide and `H` pixels tall.
+///
+/// The text is vertically centered and moved a whole pixel at a time,
+/// at a speed set in pixels per second. Call [`Self::update`] with the time elapsed
+/// since the last update, then [`Self::draw`] to draw the text at its new position.
+#[derive(Clone, Debug)]
+pub struct Marquee<'a, const W: usize, const H: usize, S = MonoTextStyle<'a, Rgb888>> {
+    text: &'a str,
+    style: S,
+
+    /// Speed of the text in pixels per second.
+    pub speed: u32,
+
+    /// How the text moves once it has scrolled across the display.
+    pub mode: MarqueeMode,
+
+    /// Time in milliseconds to pause for when the start of the text reaches the left edge.
+    /// In bounce mode, the text also pauses when the end reaches the right edge.
+    pub pause_ms: u32,
+
+    /// Width of the text in pixels.
+    text_width: i32,
+
+    /// Position of the left edge of the text.
+    x: i32,
+
+    /// Direction the text is moving, -1 for left and 1 for right.
+    direction: i32,
+
+    /// Time left of the current pause.
+    pause_remaining_ms: u32,
+
+    /// Movement carried over between updates, in thousandths of a pixel.
+    remainder: u32,
+
+    finished: bool,
+}
+
+impl<'a, const W: usize, const H: usize, S> Marquee<'a, W, H, S>
+where
+    S: TextRenderer<Color = Rgb888>,
+{
+    /// Create a new marquee for the text, scrolling at 15 pixels per second.
+    /// Any text style can be used, such as `MonoTextStyle` or [`crate::BitmapTextStyle`].
+    pub fn new(text: &'a str, style: S) -> Self {
+        let mut marquee = Self {
+            text,
+            style,
+            speed: 15,
+            mode: MarqueeMode::Loop,
+            pause_ms: 0,
+            text_width: 0,
+            x: 0,
+            direction: -1,
+            pause_remaining_ms: 0,
+            remainder: 0,
+            finished: false,
+        };
+        marquee.set_text(text);
+        marquee
+    }
+
+    /// Set the speed in pixels per second.
+    pub fn with_speed(mut self, speed: u32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Set the mode, restarting the marquee.
+    pub fn with_mode(mut self, mode: MarqueeMode) -> Self {
+        self.mode = mode;
+        self.reset();
+        self
+    }
+
+    /// Set the time in milliseconds to pause for when the text reaches the left edge.
+    pub fn with_pause(mut self, pause_ms: u32) -> Self {
+        self.pause_ms = pause_ms;
+        self.reset();
+        self
+    }
+
+    /// Get the text being scrolled.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Replace the text being scrolled, restarting the marquee.
+    pub fn set_text(&mut self, text: &'a str) {
+        self.text = text;
+        self.text_width = self
+            .style
+            .measure_string(text, Point::zero(), Baseline::Top)
+            .bounding_box
+            .size
+            .width as i32;
+        self.reset();
+    }
+
+    /// Replace the style of the text, keeping the current position.
+    pub fn set_style(&mut self, style: S) {
+        self.style = style;
+        self.text_width = self
+            .style
+            .measure_string(self.text, Point::zero(), Baseline::Top)
+            .bounding_box
+            .size
+            .width as i32;
+    }
+
+    /// Width of the text in pixels.
+    pub fn text_width(&self) -> u32 {
+        self.text_width as u32
+    }
+
+    /// Position of the top left of the text.
+    pub fn position(&self) -> Point {
+        let height = self.style.line_height() as i32;
+        Point::new(self.x, (H as i32 - height) / 2)
+    }
+
+    /// Check if the text has scrolled off the display in [`MarqueeMode::Once`].
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Move the text back to its starting position.
+    pub fn reset(&mut self) {
+        self.remainder = 0;
+        self.finished = false;
+        self.direction = -1;
+
+        match self.mode {
+            MarqueeMode::Loop | MarqueeMode::Once => {
+                self.x = W as i32;
+                self.pause_remaining_ms = 0;
+            }
+            MarqueeMode::Bounce => {
+                self.x = 0;
+                self.pause_remaining_ms = self.pause_ms;
+                if self.text_width < W as i32 {
+                    self.direction = 1;
+                }
+            }
+        }
+    }
+
+    /// Move the text by the time elapsed, in milliseconds, since the last update.
+    pub fn update(&mut self, elapsed_ms: u32) {
+        if self.finished {
+            return;
+        }
+
+        let paused = elapsed_ms.min(self.pause_remaining_ms);
+        self.pause_remaining_ms -= paused;
+
+        let moving_ms = elapsed_ms - paused;
+        if moving_ms == 0 {
+            return;
+        }
+
+        // saturate so a long stall moves the text as far as it can rather than wrapping
+        self.remainder = self
+            .remainder
+            .saturating_add(moving_ms.saturating_mul(self.speed));
+        let steps = self.remainder / 1000;
+        self.remainder %= 1000;
+
+        // the movement repeats every period, so after a long stall only the last of it is stepped,
+        // keeping a whole period to first bring back text a new style moved past the edges
+        let period = match self.mode {
+            MarqueeMode::Loop | MarqueeMode::Once => (W as i32 + self.text_width) as u32,
+            MarqueeMode::Bounce => 2 * (W as i32 - self.text_width).unsigned_abs(),
+        }
+        .max(1);
+        let steps = if steps > period {
+            period + steps % period
+        } else {
+            steps
+        };
+
+        for _ in 0..steps {
+            if !self.step() {
+                // stopped for a pause or the end, so do not carry movement past it
+                self.remainder = 0;
+                break;
+            }
+        }
+    }
+
+    /// Move the text by one pixel, returning false when it stops at a pause or the end.
+    fn step(&mut self) -> bool {
+        match self.mode {
+            MarqueeMode::Loop | MarqueeMode::Once => {
+                self.x -= 1;
+
+                if self.x <= -self.text_width {
+                    if self.mode == MarqueeMode::Once {
+                        self.finished = true;
+                        return false;
+                    }
+
+                    self.x = W as i32;
+                }
+            }
+            MarqueeMode::Bounce => {
+                let end = W as i32 - self.text_width;
+                if end == 0 {
+                    return false;
+                }
+
+                // the text moves between the edges, whichever way round they are
+                let (left, right) = (end.min(0), end.max(0));
+                self.x = (self.x + self.direction).clamp(left, right);
+
+                // turn at or past the edges, in case a new style moved them under the text
+                if self.x == left || self.x == right {
+                    self.direction = if self.x == left { 1 } else { -1 };
+                    self.pause_remaining_ms = self.pause_ms;
+                    return self.pause_ms == 0;
+                }
+
+                return true;
+            }
+        }
+
+        if self.x == 0 && self.pause_ms > 0 {
+            self.pause_remaining_ms = self.pause_ms;
+            return false;
+        }
+
+        true
+    }
+
+    /// Draw the text at its current position.
+    pub fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb888>,
+    {
+        if self.finished {
+            return Ok(());
+        }
+
+        self.style
+            .draw_string(self.text, self.position(), Baseline::Top, target)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::mono_font::{
+        ascii::{FONT_4X6, FONT_6X10},
+        MonoFont,
+    };
+    use embedded_graphics_core::prelude::RgbColor;
+
+    use super::*;
+
+    fn style(font: &'static MonoFont<'static>) -> MonoTextStyle<'static, Rgb888> {
+        MonoTextStyle::new(font, Rgb888::WHITE)
+    }
+
+    #[test]
+    fn loop_wraps_back_to_the_right_edge() {
+        let mut marquee = Marquee::<16, 8>::new("AB", style(&FONT_4X6)).with_speed(1000);
+        assert_eq!(marquee.position().x, 16);
+
+        marquee.update(23);
+        assert_eq!(marquee.position().x, -7);
+
+        marquee.update(1);
+        assert_eq!(marquee.position().x, 16);
+    }
+
+    #[test]
+    fn long_stall_does_not_overflow() {
+        let mut marquee = Marquee::<16, 8>::new("AB", style(&FONT_4X6)).with_speed(u32::MAX);
+
+        marquee.update(u32::MAX);
+        let x = marquee.position().x;
+        assert!((-8..=16).contains(&x));
+
+        let mut once = Marquee::<16, 8>::new("AB", style(&FONT_4X6))
+            .with_speed(1000)
+            .with_mode(MarqueeMode::Once);
+        once.update(u32::MAX);
+        assert!(once.is_finished());
+    }
+
+    #[test]
+    fn long_stall_steps_through_the_last_period() {
+        let mut marquee = Marquee::<16, 8>::new("AB", style(&FONT_4X6)).with_speed(1000);
+
+        // 4294967 steps, which is 23 past a whole number of 24 step loops
+        marquee.update(u32::MAX);
+        assert_eq!(marquee.position().x, -7);
+
+        let mut marquee = Marquee::<16, 8>::new("AB", style(&FONT_4X6))
+            .with_speed(1000)
+            .with_mode(MarqueeMode::Bounce);
+
+        // 7 past a whole number of 16 step bounces
+        marquee.update(u32::MAX);
+        assert_eq!(marquee.position().x, 7);
+    }
+
+    #[test]
+    fn bounce_turns_at_both_edges() {
+        let mut marquee = Marquee::<16, 8>::new("AB", style(&FONT_4X6))
+            .with_speed(1000)
+            .with_mode(MarqueeMode::Bounce);
+        assert_eq!(marquee.position().x, 0);
+
+        marquee.update(8);
+        assert_eq!(marquee.position().x, 8);
+
+        marquee.update(3);
+        assert_eq!(marquee.position().x, 5);
+
+        marquee.update(5);
+        assert_eq!(marquee.position().x, 0);
+
+        marquee.update(1);
+        assert_eq!(marquee.position().x, 1);
+    }
+
+    #[test]
+    fn bounce_turns_when_a_wider_style_moves_the_edge_under_the_text() {
+        let mut marquee = Marquee::<16, 8>::new("AB", style(&FONT_4X6))
+            .with_speed(1000)
+            .with_mode(MarqueeMode::Bounce);
+
+        marquee.update(6);
+        assert_eq!(marquee.position().x, 6);
+
+        // the text is now 12 pixels wide, so the right edge is at 4
+        marquee.set_style(style(&FONT_6X10));
+        marquee.update(1);
+        assert_eq!(marquee.position().x, 4);
+
+        marquee.update(1);
+        assert_eq!(marquee.position().x, 3);
+    }
+
+    #[test]
+    fn bounce_text_wider_than_the_display() {
+        let mut marquee = Marquee::<4, 8>::new("AB", style(&FONT_4X6))
+            .with_speed(1000)
+            .with_mode(MarqueeMode::Bounce);
+
+        marquee.update(4);
+        assert_eq!(marquee.position().x, -4);
+
+        marquee.update(2);
+        assert_eq!(marquee.position().x, -2);
+    }
+}
@@ -0,0 +1,455 @@
+use core::ops::Range;
+
+use embedded_graphics_core::{
+    pixelcolor::{raw::RawU8, PixelColor, Rgb888},
+    prelude::{DrawTarget, OriginDimensions, Point, RgbColor, Size},
+    Pixel,
+};
+
+use crate::UnicornGraphics;
+
+/// Get the number of bytes needed by an [`IndexedGraphics`] of the given size and bits per pixel.
+///
+/// Each row starts on a new byte, so a 53 pixel wide row at 4 bits per pixel takes 27 bytes.
+pub const fn indexed_buffer_len(width: usize, height: usize, bits_per_pixel: usize) -> usize {
+    (width * bits_per_pixel).div_ceil(8) * height
+}
+
+/// Index of a color in a [`Palette`], used as the color when drawing to an [`IndexedGraphics`].
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct PaletteIndex(pub u8);
+
+impl PixelColor for PaletteIndex {
+    type Raw = RawU8;
+}
+
+impl From<u8> for PaletteIndex {
+    fn from(value: u8) -> Self {
+        Self(value)
+    }
+}
+
+/// A palette of up to 256 colors, shared by any number of indexed buffers.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Palette<const N: usize> {
+    colors: [Rgb888; N],
+}
+
+impl<const N: usize> Palette<N> {
+    const VALID: () = assert!(N > 0 && N <= 256, "a palette holds from 1 to 256 colors");
+
+    /// Create a new palette of the given colors.
+    pub const fn new(colors: [Rgb888; N]) -> Self {
+        let () = Self::VALID;
+
+        Self { colors }
+    }
+
+    /// Number of colors in the palette.
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Check if the palette has no colors, which is never the case.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Get the color at the given index, or black if the index is not within the palette.
+    pub fn color(&self, index: PaletteIndex) -> Rgb888 {
+        self.colors
+            .get(index.0 as usize)
+            .copied()
+            .unwrap_or(Rgb888::BLACK)
+    }
+
+    /// Set the color at the given index, providing the index is within the palette.
+    pub fn set_color(&mut self, index: PaletteIndex, color: Rgb888) {
+        if let Some(entry) = self.colors.get_mut(index.0 as usize) {
+            *entry = color;
+        }
+    }
+
+    /// Get all colors of the palette.
+    pub fn colors(&self) -> &[Rgb888; N] {
+        &self.colors
+    }
+
+    /// Get the index of the palette color closest to the given color.
+    pub fn nearest(&self, color: Rgb888) -> PaletteIndex {
+        self.nearest_within(color, N)
+    }
+
+    /// Get the index of the color closest to the given color within the first `count` colors
+    /// of the palette, such as those an indexed buffer with fewer bits per pixel can hold.
+    pub fn nearest_within(&self, color: Rgb888, count: usize) -> PaletteIndex {
+        let distance = |other: &Rgb888| {
+            let r = color.r() as i32 - other.r() as i32;
+            let g = color.g() as i32 - other.g() as i32;
+            let b = color.b() as i32 - other.b() as i32;
+            r * r + g * g + b * b
+        };
+
+        let index = self.colors[..count.clamp(1, N)]
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, other)| distance(other))
+            .map_or(0, |(index, _)| index);
+
+        PaletteIndex(index as u8)
+    }
+
+    /// Rotate the colors within the range by the given amount, for palette cycling animation.
+    /// Positive amounts move each color to a higher index, wrapping around within the range.
+    /// The range is limited to the size of the palette.
+    pub fn cycle(&mut self, range: Range<usize>, amount: i32) {
+        let end = range.end.min(N);
+        if range.start >= end {
+            return;
+        }
+
+        let colors = &mut self.colors[range.start..end];
+        let amount = amount.rem_euclid(colors.len() as i32) as usize;
+        colors.rotate_right(amount);
+    }
+}
+
+/// A buffer of palette indexes, with `BPP` bits per pixel, for a display `W` pixels wide and `H` pixels tall.
+///
+/// This uses far less memory than [`UnicornGraphics`], so suits storing many frames of an animation.
+/// `BPP` must be 1, 2, 4 or 8, allowing up to 2, 4, 16 or 256 colors, and `BYTES` must be
+/// [`indexed_buffer_len`] of the size and bits per pixel,
+/// such as `IndexedGraphics<53, 11, 4, { indexed_buffer_len(53, 11, 4) }>`.
+///
+/// Draw to it with [`PaletteIndex`] colors, or with `Rgb888` colors through [`Self::with_palette`],
+/// then convert it to a [`UnicornGraphics`] with a [`Palette`] to show it.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct IndexedGraphics<const W: usize, const H: usize, const BPP: usize, const BYTES: usize> {
+    pixels: [u8; BYTES],
+}
+
+impl<const W: usize, const H: usize, const BPP: usize, const BYTES: usize>
+    IndexedGraphics<W, H, BPP, BYTES>
+{
+    const VALID: () = {
+        assert!(
+            BPP == 1 || BPP == 2 || BPP == 4 || BPP == 8,
+            "bits per pixel must be 1, 2, 4 or 8"
+        );
+        assert!(
+            BYTES == indexed_buffer_len(W, H, BPP),
+            "bytes must be indexed_buffer_len(W, H, BPP)"
+        );
+    };
+
+    /// Bytes used by each row of pixels.
+    const ROW_BYTES: usize = indexed_buffer_len(W, 1, BPP);
+
+    /// Number of palette colors a pixel can hold.
+    const COLORS: usize = 1 << BPP;
+
+    /// Mask of the bits of a single pixel.
+    const MASK: u8 = (((1_u16) << BPP) - 1) as u8;
+
+    /// Create a new buffer with every pixel set to index 0.
+    pub const fn new() -> Self {
+        let () = Self::VALID;
+
+        Self { pixels: [0; BYTES] }
+    }
+
+    /// Create a new buffer from the graphics, using the nearest palette color for each pixel.
+    /// Only the colors a pixel can hold are used, so the first 16 colors at 4 bits per pixel.
+    pub fn from_graphics<const N: usize>(
+        graphics: &UnicornGraphics<W, H>,
+        palette: &Palette<N>,
+    ) -> Self {
+        let mut indexed = Self::new();
+
+        for (y, row) in graphics.pixels.iter().enumerate() {
+            for (x, color) in row.iter().enumerate() {
+                let coord = Point::new(x as i32, y as i32);
+                indexed.set_index(coord, palette.nearest_within(*color, Self::COLORS));
+            }
+        }
+
+        indexed
+    }
+
+    /// Get the byte and bit offset of the pixel, providing the coord is within the buffer.
+    fn position(coord: Point) -> Option<(usize, u32)> {
+        let x = usize::try_from(coord.x).ok()?;
+        let y = usize::try_from(coord.y).ok()?;
+
+        if x >= W || y >= H {
+            return None;
+        }
+
+        let bit = x * BPP;
+        Some((y * Self::ROW_BYTES + bit / 8, (bit % 8) as u32))
+    }
+
+    /// Get the palette index of the pixel, providing the coord is within the buffer.
+    pub fn get_index(&self, coord: Point) -> Option<PaletteIndex> {
+        let (byte, shift) = Self::position(coord)?;
+        Some(PaletteIndex((self.pixels[byte] >> shift) & Self::MASK))
+    }
+
+    /// Set the palette index of the pixel, providing the coord is within the buffer.
+    /// Bits of the index above `BPP` are ignored.
+    pub fn set_index(&mut self, coord: Point, index: PaletteIndex) {
+        if let Some((byte, shift)) = Self::position(coord) {
+            let pixel = &mut self.pixels[byte];
+            *pixel = (*pixel & !(Self::MASK << shift)) | ((index.0 & Self::MASK) << shift);
+        }
+    }
+
+    /// Set every pixel to the palette index.
+    pub fn fill(&mut self, index: PaletteIndex) {
+        let index = index.0 & Self::MASK;
+
+        // repeat the index across a whole byte, then copy it to every byte
+        let mut byte = 0;
+        for shift in (0..8).step_by(BPP) {
+            byte |= index << shift;
+        }
+        self.pixels = [byte; BYTES];
+    }
+
+    /// Set every pixel to palette index 0.
+    pub fn clear_all(&mut self) {
+        self.pixels = [0; BYTES];
+    }
+
+    /// Get the packed pixels, row by row, with the first pixel of each byte in the lowest bits.
+    pub fn as_bytes(&self) -> &[u8; BYTES] {
+        &self.pixels
+    }
+
+    /// Convert the buffer into graphics for the display, looking up each pixel in the palette.
+    pub fn to_graphics<const N: usize>(&self, palette: &Palette<N>) -> UnicornGraphics<W, H> {
+        let mut graphics = UnicornGraphics::new();
+        self.draw_into(palette, &mut graphics);
+        graphics
+    }
+
+    /// Draw the buffer onto the graphics, looking up each pixel in the palette.
+    pub fn draw_into<const N: usize>(
+        &self,
+        palette: &Palette<N>,
+        graphics: &mut UnicornGraphics<W, H>,
+    ) {
+        for y in 0..H {
+            for x in 0..W {
+                let coord = Point::new(x as i32, y as i32);
+                if let Some(index) = self.get_index(coord) {
+                    graphics.set_pixel(coord, palette.color(index));
+                }
+            }
+        }
+    }
+
+    /// Get a draw target over the buffer that takes `Rgb888` colors,
+    /// drawing the nearest color in the palette.
+    pub fn with_palette<'a, const N: usize>(
+        &'a mut self,
+        palette: &'a Palette<N>,
+    ) -> PalettedGraphics<'a, W, H, BPP, BYTES, N> {
+        PalettedGraphics {
+            indexed: self,
+            palette,
+        }
+    }
+}
+
+impl<const W: usize, const H: usize, const BPP: usize, const BYTES: usize> Default
+    for IndexedGraphics<W, H, BPP, BYTES>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize, const H: usize, const BPP: usize, const BYTES: usize> DrawTarget
+    for IndexedGraphics<W, H, BPP, BYTES>
+{
+    type Color = PaletteIndex;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(coord, index) in pixels {
+            self.set_index(coord, index);
+        }
+        Ok(())
+    }
+}
+
+impl<const W: usize, const H: usize, const BPP: usize, const BYTES: usize> OriginDimensions
+    for IndexedGraphics<W, H, BPP, BYTES>
+{
+    fn size(&self) -> Size {
+        Size::new(W as u32, H as u32)
+    }
+}
+
+/// A draw target that maps `Rgb888` colors to the nearest color of a palette that a pixel can hold,
+/// so embedded graphics primitives and text can be drawn to an indexed buffer in color.
+/// Created via [`IndexedGraphics::with_palette`].
+pub struct PalettedGraphics<
+    'a,
+    const W: usize,
+    const H: usize,
+    const BPP: usize,
+    const BYTES: usize,
+    const N: usize,
+> {
+    indexed: &'a mut IndexedGraphics<W, H, BPP, BYTES>,
+    palette: &'a Palette<N>,
+}
+
+impl<const W: usize, const H: usize, const BPP: usize, const BYTES: usize, const N: usize>
+    DrawTarget for PalettedGraphics<'_, W, H, BPP, BYTES, N>
+{
+    type Color = Rgb888;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(coord, color) in pixels {
+            let index = self
+                .palette
+                .nearest_within(color, IndexedGraphics::<W, H, BPP, BYTES>::COLORS);
+            self.indexed.set_index(coord, index);
+        }
+        Ok(())
+    }
+}
+
+impl<const W: usize, const H: usize, const BPP: usize, const BYTES: usize, const N: usize>
+    OriginDimensions for PalettedGraphics<'_, W, H, BPP, BYTES, N>
+{
+    fn size(&self) -> Size {
+        self.indexed.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{
+        prelude::{Primitive, Size},
+        primitives::{PrimitiveStyle, Rectangle},
+        Drawable,
+    };
+
+    use super::*;
+
+    const COLORS: [Rgb888; 6] = [
+        Rgb888::BLACK,
+        Rgb888::WHITE,
+        Rgb888::RED,
+        Rgb888::GREEN,
+        Rgb888::BLUE,
+        Rgb888::YELLOW,
+    ];
+
+    type Indexed1 = IndexedGraphics<5, 3, 1, { indexed_buffer_len(5, 3, 1) }>;
+    type Indexed2 = IndexedGraphics<5, 3, 2, { indexed_buffer_len(5, 3, 2) }>;
+    type Indexed4 = IndexedGraphics<5, 3, 4, { indexed_buffer_len(5, 3, 4) }>;
+
+    #[test]
+    fn nearest() {
+        let palette = Palette::new(COLORS);
+
+        assert_eq!(palette.nearest(Rgb888::new(250, 10, 10)), PaletteIndex(2));
+        assert_eq!(palette.nearest(Rgb888::new(200, 200, 20)), PaletteIndex(5));
+        assert_eq!(palette.nearest(Rgb888::new(20, 20, 20)), PaletteIndex(0));
+    }
+
+    #[test]
+    fn nearest_within_limits_the_colors() {
+        let palette = Palette::new(COLORS);
+        let yellow = Rgb888::new(200, 200, 20);
+
+        assert_eq!(palette.nearest_within(yellow, 4), PaletteIndex(2));
+        assert_eq!(palette.nearest_within(yellow, 2), PaletteIndex(1));
+        assert_eq!(palette.nearest_within(Rgb888::BLUE, 0), PaletteIndex(0));
+        assert_eq!(palette.nearest_within(yellow, 300), PaletteIndex(5));
+    }
+
+    #[test]
+    fn indexed_graphics_only_use_colors_a_pixel_holds() {
+        let palette = Palette::new(COLORS);
+        let mut graphics = UnicornGraphics::<5, 3>::new();
+        graphics.fill(Rgb888::BLUE);
+        graphics.set_pixel(Point::new(1, 1), Rgb888::WHITE);
+
+        // blue is index 4, which 1 or 2 bits per pixel cannot hold
+        let indexed = Indexed1::from_graphics(&graphics, &palette);
+        assert_eq!(indexed.get_index(Point::new(0, 0)), Some(PaletteIndex(0)));
+        assert_eq!(indexed.get_index(Point::new(1, 1)), Some(PaletteIndex(1)));
+
+        let indexed = Indexed2::from_graphics(&graphics, &palette);
+        assert_eq!(indexed.get_index(Point::new(0, 0)), Some(PaletteIndex(0)));
+        assert_eq!(indexed.get_index(Point::new(1, 1)), Some(PaletteIndex(1)));
+
+        let indexed = Indexed4::from_graphics(&graphics, &palette);
+        assert_eq!(indexed.get_index(Point::new(0, 0)), Some(PaletteIndex(4)));
+        assert_eq!(
+            indexed.to_graphics(&palette).get_pixels(),
+            graphics.get_pixels()
+        );
+    }
+
+    #[test]
+    fn with_palette_only_uses_colors_a_pixel_holds() {
+        let palette = Palette::new(COLORS);
+        let mut indexed = Indexed2::new();
+
+        Rectangle::new(Point::zero(), Size::new(2, 2))
+            .into_styled(PrimitiveStyle::with_fill(Rgb888::YELLOW))
+            .draw(&mut indexed.with_palette(&palette))
+            .unwrap();
+
+        assert_eq!(indexed.get_index(Point::new(1, 1)), Some(PaletteIndex(1)));
+        assert_eq!(indexed.get_index(Point::new(2, 1)), Some(PaletteIndex(0)));
+    }
+
+    #[test]
+    fn packs_pixels_into_bytes() {
+        let mut indexed = Indexed2::new();
+        indexed.set_index(Point::new(0, 0), PaletteIndex(1));
+        indexed.set_index(Point::new(3, 0), PaletteIndex(3));
+        indexed.set_index(Point::new(4, 0), PaletteIndex(2));
+        indexed.set_index(Point::new(0, 2), PaletteIndex(7));
+        indexed.set_index(Point::new(5, 0), PaletteIndex(3));
+
+        assert_eq!(indexed.as_bytes(), &[0b1100_0001, 0b10, 0, 0, 0b11, 0]);
+        assert_eq!(indexed.get_index(Point::new(0, 2)), Some(PaletteIndex(3)));
+        assert_eq!(indexed.get_index(Point::new(5, 0)), None);
+
+        indexed.fill(PaletteIndex(2));
+        assert_eq!(indexed.as_bytes(), &[0b1010_1010; 6]);
+    }
+
+    #[test]
+    fn cycle() {
+        let mut palette = Palette::new(COLORS);
+
+        palette.cycle(1..4, 1);
+        assert_eq!(
+            &palette.colors()[..4],
+            &[Rgb888::BLACK, Rgb888::GREEN, Rgb888::WHITE, Rgb888::RED]
+        );
+
+        palette.cycle(1..4, -1);
+        assert_eq!(palette.colors(), &COLORS);
+
+        palette.cycle(4..10, 1);
+        assert_eq!(&palette.colors()[4..], &[Rgb888::YELLOW, Rgb888::BLUE]);
+    }
+}
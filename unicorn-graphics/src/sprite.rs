@@ -0,0 +1,338 @@
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics_core::{
+    pixelcolor::Rgb888,
+    prelude::{Point, PointsIter, Size},
+};
+
+use crate::{blend::Rgba, BlendMode, UnicornGraphics};
+
+/// A single frame of a sprite `W` pixels wide and `H` pixels tall.
+/// Accessed via height, then width e.g. `frame[y][x]`.
+pub type SpriteFrame<const W: usize, const H: usize> = [[Rgba; W]; H];
+
+/// Create a sprite frame from opaque pixels, where every pixel of the key color is transparent.
+pub fn key_frame<const W: usize, const H: usize>(
+    pixels: [[Rgb888; W]; H],
+    key: Rgb888,
+) -> SpriteFrame<W, H> {
+    pixels.map(|row| {
+        row.map(|color| {
+            let alpha = if color == key { 0 } else { u8::MAX };
+            Rgba::from_rgb(color, alpha)
+        })
+    })
+}
+
+/// Clockwise rotation of a sprite when drawn.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Rotation {
+    #[default]
+    Rotate0,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+/// A small image `W` pixels wide and `H` pixels tall with transparency and animation frames,
+/// which can be drawn onto graphics and checked for collisions.
+///
+/// Pixels with an alpha of 0 are transparent, and are never drawn or collided with.
+/// Use [`key_frame`] to create frames with a transparent key color instead.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Sprite<'a, const W: usize, const H: usize> {
+    frames: &'a [SpriteFrame<W, H>],
+    frame: usize,
+
+    /// Position of the top left of the sprite, after it is flipped and rotated.
+    pub position: Point,
+
+    /// Mirror the sprite left to right.
+    pub flip_horizontal: bool,
+
+    /// Mirror the sprite top to bottom.
+    pub flip_vertical: bool,
+
+    /// Rotate the sprite clockwise, after it is flipped.
+    pub rotation: Rotation,
+
+    /// Blend mode used when drawing the sprite.
+    pub mode: BlendMode,
+}
+
+impl<'a, const W: usize, const H: usize> Sprite<'a, W, H> {
+    /// Create a new sprite of the frames at the origin, showing the first frame.
+    pub const fn new(frames: &'a [SpriteFrame<W, H>]) -> Self {
+        Self {
+            frames,
+            frame: 0,
+            position: Point::zero(),
+            flip_horizontal: false,
+            flip_vertical: false,
+            rotation: Rotation::Rotate0,
+            mode: BlendMode::Normal,
+        }
+    }
+
+    /// Set the position of the top left of the sprite.
+    pub fn with_position(mut self, position: Point) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Set whether the sprite is mirrored left to right and top to bottom.
+    pub fn with_flip(mut self, horizontal: bool, vertical: bool) -> Self {
+        self.flip_horizontal = horizontal;
+        self.flip_vertical = vertical;
+        self
+    }
+
+    /// Set the clockwise rotation of the sprite.
+    pub fn with_rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Set the blend mode used when drawing the sprite.
+    pub fn with_mode(mut self, mode: BlendMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Number of animation frames of the sprite.
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Index of the frame being shown.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Show the frame at the given index, wrapping around past the last frame.
+    pub fn set_frame(&mut self, index: usize) {
+        self.frame = index.checked_rem(self.frames.len()).unwrap_or(0);
+    }
+
+    /// Show the next frame, going back to the first after the last.
+    pub fn next_frame(&mut self) {
+        self.set_frame(self.frame + 1);
+    }
+
+    /// Size of the sprite as drawn, swapping the width and height when rotated a quarter turn.
+    pub fn size(&self) -> Size {
+        match self.rotation {
+            Rotation::Rotate0 | Rotation::Rotate180 => Size::new(W as u32, H as u32),
+            Rotation::Rotate90 | Rotation::Rotate270 => Size::new(H as u32, W as u32),
+        }
+    }
+
+    /// Area covered by the sprite as drawn.
+    pub fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(self.position, self.size())
+    }
+
+    /// Get the color of the sprite at the given point, providing the point is within the sprite
+    /// and the pixel is not transparent.
+    pub fn pixel_at(&self, point: Point) -> Option<Rgba> {
+        let offset = point - self.position;
+        let size = self.size();
+
+        let (dx, dy) = (
+            usize::try_from(offset.x).ok()?,
+            usize::try_from(offset.y).ok()?,
+        );
+        if dx >= size.width as usize || dy >= size.height as usize {
+            return None;
+        }
+
+        // undo the rotation, then the flip, to find the pixel of the frame
+        let (mut x, mut y) = match self.rotation {
+            Rotation::Rotate0 => (dx, dy),
+            Rotation::Rotate90 => (dy, H - 1 - dx),
+            Rotation::Rotate180 => (W - 1 - dx, H - 1 - dy),
+            Rotation::Rotate270 => (W - 1 - dy, dx),
+        };
+
+        if self.flip_horizontal {
+            x = W - 1 - x;
+        }
+        if self.flip_vertical {
+            y = H - 1 - y;
+        }
+
+        let color = self.frames.get(self.frame)?[y][x];
+        (color.a > 0).then_some(color)
+    }
+
+    /// Draw the current frame onto the graphics, blending each pixel by its alpha.
+    pub fn draw<const GW: usize, const GH: usize>(&self, graphics: &mut UnicornGraphics<GW, GH>) {
+        for point in self.bounding_box().points() {
+            if let Some(color) = self.pixel_at(point) {
+                graphics.blend_pixel_rgba(point, color, self.mode);
+            }
+        }
+    }
+
+    /// Check if any pixel of this sprite overlaps a pixel of the other sprite, ignoring transparent pixels.
+    pub fn collides_with<const OW: usize, const OH: usize>(&self, other: &Sprite<OW, OH>) -> bool {
+        self.bounding_box()
+            .intersection(&other.bounding_box())
+            .points()
+            .any(|point| self.pixel_at(point).is_some() && other.pixel_at(point).is_some())
+    }
+
+    /// Check if any pixel of this sprite is over a colored pixel of the graphics, ignoring transparent pixels.
+    /// Check before the sprite is drawn onto the same graphics, otherwise it always collides with itself.
+    pub fn collides_with_graphics<const GW: usize, const GH: usize>(
+        &self,
+        graphics: &UnicornGraphics<GW, GH>,
+    ) -> bool {
+        self.bounding_box()
+            .points()
+            .any(|point| self.pixel_at(point).is_some() && graphics.is_colored(point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::prelude::RgbColor;
+
+    use super::*;
+
+    /// A 3x2 frame with the red channel of each pixel naming it:
+    /// `1 2 3` on the top row and `11 12 13` on the bottom row.
+    const FRAMES: [SpriteFrame<3, 2>; 1] = [[
+        [
+            Rgba::new(1, 0, 0, 255),
+            Rgba::new(2, 0, 0, 255),
+            Rgba::new(3, 0, 0, 255),
+        ],
+        [
+            Rgba::new(11, 0, 0, 255),
+            Rgba::new(12, 0, 0, 255),
+            Rgba::new(13, 0, 0, 255),
+        ],
+    ]];
+
+    /// Get the red channel of the sprite over a 3x3 area from its position, 0 where nothing is drawn.
+    fn layout(sprite: &Sprite<3, 2>) -> [[u8; 3]; 3] {
+        let mut layout = [[0; 3]; 3];
+        for (y, row) in layout.iter_mut().enumerate() {
+            for (x, red) in row.iter_mut().enumerate() {
+                let point = sprite.position + Point::new(x as i32, y as i32);
+                *red = sprite.pixel_at(point).map_or(0, |color| color.r);
+            }
+        }
+        layout
+    }
+
+    #[test]
+    fn rotations_turn_clockwise() {
+        let sprite = Sprite::new(&FRAMES).with_position(Point::new(5, -2));
+        assert_eq!(layout(&sprite), [[1, 2, 3], [11, 12, 13], [0, 0, 0]]);
+
+        let sprite = sprite.with_rotation(Rotation::Rotate90);
+        assert_eq!(sprite.size(), Size::new(2, 3));
+        assert_eq!(layout(&sprite), [[11, 1, 0], [12, 2, 0], [13, 3, 0]]);
+
+        let sprite = sprite.with_rotation(Rotation::Rotate180);
+        assert_eq!(sprite.size(), Size::new(3, 2));
+        assert_eq!(layout(&sprite), [[13, 12, 11], [3, 2, 1], [0, 0, 0]]);
+
+        let sprite = sprite.with_rotation(Rotation::Rotate270);
+        assert_eq!(layout(&sprite), [[3, 13, 0], [2, 12, 0], [1, 11, 0]]);
+    }
+
+    #[test]
+    fn flips_mirror_before_rotating() {
+        let sprite = Sprite::new(&FRAMES).with_flip(true, false);
+        assert_eq!(layout(&sprite), [[3, 2, 1], [13, 12, 11], [0, 0, 0]]);
+
+        let sprite = sprite.with_flip(false, true);
+        assert_eq!(layout(&sprite), [[11, 12, 13], [1, 2, 3], [0, 0, 0]]);
+
+        let sprite = sprite
+            .with_flip(true, false)
+            .with_rotation(Rotation::Rotate90);
+        assert_eq!(layout(&sprite), [[13, 3, 0], [12, 2, 0], [11, 1, 0]]);
+    }
+
+    #[test]
+    fn key_color_is_transparent() {
+        let frames = [key_frame(
+            [[Rgb888::RED, Rgb888::BLACK], [Rgb888::BLACK, Rgb888::BLUE]],
+            Rgb888::BLACK,
+        )];
+        let sprite = Sprite::new(&frames);
+
+        assert_eq!(
+            sprite.pixel_at(Point::new(0, 0)),
+            Some(Rgba::from(Rgb888::RED))
+        );
+        assert_eq!(sprite.pixel_at(Point::new(1, 0)), None);
+        assert_eq!(sprite.pixel_at(Point::new(2, 0)), None);
+        assert_eq!(sprite.pixel_at(Point::new(0, -1)), None);
+    }
+
+    #[test]
+    fn draw_blends_the_rotated_frame() {
+        let sprite = Sprite::new(&FRAMES)
+            .with_position(Point::new(1, 0))
+            .with_rotation(Rotation::Rotate90);
+        let mut graphics = UnicornGraphics::<4, 4>::new();
+
+        sprite.draw(&mut graphics);
+
+        assert_eq!(graphics.get_item(Point::new(0, 0)), Some(Rgb888::BLACK));
+        assert_eq!(
+            graphics.get_item(Point::new(1, 0)),
+            Some(Rgb888::new(11, 0, 0))
+        );
+        assert_eq!(
+            graphics.get_item(Point::new(2, 2)),
+            Some(Rgb888::new(3, 0, 0))
+        );
+        assert_eq!(graphics.get_item(Point::new(3, 0)), Some(Rgb888::BLACK));
+    }
+
+    #[test]
+    fn collisions_ignore_transparent_pixels() {
+        let frames = [key_frame(
+            [[Rgb888::RED, Rgb888::BLACK], [Rgb888::BLACK, Rgb888::RED]],
+            Rgb888::BLACK,
+        )];
+        let sprite = Sprite::new(&frames);
+
+        // overlapping the bounding box, but only over transparent pixels
+        let other = Sprite::new(&frames).with_position(Point::new(1, 0));
+        assert!(!sprite.collides_with(&other));
+
+        let other = other.with_flip(true, false);
+        assert!(sprite.collides_with(&other));
+
+        let mut graphics = UnicornGraphics::<4, 4>::new();
+        graphics.set_pixel(Point::new(1, 0), Rgb888::WHITE);
+        assert!(!sprite.collides_with_graphics(&graphics));
+
+        graphics.set_pixel(Point::new(1, 1), Rgb888::WHITE);
+        assert!(sprite.collides_with_graphics(&graphics));
+    }
+
+    #[test]
+    fn frames_wrap_around() {
+        let frames = [FRAMES[0]; 3];
+        let mut sprite = Sprite::new(&frames);
+
+        sprite.set_frame(4);
+        assert_eq!(sprite.frame(), 1);
+
+        sprite.next_frame();
+        sprite.next_frame();
+        assert_eq!(sprite.frame(), 0);
+
+        let mut empty = Sprite::<3, 2>::new(&[]);
+        empty.next_frame();
+        assert_eq!(empty.frame(), 0);
+        assert_eq!(empty.pixel_at(Point::zero()), None);
+    }
+}
@@ -0,0 +1,297 @@
+use embedded_graphics_core::{
+    pixelcolor::Rgb888,
+    prelude::{Dimensions, Point, RgbColor},
+    primitives::Rectangle,
+};
+
+use crate::UnicornGraphics;
+
+/// What to show in the pixels uncovered when scrolling the buffer.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum ScrollEdge {
+    /// Pixels scrolled off one edge come back in on the opposite edge.
+    #[default]
+    Wrap,
+
+    /// Uncovered pixels are filled with the color.
+    Fill(Rgb888),
+}
+
+/// How pixels are sampled when scaling the buffer to a different size.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum ScaleFilter {
+    /// Take the color of the closest source pixel, keeping hard edges.
+    #[default]
+    Nearest,
+
+    /// Mix the colors of the four closest source pixels, smoothing edges.
+    Bilinear,
+}
+
+impl<const W: usize, const H: usize> UnicornGraphics<W, H> {
+    /// Move all pixels by dx and dy, where positive values move right and down.
+    pub fn scroll(&mut self, dx: i32, dy: i32, edge: ScrollEdge) {
+        let pixels = self.pixels;
+
+        for y in 0..H {
+            for x in 0..W {
+                let source_x = x as i32 - dx;
+                let source_y = y as i32 - dy;
+
+                let color = match edge {
+                    ScrollEdge::Wrap => {
+                        let source_x = source_x.rem_euclid(W as i32) as usize;
+                        let source_y = source_y.rem_euclid(H as i32) as usize;
+                        pixels[source_y][source_x]
+                    }
+                    ScrollEdge::Fill(color) => {
+                        match (usize::try_from(source_x), usize::try_from(source_y)) {
+                            (Ok(source_x), Ok(source_y)) if source_x < W && source_y < H => {
+                                pixels[source_y][source_x]
+                            }
+                            _ => color,
+                        }
+                    }
+                };
+
+                self.write_pixel(x, y, color);
+            }
+        }
+    }
+
+    /// Mirror the buffer from left to right.
+    pub fn flip_horizontal(&mut self) {
+        let pixels = self.pixels;
+
+        for (y, row) in pixels.iter().enumerate() {
+            for (x, color) in row.iter().rev().enumerate() {
+                self.write_pixel(x, y, *color);
+            }
+        }
+    }
+
+    /// Mirror the buffer from top to bottom.
+    pub fn flip_vertical(&mut self) {
+        let pixels = self.pixels;
+
+        for (y, row) in pixels.iter().rev().enumerate() {
+            for (x, color) in row.iter().enumerate() {
+                self.write_pixel(x, y, *color);
+            }
+        }
+    }
+
+    /// Turn the buffer upside down.
+    pub fn rotate_180(&mut self) {
+        let pixels = self.pixels;
+
+        for (y, row) in pixels.iter().rev().enumerate() {
+            for (x, color) in row.iter().rev().enumerate() {
+                self.write_pixel(x, y, *color);
+            }
+        }
+    }
+
+    /// Scale the whole buffer to fill the target buffer, which can be a different size.
+    pub fn scale_into<const TW: usize, const TH: usize>(
+        &self,
+        target: &mut UnicornGraphics<TW, TH>,
+        filter: ScaleFilter,
+    ) {
+        if W == 0 || H == 0 {
+            return;
+        }
+
+        for y in 0..TH {
+            for x in 0..TW {
+                let color = match filter {
+                    ScaleFilter::Nearest => {
+                        let source_x = (2 * x + 1) * W / (2 * TW);
+                        let source_y = (2 * y + 1) * H / (2 * TH);
+                        self.pixels[source_y][source_x]
+                    }
+                    ScaleFilter::Bilinear => {
+                        self.sample_bilinear(scale_position(x, W, TW), scale_position(y, H, TH))
+                    }
+                };
+
+                target.write_pixel(x, y, color);
+            }
+        }
+    }
+
+    /// Get a copy of the buffer scaled to a different size.
+    pub fn scaled<const TW: usize, const TH: usize>(
+        &self,
+        filter: ScaleFilter,
+    ) -> UnicornGraphics<TW, TH> {
+        let mut target = UnicornGraphics::new();
+        self.scale_into(&mut target, filter);
+        target
+    }
+
+    /// Copy the area of the source buffer onto this one, with the top left of the area at the position.
+    /// Pixels outside of either buffer are skipped.
+    pub fn blit<const SW: usize, const SH: usize>(
+        &mut self,
+        source: &UnicornGraphics<SW, SH>,
+        area: &Rectangle,
+        position: Point,
+    ) {
+        let offset = position - area.top_left;
+        let area = area.intersection(&source.bounding_box());
+
+        for y in area.rows() {
+            for x in area.columns() {
+                let color = source.pixels[y as usize][x as usize];
+                self.set_pixel(Point::new(x, y) + offset, color);
+            }
+        }
+    }
+
+    /// Mix the four pixels around the position, given in 1/256ths of a pixel.
+    fn sample_bilinear(&self, x: u32, y: u32) -> Rgb888 {
+        let (x0, y0) = ((x >> 8) as usize, (y >> 8) as usize);
+        let (x1, y1) = ((x0 + 1).min(W - 1), (y0 + 1).min(H - 1));
+        let (fx, fy) = (x & 0xff, y & 0xff);
+
+        let weights = [
+            (self.pixels[y0][x0], (256 - fx) * (256 - fy)),
+            (self.pixels[y0][x1], fx * (256 - fy)),
+            (self.pixels[y1][x0], (256 - fx) * fy),
+            (self.pixels[y1][x1], fx * fy),
+        ];
+
+        let channel = |value: fn(&Rgb888) -> u8| {
+            let sum: u32 = weights
+                .iter()
+                .map(|(color, weight)| value(color) as u32 * weight)
+                .sum();
+            ((sum + (1 << 15)) >> 16) as u8
+        };
+
+        Rgb888::new(channel(Rgb888::r), channel(Rgb888::g), channel(Rgb888::b))
+    }
+}
+
+/// Get the source position of a target pixel's center in 1/256ths of a pixel,
+/// clamped to the centers of the edge pixels.
+fn scale_position(target: usize, source_len: usize, target_len: usize) -> u32 {
+    let center = ((2 * target + 1) * source_len * 256 / (2 * target_len)) as u32;
+    center
+        .saturating_sub(128)
+        .min((source_len as u32 - 1) * 256)
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::prelude::Size;
+
+    use super::*;
+
+    /// A 3x2 buffer with the red channel of each pixel naming it:
+    /// `1 2 3` on the top row and `11 12 13` on the bottom row.
+    fn numbered() -> UnicornGraphics<3, 2> {
+        let mut graphics = UnicornGraphics::new();
+        graphics.set_pixels([
+            [1, 2, 3].map(|red| Rgb888::new(red, 0, 0)),
+            [11, 12, 13].map(|red| Rgb888::new(red, 0, 0)),
+        ]);
+        graphics
+    }
+
+    fn reds<const W: usize, const H: usize>(graphics: &UnicornGraphics<W, H>) -> [[u8; W]; H] {
+        graphics.get_pixels().map(|row| row.map(|color| color.r()))
+    }
+
+    #[test]
+    fn flips_and_rotation() {
+        let mut graphics = numbered();
+        graphics.flip_horizontal();
+        assert_eq!(reds(&graphics), [[3, 2, 1], [13, 12, 11]]);
+
+        let mut graphics = numbered();
+        graphics.flip_vertical();
+        assert_eq!(reds(&graphics), [[11, 12, 13], [1, 2, 3]]);
+
+        let mut graphics = numbered();
+        graphics.rotate_180();
+        assert_eq!(reds(&graphics), [[13, 12, 11], [3, 2, 1]]);
+
+        graphics.rotate_180();
+        assert_eq!(reds(&graphics), reds(&numbered()));
+    }
+
+    #[test]
+    fn scroll_wraps_around() {
+        let mut graphics = numbered();
+        graphics.scroll(1, 0, ScrollEdge::Wrap);
+        assert_eq!(reds(&graphics), [[3, 1, 2], [13, 11, 12]]);
+
+        let mut graphics = numbered();
+        graphics.scroll(-4, 1, ScrollEdge::Wrap);
+        assert_eq!(reds(&graphics), [[12, 13, 11], [2, 3, 1]]);
+    }
+
+    #[test]
+    fn scroll_fills_uncovered_pixels() {
+        let fill = ScrollEdge::Fill(Rgb888::new(99, 0, 0));
+
+        let mut graphics = numbered();
+        graphics.scroll(-1, 0, fill);
+        assert_eq!(reds(&graphics), [[2, 3, 99], [12, 13, 99]]);
+
+        let mut graphics = numbered();
+        graphics.scroll(1, -1, fill);
+        assert_eq!(reds(&graphics), [[99, 11, 12], [99, 99, 99]]);
+
+        let mut graphics = numbered();
+        graphics.scroll(10, 0, fill);
+        assert_eq!(reds(&graphics), [[99; 3]; 2]);
+    }
+
+    #[test]
+    fn nearest_scale_repeats_and_drops_pixels() {
+        let scaled = numbered().scaled::<6, 4>(ScaleFilter::Nearest);
+        assert_eq!(
+            reds(&scaled),
+            [
+                [1, 1, 2, 2, 3, 3],
+                [1, 1, 2, 2, 3, 3],
+                [11, 11, 12, 12, 13, 13],
+                [11, 11, 12, 12, 13, 13],
+            ]
+        );
+
+        let restored = scaled.scaled::<3, 2>(ScaleFilter::Nearest);
+        assert_eq!(reds(&restored), reds(&numbered()));
+    }
+
+    #[test]
+    fn bilinear_scale_mixes_neighbours() {
+        let mut graphics = UnicornGraphics::<2, 1>::new();
+        graphics.set_pixels([[Rgb888::BLACK, Rgb888::WHITE]]);
+
+        let scaled = graphics.scaled::<4, 1>(ScaleFilter::Bilinear);
+        assert_eq!(reds(&scaled), [[0, 64, 191, 255]]);
+
+        let mut graphics = UnicornGraphics::<3, 2>::new();
+        graphics.fill(Rgb888::new(10, 20, 30));
+        let scaled = graphics.scaled::<5, 7>(ScaleFilter::Bilinear);
+        assert_eq!(scaled.get_pixels(), [[Rgb888::new(10, 20, 30); 5]; 7]);
+    }
+
+    #[test]
+    fn blit_copies_the_area_within_both_buffers() {
+        let mut target = UnicornGraphics::<3, 2>::new();
+        let area = Rectangle::new(Point::new(1, 0), Size::new(5, 2));
+
+        target.blit(&numbered(), &area, Point::new(-1, 1));
+
+        assert_eq!(reds(&target), [[0, 0, 0], [3, 0, 0]]);
+
+        let mut target = UnicornGraphics::<3, 2>::new();
+        target.blit(&numbered(), &area, Point::new(1, 0));
+        assert_eq!(reds(&target), [[0, 2, 3], [0, 12, 13]]);
+    }
+}
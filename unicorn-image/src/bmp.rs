@@ -0,0 +1,270 @@
+use embedded_graphics_core::prelude::{Point, Size};
+use unicorn_graphics::Rgba;
+
+use crate::{read_u16_le, read_u32_le, Error, Image};
+
+/// Uncompressed pixels.
+const BI_RGB: u32 = 0;
+
+/// Uncompressed pixels with masks for each channel.
+const BI_BITFIELDS: u32 = 3;
+
+/// Uncompressed pixels with masks for each channel, including alpha.
+const BI_ALPHABITFIELDS: u32 = 6;
+
+/// Size of the oldest DIB header, which has 16 bit sizes and 3 byte palette entries.
+const CORE_HEADER_SIZE: u32 = 12;
+
+/// A BMP image, read straight from its data.
+///
+/// Supports uncompressed images with 1, 4 or 8 bit palettes, and 16, 24 or 32 bit pixels
+/// including channel masks and alpha. Run length encoded images are not supported.
+#[derive(Copy, Clone, Debug)]
+pub struct Bmp<'a> {
+    data: &'a [u8],
+    width: u32,
+    height: u32,
+
+    /// Rows are stored from the top down, rather than the usual bottom up.
+    top_down: bool,
+    bits_per_pixel: u16,
+    pixels_offset: usize,
+    row_bytes: usize,
+
+    palette: &'a [u8],
+    palette_entry_bytes: usize,
+
+    /// Masks of the red, green, blue and alpha channels, for 16 and 32 bit pixels.
+    masks: [u32; 4],
+}
+
+impl<'a> Bmp<'a> {
+    /// Read the headers of the BMP data, such as from `include_bytes!`.
+    pub fn new(data: &'a [u8]) -> Result<Self, Error> {
+        if data.get(0..2) != Some(b"BM") {
+            return Err(Error::InvalidSignature);
+        }
+
+        let pixels_offset = read_u32_le(data, 10)? as usize;
+        let header_size = read_u32_le(data, 14)?;
+
+        let (width, height, bits_per_pixel, compression, colors_used) =
+            if header_size == CORE_HEADER_SIZE {
+                let width = read_u16_le(data, 18)? as i32;
+                let height = read_u16_le(data, 20)? as i32;
+                (width, height, read_u16_le(data, 24)?, BI_RGB, 0)
+            } else if header_size >= 40 {
+                (
+                    read_u32_le(data, 18)? as i32,
+                    read_u32_le(data, 22)? as i32,
+                    read_u16_le(data, 28)?,
+                    read_u32_le(data, 30)?,
+                    read_u32_le(data, 46)?,
+                )
+            } else {
+                return Err(Error::Unsupported);
+            };
+
+        if width <= 0 || height == 0 {
+            return Err(Error::Corrupt);
+        }
+
+        let masks = match (compression, bits_per_pixel) {
+            (BI_BITFIELDS | BI_ALPHABITFIELDS, 16 | 32) => {
+                // masks follow the 40 byte header, or are part of the larger headers
+                let alpha = if compression == BI_ALPHABITFIELDS || header_size >= 56 {
+                    read_u32_le(data, 66)?
+                } else {
+                    0
+                };
+                [
+                    read_u32_le(data, 54)?,
+                    read_u32_le(data, 58)?,
+                    read_u32_le(data, 62)?,
+                    alpha,
+                ]
+            }
+            (BI_RGB, 16) => [0x7c00, 0x03e0, 0x001f, 0],
+            (BI_RGB, 32) => [0x00ff_0000, 0x0000_ff00, 0x0000_00ff, 0],
+            (BI_RGB, 1 | 4 | 8 | 24) => [0; 4],
+            _ => return Err(Error::Unsupported),
+        };
+
+        let palette_entry_bytes = if header_size == CORE_HEADER_SIZE {
+            3
+        } else {
+            4
+        };
+        let palette = if bits_per_pixel <= 8 {
+            let colors: usize = match colors_used {
+                0 => 1 << bits_per_pixel,
+                colors => (colors as usize).min(1 << bits_per_pixel),
+            };
+
+            let start = 14 + header_size as usize;
+            data.get(start..start + colors * palette_entry_bytes)
+                .ok_or(Error::Truncated)?
+        } else {
+            &[]
+        };
+
+        let row_bytes = (width as usize)
+            .checked_mul(bits_per_pixel as usize)
+            .ok_or(Error::Unsupported)?
+            .div_ceil(32)
+            * 4;
+        let bmp = Self {
+            data,
+            width: width as u32,
+            height: height.unsigned_abs(),
+            top_down: height < 0,
+            bits_per_pixel,
+            pixels_offset,
+            row_bytes,
+            palette,
+            palette_entry_bytes,
+            masks,
+        };
+
+        let end = (row_bytes as u64 * bmp.height as u64).saturating_add(pixels_offset as u64);
+        if (data.len() as u64) < end {
+            return Err(Error::Truncated);
+        }
+
+        Ok(bmp)
+    }
+
+    /// Get the color of the pixel, providing it is within the image.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Rgba> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let row = if self.top_down {
+            y
+        } else {
+            self.height - 1 - y
+        };
+        let start = self.pixels_offset + row as usize * self.row_bytes;
+        let row = &self.data[start..start + self.row_bytes];
+        let x = x as usize;
+
+        let color = match self.bits_per_pixel {
+            1 | 4 | 8 => {
+                let bits = self.bits_per_pixel as usize;
+                let bit = x * bits;
+                let mask = (1_u16 << bits) - 1;
+                // the first pixel of each byte is in the highest bits
+                let index = (row[bit / 8] as u16 >> (8 - bits - bit % 8)) & mask;
+                self.palette_color(index as usize)
+            }
+            24 => Rgba::new(row[x * 3 + 2], row[x * 3 + 1], row[x * 3], u8::MAX),
+            16 => self.masked_color(u16::from_le_bytes([row[x * 2], row[x * 2 + 1]]) as u32),
+            _ => {
+                let bytes = [row[x * 4], row[x * 4 + 1], row[x * 4 + 2], row[x * 4 + 3]];
+                self.masked_color(u32::from_le_bytes(bytes))
+            }
+        };
+
+        Some(color)
+    }
+
+    /// Get the color of the palette entry, or black if the index is not within the palette.
+    fn palette_color(&self, index: usize) -> Rgba {
+        let start = index * self.palette_entry_bytes;
+        match self.palette.get(start..start + 3) {
+            Some(entry) => Rgba::new(entry[2], entry[1], entry[0], u8::MAX),
+            None => Rgba::new(0, 0, 0, u8::MAX),
+        }
+    }
+
+    /// Get the color of a 16 or 32 bit pixel from the channel masks.
+    /// The pixel is opaque when there is no alpha mask.
+    fn masked_color(&self, pixel: u32) -> Rgba {
+        let [r, g, b, a] = self.masks.map(|mask| channel(pixel, mask));
+        let a = if self.masks[3] == 0 { u8::MAX } else { a };
+        Rgba::new(r, g, b, a)
+    }
+}
+
+impl Image for Bmp<'_> {
+    fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+
+    fn for_each_pixel<F>(&self, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(Point, Rgba),
+    {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if let Some(color) = self.pixel(x, y) {
+                    f(Point::new(x as i32, y as i32), color);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Get the channel of the pixel under the mask, scaled to 8 bits.
+fn channel(pixel: u32, mask: u32) -> u8 {
+    if mask == 0 {
+        return 0;
+    }
+
+    let value = (pixel & mask) >> mask.trailing_zeros();
+    let max = mask >> mask.trailing_zeros();
+    (value as u64 * 255 / max as u64) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::prelude::Size;
+    use unicorn_graphics::Rgba;
+
+    use super::*;
+    use crate::tests::{assert_pixels, palette_color, palette_index, pattern};
+
+    #[test]
+    fn rgb24_bottom_up_with_padding() {
+        let bmp = Bmp::new(include_bytes!("../fixtures/rgb24.bmp")).unwrap();
+        assert_pixels(&bmp, Size::new(9, 7), |x, y| pattern(x, y, false));
+    }
+
+    #[test]
+    fn indexed8_top_down() {
+        let bmp = Bmp::new(include_bytes!("../fixtures/indexed8_top_down.bmp")).unwrap();
+        assert_pixels(&bmp, Size::new(9, 7), |x, y| {
+            palette_color(palette_index(x, y))
+        });
+    }
+
+    #[test]
+    fn rgba32_with_alpha_masks() {
+        let bmp = Bmp::new(include_bytes!("../fixtures/rgba32_bitfields.bmp")).unwrap();
+        assert_pixels(&bmp, Size::new(9, 7), |x, y| pattern(x, y, true));
+    }
+
+    #[test]
+    fn pixel_lookup() {
+        let bmp = Bmp::new(include_bytes!("../fixtures/rgb24.bmp")).unwrap();
+
+        assert_eq!(bmp.pixel(8, 6), Some(pattern(8, 6, false)));
+        assert_eq!(bmp.pixel(9, 0), None);
+        assert_eq!(bmp.pixel(0, 7), None);
+    }
+
+    #[test]
+    fn invalid_data() {
+        let data = include_bytes!("../fixtures/rgb24.bmp");
+
+        assert_eq!(Bmp::new(b"PNG").err(), Some(Error::InvalidSignature));
+        assert_eq!(Bmp::new(&data[..20]).err(), Some(Error::Truncated));
+
+        // the header is read, but the pixels run past the end of the data
+        let bmp = Bmp::new(&data[..data.len() - 10]);
+        let result = bmp.and_then(|bmp| bmp.for_each_pixel(|_, _: Rgba| {}));
+        assert_eq!(result, Err(Error::Truncated));
+    }
+}
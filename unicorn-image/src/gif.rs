@@ -0,0 +1,741 @@
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics_core::{
+    pixelcolor::Rgb888,
+    prelude::{Point, PointsIter, Size},
+};
+use unicorn_graphics::UnicornGraphics;
+
+use crate::{read_u16_le, Error};
+
+/// Largest number of codes in an LZW table.
+const MAX_CODES: usize = 4096;
+
+/// Largest code size in bits.
+const MAX_CODE_BITS: u32 = 12;
+
+const EXTENSION: u8 = 0x21;
+const IMAGE: u8 = 0x2c;
+const TRAILER: u8 = 0x3b;
+
+const GRAPHIC_CONTROL: u8 = 0xf9;
+const APPLICATION: u8 = 0xff;
+
+/// An animated GIF, read straight from its data.
+///
+/// Frames are decoded one at a time by a [`GifPlayer`], so only the player needs memory.
+#[derive(Copy, Clone, Debug)]
+pub struct Gif<'a> {
+    data: &'a [u8],
+    width: u16,
+    height: u16,
+    global_palette: &'a [u8],
+
+    /// Offset of the first block after the header and global palette.
+    first_block: usize,
+    frame_count: usize,
+    loop_count: Option<u16>,
+}
+
+impl<'a> Gif<'a> {
+    /// Read the GIF data, such as from `include_bytes!`, checking each of its blocks.
+    pub fn new(data: &'a [u8]) -> Result<Self, Error> {
+        match data.get(0..6) {
+            Some(b"GIF87a" | b"GIF89a") => {}
+            Some(_) => return Err(Error::InvalidSignature),
+            None => return Err(Error::Truncated),
+        }
+
+        let width = read_u16_le(data, 6)?;
+        let height = read_u16_le(data, 8)?;
+        let flags = *data.get(10).ok_or(Error::Truncated)?;
+
+        let (global_palette, first_block) = palette(data, 13, flags)?;
+
+        let mut gif = Self {
+            data,
+            width,
+            height,
+            global_palette,
+            first_block,
+            frame_count: 0,
+            loop_count: None,
+        };
+
+        let mut offset = first_block;
+        loop {
+            match gif.block(offset)? {
+                Block::Control { next, .. } | Block::Other { next } => offset = next,
+                Block::Loop { count, next } => {
+                    gif.loop_count = Some(count);
+                    offset = next;
+                }
+                Block::Image { data_start, .. } => {
+                    gif.frame_count += 1;
+                    offset = skip_sub_blocks(data, data_start + 1)?;
+                }
+                Block::Trailer => break,
+            }
+        }
+
+        Ok(gif)
+    }
+
+    /// Size of the animation in pixels.
+    pub fn size(&self) -> Size {
+        Size::new(self.width as u32, self.height as u32)
+    }
+
+    /// Number of frames of the animation.
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    /// Number of times the animation repeats after it is first played, where 0 repeats forever.
+    /// None when the GIF does not loop, so plays once.
+    pub fn loop_count(&self) -> Option<u16> {
+        self.loop_count
+    }
+
+    /// Read the block at the offset.
+    fn block(&self, offset: usize) -> Result<Block<'a>, Error> {
+        let data = self.data;
+
+        match data.get(offset).copied() {
+            Some(EXTENSION) => {
+                let label = *data.get(offset + 1).ok_or(Error::Truncated)?;
+                let next = skip_sub_blocks(data, offset + 2)?;
+                let block = data.get(offset + 2..next).ok_or(Error::Truncated)?;
+
+                match (label, block) {
+                    (GRAPHIC_CONTROL, [4, flags, delay_low, delay_high, transparent, ..]) => {
+                        Ok(Block::Control {
+                            control: FrameControl {
+                                disposal: Disposal::from_flags(*flags),
+                                delay: u16::from_le_bytes([*delay_low, *delay_high]),
+                                transparent: (flags & 0x01 != 0).then_some(*transparent),
+                            },
+                            next,
+                        })
+                    }
+                    (APPLICATION, [11, id @ .., 3, 1, count_low, count_high, 0])
+                        if id.starts_with(b"NETSCAPE2.0") || id.starts_with(b"ANIMEXTS1.0") =>
+                    {
+                        Ok(Block::Loop {
+                            count: u16::from_le_bytes([*count_low, *count_high]),
+                            next,
+                        })
+                    }
+                    _ => Ok(Block::Other { next }),
+                }
+            }
+            Some(IMAGE) => {
+                let descriptor = data.get(offset + 1..offset + 10).ok_or(Error::Truncated)?;
+                let area = Rectangle::new(
+                    Point::new(
+                        read_u16_le(descriptor, 0)? as i32,
+                        read_u16_le(descriptor, 2)? as i32,
+                    ),
+                    Size::new(
+                        read_u16_le(descriptor, 4)? as u32,
+                        read_u16_le(descriptor, 6)? as u32,
+                    ),
+                );
+                let flags = descriptor[8];
+
+                let (local_palette, data_start) = palette(data, offset + 10, flags)?;
+                let palette = if flags & 0x80 != 0 {
+                    local_palette
+                } else {
+                    self.global_palette
+                };
+
+                Ok(Block::Image {
+                    area,
+                    palette,
+                    interlaced: flags & 0x40 != 0,
+                    data_start,
+                })
+            }
+            Some(TRAILER) => Ok(Block::Trailer),
+            Some(_) => Err(Error::Corrupt),
+            None => Err(Error::Truncated),
+        }
+    }
+}
+
+/// How a frame is cleared before the next frame is drawn.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+enum Disposal {
+    /// Leave the frame in place.
+    #[default]
+    Keep,
+
+    /// Clear the area of the frame to the background.
+    Background,
+
+    /// Restore the area of the frame to what it was before the frame was drawn.
+    Previous,
+}
+
+impl Disposal {
+    fn from_flags(flags: u8) -> Self {
+        match (flags >> 2) & 0x07 {
+            2 => Disposal::Background,
+            3 => Disposal::Previous,
+            _ => Disposal::Keep,
+        }
+    }
+}
+
+/// Timing and transparency of the next frame, from a graphic control extension.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+struct FrameControl {
+    disposal: Disposal,
+
+    /// Delay after the frame in hundredths of a second.
+    delay: u16,
+    transparent: Option<u8>,
+}
+
+/// A block of a GIF.
+enum Block<'a> {
+    Control {
+        control: FrameControl,
+        next: usize,
+    },
+    Loop {
+        count: u16,
+        next: usize,
+    },
+    Image {
+        area: Rectangle,
+        palette: &'a [u8],
+        interlaced: bool,
+        data_start: usize,
+    },
+    Other {
+        next: usize,
+    },
+    Trailer,
+}
+
+/// A frame drawn by a [`GifPlayer`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct GifFrame {
+    /// Time to show the frame for before the next, in milliseconds.
+    /// Often 0 for GIFs meant to play as fast as possible, which most viewers show for 100ms.
+    pub delay_ms: u32,
+
+    /// Area of the graphics the frame was drawn to.
+    pub area: Rectangle,
+}
+
+/// Plays a [`Gif`] onto graphics `W` pixels wide and `H` pixels tall, a frame at a time.
+///
+/// Uses around 12KB for decoding, plus a copy of the graphics for frames
+/// that restore what was under them, so it is best kept in a static on a microcontroller.
+pub struct GifPlayer<'a, const W: usize, const H: usize> {
+    gif: Gif<'a>,
+
+    /// Position of the top left of the animation on the graphics.
+    pub position: Point,
+
+    /// Offset of the next block to read.
+    offset: usize,
+
+    /// Number of times the animation has played through.
+    plays: u32,
+
+    /// How to clear the last frame drawn, and the area it covered.
+    disposal: Option<(Disposal, Rectangle)>,
+
+    /// The graphics as they were before the last frame drawn, for [`Disposal::Previous`].
+    previous: UnicornGraphics<W, H>,
+
+    lzw: Lzw,
+}
+
+impl<'a, const W: usize, const H: usize> GifPlayer<'a, W, H> {
+    /// Create a new player of the animation, drawing with its top left at the origin.
+    pub fn new(gif: Gif<'a>) -> Self {
+        Self {
+            gif,
+            position: Point::zero(),
+            offset: gif.first_block,
+            plays: 0,
+            disposal: None,
+            previous: UnicornGraphics::new(),
+            lzw: Lzw::new(),
+        }
+    }
+
+    /// Set the position of the top left of the animation on the graphics.
+    pub fn with_position(mut self, position: Point) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Get the animation being played.
+    pub fn gif(&self) -> &Gif<'a> {
+        &self.gif
+    }
+
+    /// Start playing from the first frame again.
+    pub fn reset(&mut self) {
+        self.offset = self.gif.first_block;
+        self.plays = 0;
+        self.disposal = None;
+    }
+
+    /// Draw the next frame onto the graphics, clearing the last frame as the GIF asks.
+    /// Returns `None` once the animation has finished, going back to the start when it loops.
+    pub fn next_frame(
+        &mut self,
+        graphics: &mut UnicornGraphics<W, H>,
+    ) -> Result<Option<GifFrame>, Error> {
+        let mut control = FrameControl::default();
+
+        loop {
+            match self.gif.block(self.offset)? {
+                Block::Control {
+                    control: frame_control,
+                    next,
+                } => {
+                    control = frame_control;
+                    self.offset = next;
+                }
+                Block::Loop { next, .. } | Block::Other { next } => self.offset = next,
+                Block::Image {
+                    area,
+                    palette,
+                    interlaced,
+                    data_start,
+                } => {
+                    let area = Rectangle::new(area.top_left + self.position, area.size);
+                    self.dispose(graphics);
+
+                    if control.disposal == Disposal::Previous {
+                        self.previous = *graphics;
+                    }
+
+                    let mut blocks = SubBlocks::new(self.gif.data, data_start + 1);
+                    let min_code_size = *self.gif.data.get(data_start).ok_or(Error::Truncated)?;
+                    let width = area.size.width as usize;
+                    let rows = area.size.height as usize;
+
+                    self.lzw
+                        .decode(min_code_size, &mut blocks, |index, color| {
+                            if Some(color) == control.transparent || width == 0 {
+                                return;
+                            }
+
+                            let row = index / width;
+                            if row >= rows {
+                                return;
+                            }
+                            let y = if interlaced {
+                                interlaced_row(row, rows)
+                            } else {
+                                row
+                            };
+
+                            let start = color as usize * 3;
+                            if let Some(rgb) = palette.get(start..start + 3) {
+                                let point =
+                                    area.top_left + Point::new((index % width) as i32, y as i32);
+                                graphics.set_pixel(point, Rgb888::new(rgb[0], rgb[1], rgb[2]));
+                            }
+                        })?;
+
+                    self.offset = blocks.skip_rest()?;
+                    self.disposal = Some((control.disposal, area));
+
+                    return Ok(Some(GifFrame {
+                        delay_ms: control.delay as u32 * 10,
+                        area,
+                    }));
+                }
+                Block::Trailer => {
+                    self.plays += 1;
+
+                    let repeat = match self.gif.loop_count {
+                        Some(0) => true,
+                        Some(count) => self.plays <= count as u32,
+                        None => false,
+                    };
+                    if !repeat || self.gif.frame_count == 0 {
+                        return Ok(None);
+                    }
+
+                    // start again from the background
+                    self.disposal = None;
+                    self.offset = self.gif.first_block;
+                    let area = Rectangle::new(self.position, self.gif.size());
+                    for point in area.points() {
+                        graphics.clear_pixel(point);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Clear the last frame drawn as it asked.
+    fn dispose(&mut self, graphics: &mut UnicornGraphics<W, H>) {
+        match self.disposal.take() {
+            Some((Disposal::Background, area)) => {
+                for point in area.points() {
+                    graphics.clear_pixel(point);
+                }
+            }
+            Some((Disposal::Previous, area)) => {
+                for point in area.points() {
+                    if let Some(color) = self.previous.get_item(point) {
+                        graphics.set_pixel(point, color);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Get the palette starting at the offset when the flags say there is one,
+/// and the offset after it.
+fn palette(data: &[u8], offset: usize, flags: u8) -> Result<(&[u8], usize), Error> {
+    if flags & 0x80 == 0 {
+        return Ok((&[], offset));
+    }
+
+    let len = 3 << ((flags & 0x07) + 1);
+    let palette = data.get(offset..offset + len).ok_or(Error::Truncated)?;
+    Ok((palette, offset + len))
+}
+
+/// Get the offset after the sub blocks starting at the offset.
+fn skip_sub_blocks(data: &[u8], mut offset: usize) -> Result<usize, Error> {
+    loop {
+        let len = *data.get(offset).ok_or(Error::Truncated)? as usize;
+        offset += 1 + len;
+        if len == 0 {
+            return Ok(offset);
+        }
+    }
+}
+
+/// Get the row of the image for the row decoded from an interlaced image,
+/// which stores every 8th row, then the 4th, 2nd and finally the rest.
+fn interlaced_row(row: usize, height: usize) -> usize {
+    let mut row = row;
+    for (start, step) in [(0, 8), (4, 8), (2, 4), (1, 2)] {
+        let pass_rows = height.saturating_sub(start).div_ceil(step);
+        if row < pass_rows {
+            return start + row * step;
+        }
+        row -= pass_rows;
+    }
+    row
+}
+
+/// Reads the bytes of a chain of sub blocks.
+struct SubBlocks<'a> {
+    data: &'a [u8],
+    offset: usize,
+
+    /// Bytes left of the current sub block, or None once the terminating block is reached.
+    remaining: Option<usize>,
+}
+
+impl<'a> SubBlocks<'a> {
+    fn new(data: &'a [u8], offset: usize) -> Self {
+        Self {
+            data,
+            offset,
+            remaining: Some(0),
+        }
+    }
+
+    /// Skip any bytes left, returning the offset after the terminating block.
+    fn skip_rest(self) -> Result<usize, Error> {
+        match self.remaining {
+            Some(remaining) => skip_sub_blocks(self.data, self.offset + remaining),
+            None => Ok(self.offset),
+        }
+    }
+}
+
+impl Iterator for SubBlocks<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        let mut remaining = self.remaining?;
+
+        while remaining == 0 {
+            remaining = *self.data.get(self.offset)? as usize;
+            self.offset += 1;
+            if remaining == 0 {
+                self.remaining = None;
+                return None;
+            }
+        }
+
+        let byte = *self.data.get(self.offset)?;
+        self.offset += 1;
+        self.remaining = Some(remaining - 1);
+        Some(byte)
+    }
+}
+
+/// LZW decoder, where each code is stored as the code before it and its last color.
+struct Lzw {
+    prefix: [u16; MAX_CODES],
+    suffix: [u8; MAX_CODES],
+}
+
+impl Lzw {
+    const fn new() -> Self {
+        Self {
+            prefix: [0; MAX_CODES],
+            suffix: [0; MAX_CODES],
+        }
+    }
+
+    /// Decode the codes of the bytes, calling the function with the index of each pixel and its color.
+    fn decode<I, F>(&mut self, min_code_size: u8, bytes: &mut I, mut f: F) -> Result<(), Error>
+    where
+        I: Iterator<Item = u8>,
+        F: FnMut(usize, u8),
+    {
+        if !(2..=8).contains(&min_code_size) {
+            return Err(Error::Corrupt);
+        }
+
+        let clear = 1_u16 << min_code_size;
+        let end = clear + 1;
+        for code in 0..clear {
+            self.suffix[code as usize] = code as u8;
+        }
+
+        let mut next_code = clear + 2;
+        let mut code_bits = min_code_size as u32 + 1;
+        let mut previous: Option<u16> = None;
+        let mut index = 0;
+
+        let mut bits = 0_u32;
+        let mut count = 0;
+
+        loop {
+            while count < code_bits {
+                // images that end without an end code are still shown
+                let Some(byte) = bytes.next() else {
+                    return Ok(());
+                };
+                bits |= (byte as u32) << count;
+                count += 8;
+            }
+
+            let code = (bits & ((1 << code_bits) - 1)) as u16;
+            bits >>= code_bits;
+            count -= code_bits;
+
+            if code == clear {
+                next_code = clear + 2;
+                code_bits = min_code_size as u32 + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return Ok(());
+            }
+
+            let Some(previous_code) = previous else {
+                if code >= clear {
+                    return Err(Error::Corrupt);
+                }
+                f(index, code as u8);
+                index += 1;
+                previous = Some(code);
+                continue;
+            };
+
+            let first = if code < next_code {
+                let first = self.first(code, clear);
+                index = self.output(code, clear, index, &mut f);
+                first
+            } else if code == next_code {
+                // the code being defined, which is the previous string followed by its first color
+                let first = self.first(previous_code, clear);
+                index = self.output(previous_code, clear, index, &mut f);
+                f(index, first);
+                index += 1;
+                first
+            } else {
+                return Err(Error::Corrupt);
+            };
+
+            if (next_code as usize) < MAX_CODES {
+                self.prefix[next_code as usize] = previous_code;
+                self.suffix[next_code as usize] = first;
+                next_code += 1;
+
+                if next_code == 1 << code_bits && code_bits < MAX_CODE_BITS {
+                    code_bits += 1;
+                }
+            }
+
+            previous = Some(code);
+        }
+    }
+
+    /// Get the first color of the code.
+    fn first(&self, mut code: u16, clear: u16) -> u8 {
+        while code > clear {
+            code = self.prefix[code as usize];
+        }
+        self.suffix[code as usize]
+    }
+
+    /// Output the colors of the code from the index, returning the index after them.
+    /// The colors are stored last to first, so are output in reverse from the end.
+    fn output<F: FnMut(usize, u8)>(&self, code: u16, clear: u16, index: usize, f: &mut F) -> usize {
+        let mut len = 1;
+        let mut current = code;
+        while current > clear {
+            current = self.prefix[current as usize];
+            len += 1;
+        }
+
+        let mut current = code;
+        for position in (index..index + len).rev() {
+            f(position, self.suffix[current as usize]);
+            current = self.prefix[current as usize];
+        }
+
+        index + len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::prelude::RgbColor;
+
+    use super::*;
+    use crate::tests::{palette_color, palette_index};
+
+    const ANIMATION: &[u8] = include_bytes!("../fixtures/animation.gif");
+
+    fn color(index: u32) -> Rgb888 {
+        let color = palette_color(index);
+        Rgb888::new(color.r, color.g, color.b)
+    }
+
+    /// Check the graphics against rows of palette indexes, where `.` is black.
+    fn assert_graphics<const W: usize, const H: usize>(
+        graphics: &UnicornGraphics<W, H>,
+        rows: [&str; H],
+    ) {
+        for (y, row) in rows.iter().enumerate() {
+            for (x, index) in row.chars().enumerate() {
+                let expected = match index.to_digit(16) {
+                    Some(index) => color(index),
+                    None => Rgb888::BLACK,
+                };
+                let point = Point::new(x as i32, y as i32);
+                assert_eq!(graphics.get_item(point), Some(expected), "pixel {x}, {y}");
+            }
+        }
+    }
+
+    #[test]
+    fn interlaced() {
+        let gif = Gif::new(include_bytes!("../fixtures/interlaced.gif")).unwrap();
+        let mut player = GifPlayer::<8, 10>::new(gif);
+        let mut graphics = UnicornGraphics::new();
+
+        assert_eq!(gif.size(), Size::new(8, 10));
+        assert_eq!(gif.frame_count(), 1);
+        assert_eq!(gif.loop_count(), None);
+
+        let frame = player.next_frame(&mut graphics).unwrap().unwrap();
+        assert_eq!(frame.area, Rectangle::new(Point::zero(), Size::new(8, 10)));
+
+        for y in 0..10 {
+            for x in 0..8 {
+                let point = Point::new(x as i32, y as i32);
+                let expected = color(palette_index(x, y));
+                assert_eq!(graphics.get_item(point), Some(expected), "pixel {x}, {y}");
+            }
+        }
+
+        assert_eq!(player.next_frame(&mut graphics), Ok(None));
+    }
+
+    #[test]
+    fn header() {
+        let gif = Gif::new(ANIMATION).unwrap();
+
+        assert_eq!(gif.size(), Size::new(6, 4));
+        assert_eq!(gif.frame_count(), 4);
+        assert_eq!(gif.loop_count(), Some(1));
+    }
+
+    #[test]
+    fn disposal_transparency_and_looping() {
+        let mut player =
+            GifPlayer::<8, 5>::new(Gif::new(ANIMATION).unwrap()).with_position(Point::new(1, 1));
+        let mut graphics = UnicornGraphics::new();
+        graphics.fill(color(15));
+
+        for _ in 0..2 {
+            let frame = player.next_frame(&mut graphics).unwrap().unwrap();
+            assert_eq!(frame.delay_ms, 100);
+            assert_eq!(
+                frame.area,
+                Rectangle::new(Point::new(1, 1), Size::new(6, 4))
+            );
+            assert_graphics(
+                &graphics,
+                ["ffffffff", "f111111f", "f111111f", "f111111f", "f111111f"],
+            );
+
+            // the first pixel is transparent, showing the frame below
+            let frame = player.next_frame(&mut graphics).unwrap().unwrap();
+            assert_eq!(frame.delay_ms, 50);
+            assert_eq!(
+                frame.area,
+                Rectangle::new(Point::new(2, 2), Size::new(2, 2))
+            );
+            assert_graphics(
+                &graphics,
+                ["ffffffff", "f111111f", "f112111f", "f122111f", "f111111f"],
+            );
+
+            // the last frame is restored to what was under it
+            let frame = player.next_frame(&mut graphics).unwrap().unwrap();
+            assert_eq!(frame.delay_ms, 0);
+            assert_graphics(
+                &graphics,
+                ["ffffffff", "f111331f", "f111331f", "f111111f", "f111111f"],
+            );
+
+            // the last frame is cleared to the background
+            let frame = player.next_frame(&mut graphics).unwrap().unwrap();
+            assert_eq!(frame.delay_ms, 200);
+            assert_graphics(
+                &graphics,
+                ["ffffffff", "f111..1f", "f111..1f", "f111111f", "f411111f"],
+            );
+        }
+
+        assert_eq!(player.next_frame(&mut graphics), Ok(None));
+
+        player.reset();
+        assert!(player.next_frame(&mut graphics).unwrap().is_some());
+    }
+
+    #[test]
+    fn invalid_data() {
+        assert_eq!(Gif::new(b"GIF90a").err(), Some(Error::InvalidSignature));
+        assert_eq!(Gif::new(b"GIF8").err(), Some(Error::Truncated));
+        assert_eq!(Gif::new(&ANIMATION[..100]).err(), Some(Error::Truncated));
+    }
+}
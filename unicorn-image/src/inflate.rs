@@ -0,0 +1,314 @@
+//! Decompression of zlib streams, as used by PNG.
+//!
+//! The whole output is kept in the buffer it is decompressed into, so back references
+//! are copied from the buffer itself, without needing a separate 32KB window.
+
+use crate::Error;
+
+/// Longest Huffman code in bits.
+const MAX_BITS: usize = 15;
+
+/// Number of literal and length codes.
+const LITERAL_CODES: usize = 288;
+
+/// Number of distance codes.
+const DISTANCE_CODES: usize = 30;
+
+/// Code marking the end of a block.
+const END_OF_BLOCK: u16 = 256;
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+/// Order the code length code lengths are stored in dynamic blocks.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Reads bits from a stream of bytes, lowest bit first.
+struct BitReader<I> {
+    bytes: I,
+    bits: u32,
+    count: u32,
+}
+
+impl<I: Iterator<Item = u8>> BitReader<I> {
+    fn new(bytes: I) -> Self {
+        Self {
+            bytes,
+            bits: 0,
+            count: 0,
+        }
+    }
+
+    /// Read the given number of bits, up to 16.
+    fn bits(&mut self, count: u32) -> Result<u32, Error> {
+        while self.count < count {
+            let byte = self.bytes.next().ok_or(Error::Truncated)?;
+            self.bits |= (byte as u32) << self.count;
+            self.count += 8;
+        }
+
+        let value = self.bits & ((1 << count) - 1);
+        self.bits >>= count;
+        self.count -= count;
+        Ok(value)
+    }
+
+    /// Read a whole byte, ignoring any bits left of the current byte.
+    fn byte(&mut self) -> Result<u8, Error> {
+        if self.count >= 8 {
+            return Ok(self.bits(8)? as u8);
+        }
+        self.bytes.next().ok_or(Error::Truncated)
+    }
+
+    /// Skip to the start of the next byte.
+    fn align(&mut self) {
+        let partial = self.count % 8;
+        self.bits >>= partial;
+        self.count -= partial;
+    }
+}
+
+/// A canonical Huffman code, stored as the number of codes of each length
+/// and the symbols ordered by their code.
+struct Huffman<const N: usize> {
+    counts: [u16; MAX_BITS + 1],
+    symbols: [u16; N],
+}
+
+impl<const N: usize> Huffman<N> {
+    /// Build the code from the length of the code of each symbol, where 0 is unused.
+    fn new(lengths: &[u8]) -> Result<Self, Error> {
+        let mut huffman = Self {
+            counts: [0; MAX_BITS + 1],
+            symbols: [0; N],
+        };
+
+        for &length in lengths {
+            huffman.counts[length as usize] += 1;
+        }
+        huffman.counts[0] = 0;
+
+        // check the code is not over subscribed, incomplete codes are allowed
+        let mut left: i32 = 1;
+        for count in &huffman.counts[1..] {
+            left = (left << 1) - *count as i32;
+            if left < 0 {
+                return Err(Error::Corrupt);
+            }
+        }
+
+        let mut offsets = [0; MAX_BITS + 1];
+        for length in 1..MAX_BITS {
+            offsets[length + 1] = offsets[length] + huffman.counts[length];
+        }
+
+        for (symbol, &length) in lengths.iter().enumerate() {
+            if length != 0 {
+                let offset = &mut offsets[length as usize];
+                huffman.symbols[*offset as usize] = symbol as u16;
+                *offset += 1;
+            }
+        }
+
+        Ok(huffman)
+    }
+
+    /// Decode the next symbol from the reader.
+    fn decode<I: Iterator<Item = u8>>(&self, reader: &mut BitReader<I>) -> Result<u16, Error> {
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: i32 = 0;
+
+        for count in &self.counts[1..] {
+            code |= reader.bits(1)? as i32;
+            let count = *count as i32;
+            if code - count < first {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+
+        Err(Error::Corrupt)
+    }
+}
+
+/// Decompress the zlib stream into the buffer, returning the number of bytes written.
+pub(crate) fn inflate<I: Iterator<Item = u8>>(bytes: I, out: &mut [u8]) -> Result<usize, Error> {
+    let mut reader = BitReader::new(bytes);
+
+    let method = reader.byte()?;
+    let flags = reader.byte()?;
+    if method & 0x0f != 8 || !((method as u16) << 8 | flags as u16).is_multiple_of(31) {
+        return Err(Error::Corrupt);
+    }
+    if flags & 0x20 != 0 {
+        // preset dictionaries are never used by PNG
+        return Err(Error::Unsupported);
+    }
+
+    let mut written = 0;
+    loop {
+        let last = reader.bits(1)? == 1;
+
+        match reader.bits(2)? {
+            0 => written = stored(&mut reader, out, written)?,
+            1 => {
+                let (literals, distances) = fixed_codes()?;
+                written = compressed(&mut reader, out, written, &literals, &distances)?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_codes(&mut reader)?;
+                written = compressed(&mut reader, out, written, &literals, &distances)?;
+            }
+            _ => return Err(Error::Corrupt),
+        }
+
+        if last {
+            return Ok(written);
+        }
+    }
+}
+
+/// Copy an uncompressed block.
+fn stored<I: Iterator<Item = u8>>(
+    reader: &mut BitReader<I>,
+    out: &mut [u8],
+    mut written: usize,
+) -> Result<usize, Error> {
+    reader.align();
+
+    let length = reader.bits(16)?;
+    let inverse = reader.bits(16)?;
+    if length != !inverse & 0xffff {
+        return Err(Error::Corrupt);
+    }
+
+    for _ in 0..length {
+        *out.get_mut(written).ok_or(Error::Corrupt)? = reader.byte()?;
+        written += 1;
+    }
+
+    Ok(written)
+}
+
+/// Build the fixed codes used by compressed blocks without their own codes.
+fn fixed_codes() -> Result<(Huffman<LITERAL_CODES>, Huffman<DISTANCE_CODES>), Error> {
+    let mut lengths = [0; LITERAL_CODES];
+    lengths[..144].fill(8);
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+
+    Ok((Huffman::new(&lengths)?, Huffman::new(&[5; DISTANCE_CODES])?))
+}
+
+/// Read the codes stored at the start of a dynamic block.
+fn dynamic_codes<I: Iterator<Item = u8>>(
+    reader: &mut BitReader<I>,
+) -> Result<(Huffman<LITERAL_CODES>, Huffman<DISTANCE_CODES>), Error> {
+    let literal_count = reader.bits(5)? as usize + 257;
+    let distance_count = reader.bits(5)? as usize + 1;
+    let code_length_count = reader.bits(4)? as usize + 4;
+
+    if literal_count > 286 || distance_count > DISTANCE_CODES {
+        return Err(Error::Corrupt);
+    }
+
+    let mut code_lengths = [0; 19];
+    for &symbol in &CODE_LENGTH_ORDER[..code_length_count] {
+        code_lengths[symbol] = reader.bits(3)? as u8;
+    }
+    let code_length_code = Huffman::<19>::new(&code_lengths)?;
+
+    // the literal and distance lengths are stored as one sequence, so repeats can cross between them
+    let mut lengths = [0; 286 + DISTANCE_CODES];
+    let total = literal_count + distance_count;
+    let mut index = 0;
+
+    while index < total {
+        let symbol = code_length_code.decode(reader)?;
+
+        let (length, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => {
+                let previous = *lengths.get(index.wrapping_sub(1)).ok_or(Error::Corrupt)?;
+                (previous, 3 + reader.bits(2)? as usize)
+            }
+            17 => (0, 3 + reader.bits(3)? as usize),
+            _ => (0, 11 + reader.bits(7)? as usize),
+        };
+
+        if index + repeat > total {
+            return Err(Error::Corrupt);
+        }
+        lengths[index..index + repeat].fill(length);
+        index += repeat;
+    }
+
+    if lengths[END_OF_BLOCK as usize] == 0 {
+        return Err(Error::Corrupt);
+    }
+
+    Ok((
+        Huffman::new(&lengths[..literal_count])?,
+        Huffman::new(&lengths[literal_count..total])?,
+    ))
+}
+
+/// Decode a Huffman compressed block.
+fn compressed<I: Iterator<Item = u8>>(
+    reader: &mut BitReader<I>,
+    out: &mut [u8],
+    mut written: usize,
+    literals: &Huffman<LITERAL_CODES>,
+    distances: &Huffman<DISTANCE_CODES>,
+) -> Result<usize, Error> {
+    loop {
+        let symbol = literals.decode(reader)?;
+
+        if symbol < END_OF_BLOCK {
+            *out.get_mut(written).ok_or(Error::Corrupt)? = symbol as u8;
+            written += 1;
+            continue;
+        }
+        if symbol == END_OF_BLOCK {
+            return Ok(written);
+        }
+
+        let code = (symbol - END_OF_BLOCK - 1) as usize;
+        let base = *LENGTH_BASE.get(code).ok_or(Error::Corrupt)? as usize;
+        let length = base + reader.bits(LENGTH_EXTRA[code] as u32)? as usize;
+
+        let code = distances.decode(reader)? as usize;
+        let base = *DISTANCE_BASE.get(code).ok_or(Error::Corrupt)? as usize;
+        let distance = base + reader.bits(DISTANCE_EXTRA[code] as u32)? as usize;
+
+        if distance > written || written + length > out.len() {
+            return Err(Error::Corrupt);
+        }
+
+        // copied a byte at a time, as the source can overlap what is being written
+        for _ in 0..length {
+            out[written] = out[written - distance];
+            written += 1;
+        }
+    }
+}
@@ -0,0 +1,189 @@
+#![no_std]
+
+use core::fmt;
+
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics_core::prelude::{Point, Size};
+use unicorn_graphics::{BlendMode, Rgba, SpriteFrame, UnicornGraphics};
+
+pub mod bmp;
+pub mod gif;
+mod inflate;
+pub mod png;
+pub mod qoi;
+
+pub use bmp::Bmp;
+pub use gif::{Gif, GifFrame, GifPlayer};
+pub use png::{Png, PngImage};
+pub use qoi::Qoi;
+
+/// Errors that can occur when decoding images.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// The data is not an image of the expected format.
+    InvalidSignature,
+
+    /// The data ends before the image does.
+    Truncated,
+
+    /// The image uses a feature of the format that is not supported.
+    Unsupported,
+
+    /// The image data is not valid.
+    Corrupt,
+
+    /// The buffer given to decode into is too small, see [`Png::buffer_len`].
+    BufferTooSmall,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidSignature => write!(f, "invalid signature"),
+            Error::Truncated => write!(f, "image data is truncated"),
+            Error::Unsupported => write!(f, "unsupported image feature"),
+            Error::Corrupt => write!(f, "image data is corrupt"),
+            Error::BufferTooSmall => write!(f, "decode buffer is too small"),
+        }
+    }
+}
+
+/// A decoded image that can be drawn onto graphics or turned into sprite frames.
+pub trait Image {
+    /// Size of the image in pixels.
+    fn size(&self) -> Size;
+
+    /// Call the function with every pixel of the image, row by row from the top left.
+    fn for_each_pixel<F>(&self, f: F) -> Result<(), Error>
+    where
+        F: FnMut(Point, Rgba);
+
+    /// Draw the whole image onto the graphics with its top left at the position,
+    /// blending each pixel by its alpha.
+    fn draw<const W: usize, const H: usize>(
+        &self,
+        graphics: &mut UnicornGraphics<W, H>,
+        position: Point,
+    ) -> Result<(), Error> {
+        let area = Rectangle::new(Point::zero(), self.size());
+        self.draw_cropped(graphics, &area, position)
+    }
+
+    /// Draw the cropped area of the image onto the graphics with the top left of the area at the position,
+    /// blending each pixel by its alpha.
+    fn draw_cropped<const W: usize, const H: usize>(
+        &self,
+        graphics: &mut UnicornGraphics<W, H>,
+        crop: &Rectangle,
+        position: Point,
+    ) -> Result<(), Error> {
+        self.for_each_pixel(|point, color| {
+            if color.a > 0 && crop.contains(point) {
+                let target = position + (point - crop.top_left);
+                graphics.blend_pixel_rgba(target, color, BlendMode::Normal);
+            }
+        })
+    }
+
+    /// Get a sprite frame from the area of the image starting at the origin,
+    /// such as a single frame of a sprite sheet. Pixels beyond the image are transparent.
+    fn to_sprite_frame<const W: usize, const H: usize>(
+        &self,
+        origin: Point,
+    ) -> Result<SpriteFrame<W, H>, Error> {
+        let mut frame = [[Rgba::new(0, 0, 0, 0); W]; H];
+
+        self.for_each_pixel(|point, color| {
+            let offset = point - origin;
+            if let (Ok(x), Ok(y)) = (usize::try_from(offset.x), usize::try_from(offset.y)) {
+                if x < W && y < H {
+                    frame[y][x] = color;
+                }
+            }
+        })?;
+
+        Ok(frame)
+    }
+}
+
+/// Read a little endian u16 at the offset of the data.
+fn read_u16_le(data: &[u8], offset: usize) -> Result<u16, Error> {
+    let bytes = data.get(offset..offset + 2).ok_or(Error::Truncated)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+/// Read a little endian u32 at the offset of the data.
+fn read_u32_le(data: &[u8], offset: usize) -> Result<u32, Error> {
+    let bytes = data.get(offset..offset + 4).ok_or(Error::Truncated)?;
+    Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Read a big endian u32 at the offset of the data.
+fn read_u32_be(data: &[u8], offset: usize) -> Result<u32, Error> {
+    let bytes = data.get(offset..offset + 4).ok_or(Error::Truncated)?;
+    Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Pattern shared by the fixtures, with runs, small and large color changes, and alpha.
+#[cfg(test)]
+pub(crate) mod tests {
+    use embedded_graphics_core::prelude::{Point, Size};
+    use unicorn_graphics::Rgba;
+
+    use super::Image;
+
+    /// Color of the pixel of the pattern, with alpha when the image has an alpha channel.
+    pub(crate) fn pattern(x: u32, y: u32, has_alpha: bool) -> Rgba {
+        let (r, g, b) = if x < 3 {
+            (200, 10, 10)
+        } else if x < 6 {
+            (100 + x, 100 + y, 100)
+        } else {
+            (x * 20 % 256, y * 30 % 256, x * y * 7 % 256)
+        };
+
+        let a = if has_alpha && x >= 6 {
+            (255 + 512 - x * 20 - y * 3) % 256
+        } else {
+            255
+        };
+
+        Rgba::new(r as u8, g as u8, b as u8, a as u8)
+    }
+
+    /// Color of the entry of the palette shared by the indexed fixtures.
+    pub(crate) fn palette_color(index: u32) -> Rgba {
+        Rgba::new(
+            (index * 16) as u8,
+            (255 - index * 16) as u8,
+            (index * 7) as u8,
+            255,
+        )
+    }
+
+    /// Index of the pixel in the indexed fixtures.
+    pub(crate) fn palette_index(x: u32, y: u32) -> u32 {
+        (x * 3 + y) % 16
+    }
+
+    /// Check every pixel of the image, in order, against the expected color.
+    pub(crate) fn assert_pixels<I, F>(image: &I, size: Size, expected: F)
+    where
+        I: Image,
+        F: Fn(u32, u32) -> Rgba,
+    {
+        assert_eq!(image.size(), size);
+
+        let mut count = 0;
+        image
+            .for_each_pixel(|point, color| {
+                let (x, y) = (count % size.width, count / size.width);
+                assert_eq!(point, Point::new(x as i32, y as i32));
+                assert_eq!(color, expected(x, y), "pixel {x}, {y}");
+                count += 1;
+            })
+            .unwrap();
+
+        assert_eq!(count, size.width * size.height);
+    }
+}
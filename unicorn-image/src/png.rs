@@ -0,0 +1,471 @@
+use embedded_graphics_core::prelude::{Point, Size};
+use unicorn_graphics::Rgba;
+
+use crate::{inflate::inflate, read_u32_be, Error, Image};
+
+/// Signature at the start of every PNG.
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// How the color of each pixel is stored.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum ColorType {
+    Grayscale,
+    Rgb,
+    Indexed,
+    GrayscaleAlpha,
+    Rgba,
+}
+
+impl ColorType {
+    /// Number of samples of each pixel.
+    fn channels(&self) -> usize {
+        match self {
+            ColorType::Grayscale | ColorType::Indexed => 1,
+            ColorType::GrayscaleAlpha => 2,
+            ColorType::Rgb => 3,
+            ColorType::Rgba => 4,
+        }
+    }
+}
+
+/// A PNG image, which must be decoded into a buffer before it is drawn.
+///
+/// Supports every color type and bit depth, with transparency from the alpha channel
+/// or a `tRNS` chunk. Interlaced images are not supported, and checksums are not checked.
+#[derive(Copy, Clone, Debug)]
+pub struct Png<'a> {
+    data: &'a [u8],
+    width: u32,
+    height: u32,
+    bit_depth: u8,
+    color_type: ColorType,
+
+    /// Bytes of each row, not including the filter type.
+    row_bytes: usize,
+
+    /// Red, green and blue of each palette entry, for indexed images.
+    palette: &'a [u8],
+
+    /// Alpha of each palette entry, or the transparent color for other color types.
+    transparency: &'a [u8],
+}
+
+impl<'a> Png<'a> {
+    /// Read the header and palette of the PNG data, such as from `include_bytes!`.
+    pub fn new(data: &'a [u8]) -> Result<Self, Error> {
+        if data.get(0..8) != Some(&SIGNATURE) {
+            return Err(Error::InvalidSignature);
+        }
+
+        let header = match chunks(data).next() {
+            Some((b"IHDR", header)) if header.len() >= 13 => header,
+            Some(_) => return Err(Error::Corrupt),
+            None => return Err(Error::Truncated),
+        };
+
+        let width = read_u32_be(header, 0)?;
+        let height = read_u32_be(header, 4)?;
+        let bit_depth = header[8];
+        let color_type = match header[9] {
+            0 => ColorType::Grayscale,
+            2 => ColorType::Rgb,
+            3 => ColorType::Indexed,
+            4 => ColorType::GrayscaleAlpha,
+            6 => ColorType::Rgba,
+            _ => return Err(Error::Corrupt),
+        };
+
+        let valid_depth = match color_type {
+            ColorType::Grayscale => matches!(bit_depth, 1 | 2 | 4 | 8 | 16),
+            ColorType::Indexed => matches!(bit_depth, 1 | 2 | 4 | 8),
+            _ => matches!(bit_depth, 8 | 16),
+        };
+        if width == 0 || height == 0 || !valid_depth {
+            return Err(Error::Corrupt);
+        }
+
+        // the decode buffer must fit in a usize, which is only 32 bits on the pico
+        let row_bytes = (width as usize)
+            .checked_mul(color_type.channels() * bit_depth as usize)
+            .map(|bits| bits.div_ceil(8))
+            .filter(|row_bytes| (row_bytes + 1).checked_mul(height as usize).is_some())
+            .ok_or(Error::Unsupported)?;
+
+        if header[10] != 0 || header[11] != 0 || header[12] != 0 {
+            // only deflate compression, the standard filters and no interlacing
+            return Err(Error::Unsupported);
+        }
+
+        let mut png = Self {
+            data,
+            width,
+            height,
+            bit_depth,
+            color_type,
+            row_bytes,
+            palette: &[],
+            transparency: &[],
+        };
+
+        for (kind, chunk) in chunks(data) {
+            match kind {
+                b"PLTE" => png.palette = chunk,
+                b"tRNS" => png.transparency = chunk,
+                b"IDAT" | b"IEND" => break,
+                _ => {}
+            }
+        }
+
+        if color_type == ColorType::Indexed && png.palette.is_empty() {
+            return Err(Error::Corrupt);
+        }
+
+        Ok(png)
+    }
+
+    /// Size of the image in pixels.
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+
+    /// Bytes of each row, not including the filter type.
+    fn row_bytes(&self) -> usize {
+        self.row_bytes
+    }
+
+    /// Number of bytes of the buffer needed by [`Self::decode`].
+    pub fn buffer_len(&self) -> usize {
+        (1 + self.row_bytes()) * self.height as usize
+    }
+
+    /// Decode the pixels into the buffer, which must be at least [`Self::buffer_len`] bytes.
+    pub fn decode<'b>(&self, buffer: &'b mut [u8]) -> Result<PngImage<'a, 'b>, Error> {
+        let len = self.buffer_len();
+        let buffer = buffer.get_mut(..len).ok_or(Error::BufferTooSmall)?;
+
+        let compressed = chunks(self.data)
+            .filter(|(kind, _)| kind == b"IDAT")
+            .flat_map(|(_, chunk)| chunk.iter().copied());
+
+        if inflate(compressed, buffer)? != len {
+            return Err(Error::Truncated);
+        }
+
+        self.unfilter(buffer)?;
+
+        Ok(PngImage {
+            png: *self,
+            pixels: buffer,
+        })
+    }
+
+    /// Check if the samples match the transparent color of a grayscale or rgb image.
+    fn transparent(&self, samples: &[u16]) -> bool {
+        if self.transparency.len() < samples.len() * 2 {
+            return false;
+        }
+
+        samples.iter().enumerate().all(|(index, sample)| {
+            let key = &self.transparency[index * 2..index * 2 + 2];
+            u16::from_be_bytes([key[0], key[1]]) == *sample
+        })
+    }
+
+    /// Undo the filter of each row in place, leaving the filter type at the start of each row.
+    fn unfilter(&self, buffer: &mut [u8]) -> Result<(), Error> {
+        let stride = 1 + self.row_bytes();
+        // bytes per complete pixel, or 1 when pixels are smaller than a byte
+        let pixel_bytes = (self.color_type.channels() * self.bit_depth as usize).div_ceil(8);
+
+        for row in 0..self.height as usize {
+            let (previous, current) = buffer.split_at_mut(row * stride);
+            let previous = match row {
+                0 => None,
+                _ => Some(&previous[(row - 1) * stride + 1..]),
+            };
+            let (filter, current) = current[..stride].split_first_mut().ok_or(Error::Corrupt)?;
+
+            for x in 0..current.len() {
+                let left = if x >= pixel_bytes {
+                    current[x - pixel_bytes]
+                } else {
+                    0
+                };
+                let above = previous.map_or(0, |previous| previous[x]);
+                let above_left = match previous {
+                    Some(previous) if x >= pixel_bytes => previous[x - pixel_bytes],
+                    _ => 0,
+                };
+
+                let prediction = match filter {
+                    0 => 0,
+                    1 => left,
+                    2 => above,
+                    3 => ((left as u16 + above as u16) / 2) as u8,
+                    4 => paeth(left, above, above_left),
+                    _ => return Err(Error::Corrupt),
+                };
+                current[x] = current[x].wrapping_add(prediction);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A PNG decoded into a buffer, ready to be drawn.
+#[derive(Debug)]
+pub struct PngImage<'a, 'b> {
+    png: Png<'a>,
+    pixels: &'b [u8],
+}
+
+impl PngImage<'_, '_> {
+    /// Get the color of the pixel, providing it is within the image.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Rgba> {
+        if x >= self.png.width || y >= self.png.height {
+            return None;
+        }
+
+        let png = &self.png;
+        let stride = 1 + png.row_bytes();
+        let start = y as usize * stride + 1;
+        let row = &self.pixels[start..start + stride - 1];
+
+        let channels = png.color_type.channels();
+        let sample = |channel: usize| sample(row, x as usize * channels + channel, png.bit_depth);
+        let scale = |value: u16| scale(value, png.bit_depth);
+
+        let color = match png.color_type {
+            ColorType::Grayscale => {
+                let gray = sample(0);
+                let alpha = if png.transparent(&[gray]) { 0 } else { u8::MAX };
+                let gray = scale(gray);
+                Rgba::new(gray, gray, gray, alpha)
+            }
+            ColorType::Rgb => {
+                let rgb = [sample(0), sample(1), sample(2)];
+                let alpha = if png.transparent(&rgb) { 0 } else { u8::MAX };
+                Rgba::new(scale(rgb[0]), scale(rgb[1]), scale(rgb[2]), alpha)
+            }
+            ColorType::Indexed => {
+                let index = sample(0) as usize;
+                let alpha = png.transparency.get(index).copied().unwrap_or(u8::MAX);
+                match png.palette.get(index * 3..index * 3 + 3) {
+                    Some(entry) => Rgba::new(entry[0], entry[1], entry[2], alpha),
+                    None => Rgba::new(0, 0, 0, alpha),
+                }
+            }
+            ColorType::GrayscaleAlpha => {
+                let gray = scale(sample(0));
+                Rgba::new(gray, gray, gray, scale(sample(1)))
+            }
+            ColorType::Rgba => Rgba::new(
+                scale(sample(0)),
+                scale(sample(1)),
+                scale(sample(2)),
+                scale(sample(3)),
+            ),
+        };
+
+        Some(color)
+    }
+}
+
+impl Image for PngImage<'_, '_> {
+    fn size(&self) -> Size {
+        self.png.size()
+    }
+
+    fn for_each_pixel<F>(&self, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(Point, Rgba),
+    {
+        for y in 0..self.png.height {
+            for x in 0..self.png.width {
+                if let Some(color) = self.pixel(x, y) {
+                    f(Point::new(x as i32, y as i32), color);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Iterate over the type and data of each chunk after the signature,
+/// stopping at the end of the data.
+fn chunks(data: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+    let mut position = SIGNATURE.len();
+
+    core::iter::from_fn(move || {
+        let length = read_u32_be(data, position).ok()? as usize;
+        let kind = data.get(position + 4..position + 8)?;
+        let chunk = data.get(position + 8..(position + 8).checked_add(length)?)?;
+
+        // skip the checksum
+        position += 12 + length;
+        Some((kind, chunk))
+    })
+}
+
+/// Get the sample at the index of the row, where samples are packed with the first in the highest bits.
+fn sample(row: &[u8], index: usize, bit_depth: u8) -> u16 {
+    match bit_depth {
+        16 => u16::from_be_bytes([row[index * 2], row[index * 2 + 1]]),
+        8 => row[index] as u16,
+        _ => {
+            let bits = bit_depth as usize;
+            let bit = index * bits;
+            let mask = (1 << bits) - 1;
+            ((row[bit / 8] >> (8 - bits - bit % 8)) & mask) as u16
+        }
+    }
+}
+
+/// Scale the sample of the bit depth to 8 bits.
+fn scale(sample: u16, bit_depth: u8) -> u8 {
+    match bit_depth {
+        16 => (sample >> 8) as u8,
+        depth => (sample as u32 * 255 / ((1 << depth) - 1)) as u8,
+    }
+}
+
+/// Predict a byte from the bytes to the left, above and above left, whichever is closest to their gradient.
+fn paeth(left: u8, above: u8, above_left: u8) -> u8 {
+    let estimate = left as i16 + above as i16 - above_left as i16;
+    let to_left = (estimate - left as i16).abs();
+    let to_above = (estimate - above as i16).abs();
+    let to_above_left = (estimate - above_left as i16).abs();
+
+    if to_left <= to_above && to_left <= to_above_left {
+        left
+    } else if to_above <= to_above_left {
+        above
+    } else {
+        above_left
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::prelude::Size;
+    use unicorn_graphics::Rgba;
+
+    use super::*;
+    use crate::tests::{assert_pixels, palette_color, palette_index, pattern};
+
+    /// Decode the PNG and check every pixel against the expected color.
+    fn assert_png<F>(data: &[u8], size: Size, expected: F)
+    where
+        F: Fn(u32, u32) -> Rgba,
+    {
+        let png = Png::new(data).unwrap();
+        let mut buffer = [0; 4096];
+        let image = png.decode(&mut buffer[..png.buffer_len()]).unwrap();
+
+        assert_pixels(&image, size, expected);
+    }
+
+    /// PNG data with only a header, which is enough for [`Png::new`].
+    fn header_only(width: u32, height: u32, bit_depth: u8, color_type: u8) -> [u8; 33] {
+        let mut data = [0; 33];
+        data[..8].copy_from_slice(&SIGNATURE);
+        data[8..12].copy_from_slice(&13_u32.to_be_bytes());
+        data[12..16].copy_from_slice(b"IHDR");
+        data[16..20].copy_from_slice(&width.to_be_bytes());
+        data[20..24].copy_from_slice(&height.to_be_bytes());
+        data[24] = bit_depth;
+        data[25] = color_type;
+        data
+    }
+
+    #[test]
+    fn rgb_stored_blocks() {
+        let data = include_bytes!("../fixtures/rgb_stored.png");
+        assert_png(data, Size::new(9, 7), |x, y| pattern(x, y, false));
+    }
+
+    #[test]
+    fn rgba_fixed_huffman_with_every_filter() {
+        let data = include_bytes!("../fixtures/rgba_fixed.png");
+        assert_png(data, Size::new(9, 7), |x, y| pattern(x, y, true));
+    }
+
+    #[test]
+    fn rgba_dynamic_huffman_with_every_filter() {
+        let data = include_bytes!("../fixtures/rgba_dynamic.png");
+        assert_png(data, Size::new(32, 24), |x, y| pattern(x, y, true));
+    }
+
+    #[test]
+    fn grayscale_2_bit() {
+        let data = include_bytes!("../fixtures/gray2.png");
+        assert_png(data, Size::new(9, 7), |x, y| {
+            let gray = ((x + y) % 4 * 85) as u8;
+            Rgba::new(gray, gray, gray, 255)
+        });
+    }
+
+    #[test]
+    fn indexed_4_bit_with_transparency() {
+        let data = include_bytes!("../fixtures/indexed4_trns.png");
+        assert_png(data, Size::new(9, 7), |x, y| {
+            let index = palette_index(x, y);
+            let alpha = [0, 64, 128, 192]
+                .get(index as usize)
+                .copied()
+                .unwrap_or(255);
+            Rgba {
+                a: alpha,
+                ..palette_color(index)
+            }
+        });
+    }
+
+    #[test]
+    fn grayscale_alpha_16_bit() {
+        let data = include_bytes!("../fixtures/gray_alpha16.png");
+        assert_png(data, Size::new(5, 3), |x, y| {
+            let gray = ((x * 10000 + y * 300) >> 8) as u8;
+            Rgba::new(gray, gray, gray, ((65535 - x * 5000) >> 8) as u8)
+        });
+    }
+
+    #[test]
+    fn buffer_too_small() {
+        let png = Png::new(include_bytes!("../fixtures/rgb_stored.png")).unwrap();
+        let mut buffer = [0; 4096];
+
+        assert_eq!(png.buffer_len(), 7 * (1 + 9 * 3));
+        let result = png.decode(&mut buffer[..png.buffer_len() - 1]);
+        assert_eq!(result.err(), Some(Error::BufferTooSmall));
+    }
+
+    #[test]
+    fn truncated_data() {
+        let data = include_bytes!("../fixtures/rgba_dynamic.png");
+        let png = Png::new(&data[..data.len() - 200]).unwrap();
+        let mut buffer = [0; 4096];
+
+        assert!(png.decode(&mut buffer[..png.buffer_len()]).is_err());
+    }
+
+    #[test]
+    fn huge_header_is_unsupported() {
+        let data = header_only(u32::MAX, u32::MAX, 16, 6);
+        assert_eq!(Png::new(&data).err(), Some(Error::Unsupported));
+    }
+
+    #[test]
+    fn invalid_header() {
+        assert_eq!(Png::new(b"GIF89a").err(), Some(Error::InvalidSignature));
+        assert_eq!(
+            Png::new(&header_only(0, 1, 8, 6)).err(),
+            Some(Error::Corrupt)
+        );
+        assert_eq!(
+            Png::new(&header_only(1, 1, 4, 2)).err(),
+            Some(Error::Corrupt)
+        );
+    }
+}
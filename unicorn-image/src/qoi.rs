@@ -0,0 +1,167 @@
+use embedded_graphics_core::prelude::{Point, Size};
+use unicorn_graphics::Rgba;
+
+use crate::{read_u32_be, Error, Image};
+
+/// Size of the header before the chunks.
+const HEADER_SIZE: usize = 14;
+
+/// Marker at the end of the chunks.
+const END_MARKER: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 1];
+
+const OP_INDEX: u8 = 0b00;
+const OP_DIFF: u8 = 0b01;
+const OP_LUMA: u8 = 0b10;
+const OP_RGB: u8 = 0b1111_1110;
+const OP_RGBA: u8 = 0b1111_1111;
+
+/// A QOI image, decoded from its data each time it is drawn.
+#[derive(Copy, Clone, Debug)]
+pub struct Qoi<'a> {
+    data: &'a [u8],
+    width: u32,
+    height: u32,
+}
+
+impl<'a> Qoi<'a> {
+    /// Read the header of the QOI data, such as from `include_bytes!`.
+    pub fn new(data: &'a [u8]) -> Result<Self, Error> {
+        if data.get(0..4) != Some(b"qoif") {
+            return Err(Error::InvalidSignature);
+        }
+
+        let width = read_u32_be(data, 4)?;
+        let height = read_u32_be(data, 8)?;
+        let channels = *data.get(12).ok_or(Error::Truncated)?;
+
+        if width == 0 || height == 0 || !(3..=4).contains(&channels) {
+            return Err(Error::Corrupt);
+        }
+
+        Ok(Self {
+            data,
+            width,
+            height,
+        })
+    }
+}
+
+impl Image for Qoi<'_> {
+    fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+
+    fn for_each_pixel<F>(&self, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(Point, Rgba),
+    {
+        let chunks = self.data.get(HEADER_SIZE..).unwrap_or(&[]);
+        let mut position = 0;
+        let mut next = || {
+            let byte = chunks.get(position).copied().ok_or(Error::Truncated);
+            position += 1;
+            byte
+        };
+
+        let mut seen = [Rgba::new(0, 0, 0, 0); 64];
+        let mut pixel = Rgba::new(0, 0, 0, u8::MAX);
+        let mut run = 0;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if run > 0 {
+                    run -= 1;
+                } else {
+                    let op = next()?;
+
+                    match op {
+                        OP_RGB => {
+                            pixel.r = next()?;
+                            pixel.g = next()?;
+                            pixel.b = next()?;
+                        }
+                        OP_RGBA => {
+                            pixel.r = next()?;
+                            pixel.g = next()?;
+                            pixel.b = next()?;
+                            pixel.a = next()?;
+                        }
+                        _ => match op >> 6 {
+                            OP_INDEX => pixel = seen[op as usize],
+                            OP_DIFF => {
+                                pixel.r = pixel.r.wrapping_add((op >> 4) & 0x03).wrapping_sub(2);
+                                pixel.g = pixel.g.wrapping_add((op >> 2) & 0x03).wrapping_sub(2);
+                                pixel.b = pixel.b.wrapping_add(op & 0x03).wrapping_sub(2);
+                            }
+                            OP_LUMA => {
+                                let green = (op & 0x3f).wrapping_sub(32);
+                                let red_blue = next()?;
+                                pixel.r = pixel
+                                    .r
+                                    .wrapping_add(green)
+                                    .wrapping_add(red_blue >> 4)
+                                    .wrapping_sub(8);
+                                pixel.g = pixel.g.wrapping_add(green);
+                                pixel.b = pixel
+                                    .b
+                                    .wrapping_add(green)
+                                    .wrapping_add(red_blue & 0x0f)
+                                    .wrapping_sub(8);
+                            }
+                            // the run includes this pixel
+                            _ => run = op & 0x3f,
+                        },
+                    }
+
+                    seen[hash(pixel)] = pixel;
+                }
+
+                f(Point::new(x as i32, y as i32), pixel);
+            }
+        }
+
+        if chunks.get(position..position + END_MARKER.len()) != Some(&END_MARKER) {
+            return Err(Error::Corrupt);
+        }
+
+        Ok(())
+    }
+}
+
+/// Position of the color in the array of previously seen colors.
+fn hash(color: Rgba) -> usize {
+    let sum =
+        color.r as usize * 3 + color.g as usize * 5 + color.b as usize * 7 + color.a as usize * 11;
+    sum % 64
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::prelude::Size;
+
+    use super::*;
+    use crate::tests::{assert_pixels, pattern};
+
+    #[test]
+    fn rgb() {
+        let qoi = Qoi::new(include_bytes!("../fixtures/rgb.qoi")).unwrap();
+        assert_pixels(&qoi, Size::new(9, 7), |x, y| pattern(x, y, false));
+    }
+
+    #[test]
+    fn rgba() {
+        let qoi = Qoi::new(include_bytes!("../fixtures/rgba.qoi")).unwrap();
+        assert_pixels(&qoi, Size::new(9, 7), |x, y| pattern(x, y, true));
+    }
+
+    #[test]
+    fn invalid_data() {
+        let data = include_bytes!("../fixtures/rgba.qoi");
+
+        assert_eq!(Qoi::new(b"qoi").err(), Some(Error::InvalidSignature));
+        assert_eq!(Qoi::new(&data[..10]).err(), Some(Error::Truncated));
+
+        let qoi = Qoi::new(&data[..data.len() - 12]).unwrap();
+        assert!(qoi.for_each_pixel(|_, _| {}).is_err());
+    }
+}
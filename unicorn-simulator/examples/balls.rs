@@ -0,0 +1,108 @@
+//! Fire effect from the embassy balls example, running in the simulator.
+//!
+//! Animates in the terminal, or records a GIF when given a path.
+//!
+//! `cargo run --example balls -- balls.gif`
+
+use std::{env, thread, time::Duration};
+
+use embedded_graphics_core::{pixelcolor::Rgb888, prelude::Point};
+
+use unicorn_bitstream::{HEIGHT, WIDTH};
+use unicorn_graphics::UnicornGraphics;
+use unicorn_simulator::{
+    image::{GifRecorder, RenderOptions},
+    terminal::TerminalRenderer,
+    Frame,
+};
+
+const FRAME_COUNT: usize = 200;
+const FRAME_DELAY_MS: u32 = 50;
+const BRIGHTNESS: u8 = 150;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut recorder = match env::args().nth(1) {
+        Some(path) => Some(GifRecorder::new(
+            path,
+            WIDTH,
+            HEIGHT,
+            RenderOptions::default(),
+        )?),
+        None => None,
+    };
+    let mut terminal = TerminalRenderer::new();
+
+    let mut graphics = UnicornGraphics::<WIDTH, HEIGHT>::new();
+    let mut heat = [[0.0_f32; HEIGHT + 2]; WIDTH];
+    let mut seed: u32 = 0x1234_5678;
+
+    for _ in 0..FRAME_COUNT {
+        for y in 0..HEIGHT as i32 {
+            for x in 0..WIDTH as i32 {
+                let coord = Point { x, y };
+
+                let x = x as usize;
+                let y = y as usize;
+                if heat[x][y] > 0.5 {
+                    graphics.set_pixel(coord, Rgb888::new(255, 255, 180));
+                } else if heat[x][y] > 0.4 {
+                    graphics.set_pixel(coord, Rgb888::new(220, 160, 0));
+                } else if heat[x][y] > 0.3 {
+                    graphics.set_pixel(coord, Rgb888::new(180, 50, 0));
+                } else if heat[x][y] > 0.2 {
+                    graphics.set_pixel(coord, Rgb888::new(40, 40, 40));
+                }
+
+                // Update this pixel by averaging the below pixels
+                if x == 0 {
+                    heat[x][y] =
+                        (heat[x][y] + heat[x][y + 2] + heat[x][y + 1] + heat[x + 1][y + 1]) / 4.0;
+                } else if x == WIDTH - 1 {
+                    heat[x][y] =
+                        (heat[x][y] + heat[x][y + 2] + heat[x][y + 1] + heat[x - 1][y + 1]) / 4.0;
+                } else {
+                    heat[x][y] = (heat[x][y]
+                        + heat[x][y + 2]
+                        + heat[x][y + 1]
+                        + heat[x - 1][y + 1]
+                        + heat[x + 1][y + 1])
+                        / 5.0;
+                }
+
+                heat[x][y] -= 0.01;
+                heat[x][y] = heat[x][y].max(0.0);
+            }
+        }
+
+        let frame = Frame::from_graphics_encoded(&graphics, BRIGHTNESS);
+        match recorder.as_mut() {
+            Some(recorder) => recorder.add_frame(&frame, FRAME_DELAY_MS)?,
+            None => {
+                terminal.draw(&frame)?;
+                thread::sleep(Duration::from_millis(FRAME_DELAY_MS as u64));
+            }
+        }
+
+        // clear the bottom row and then add a new fire seed to it
+        for column in heat.iter_mut() {
+            column[HEIGHT] = 0.0;
+        }
+
+        // add a new random heat source
+        for _ in 0..5 {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+
+            let px: usize = seed as usize % (WIDTH - 2) + 1;
+            heat[px][HEIGHT] = 1.0;
+            heat[px + 1][HEIGHT] = 1.0;
+            heat[px - 1][HEIGHT] = 1.0;
+            heat[px][HEIGHT + 1] = 1.0;
+            heat[px + 1][HEIGHT + 1] = 1.0;
+            heat[px - 1][HEIGHT + 1] = 1.0;
+        }
+    }
+
+    Ok(())
+}
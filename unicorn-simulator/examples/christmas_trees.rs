@@ -0,0 +1,318 @@
+//! Christmas trees with falling snow from the rp example, running in the simulator.
+//!
+//! Animates in the terminal, or records a GIF when given a path.
+//!
+//! `cargo run --example christmas_trees -- christmas_trees.gif`
+
+use std::{env, thread, time::Duration};
+
+use embedded_graphics_core::{
+    pixelcolor::{Rgb888, RgbColor, WebColors},
+    prelude::Point,
+};
+
+use unicorn_bitstream::{HEIGHT, WIDTH};
+use unicorn_graphics::UnicornGraphics;
+use unicorn_simulator::{
+    image::{GifRecorder, RenderOptions},
+    terminal::TerminalRenderer,
+    Frame,
+};
+
+const FRAME_COUNT: usize = 400;
+const FRAME_DELAY_MS: u32 = 20;
+const BRIGHTNESS: u8 = 255;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut recorder = match env::args().nth(1) {
+        Some(path) => Some(GifRecorder::new(
+            path,
+            WIDTH,
+            HEIGHT,
+            RenderOptions::default(),
+        )?),
+        None => None,
+    };
+    let mut terminal = TerminalRenderer::new();
+
+    let mut graphics = UnicornGraphics::<WIDTH, HEIGHT>::new();
+
+    // simulated microsecond timer, in place of the rp timer
+    let mut ticks: u64 = 0;
+
+    let mut x: i32 = 0;
+    let mut y: i32 = 0;
+
+    // spread the trees evenly across the display, at 4, 15, 26, 37 and 48 on the galactic unicorn
+    let tree_x = |index: i32| 4 + index * (WIDTH as i32 - 9) / 4;
+    let mut tree_1 = Tree::new(tree_x(0));
+    let mut tree_2 = Tree::new(tree_x(1));
+    let mut tree_3 = Tree::new(tree_x(2));
+    let mut tree_4 = Tree::new(tree_x(3));
+    let mut tree_5 = Tree::new(tree_x(4));
+
+    let mut snowflakes = [
+        Snowflake::new(),
+        Snowflake::new(),
+        Snowflake::new(),
+        Snowflake::new(),
+        Snowflake::new(),
+        Snowflake::new(),
+        Snowflake::new(),
+        Snowflake::new(),
+    ];
+
+    let mut snowflake_start = 3;
+
+    for _ in 0..FRAME_COUNT {
+        ticks += FRAME_DELAY_MS as u64 * 1000;
+
+        x += 1;
+        y += 1;
+
+        if y as usize > HEIGHT {
+            y = 0;
+        }
+
+        if x as usize > WIDTH {
+            x = 0;
+        }
+
+        graphics.clear_all();
+
+        snowflake_start += 1;
+
+        for snow in snowflakes.iter_mut() {
+            if !snow.running && snowflake_start > 15 {
+                snowflake_start = 0;
+                snow.start(x);
+            }
+
+            if snow.running {
+                if ticks - snow.last_fell > 300000 {
+                    snow.fall(ticks);
+                }
+                graphics.set_pixel(snow.point(), Rgb888::CSS_SNOW);
+            }
+        }
+
+        draw_tree(&mut tree_1, &mut graphics, ticks);
+        draw_tree_alt(&mut tree_2, &mut graphics, ticks);
+        draw_tree(&mut tree_3, &mut graphics, ticks);
+        draw_tree_alt(&mut tree_4, &mut graphics, ticks);
+        draw_tree(&mut tree_5, &mut graphics, ticks);
+
+        let frame = Frame::from_graphics_encoded(&graphics, BRIGHTNESS);
+        match recorder.as_mut() {
+            Some(recorder) => recorder.add_frame(&frame, FRAME_DELAY_MS)?,
+            None => {
+                terminal.draw(&frame)?;
+                thread::sleep(Duration::from_millis(FRAME_DELAY_MS as u64));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn draw_tree(tree: &mut Tree, gu: &mut UnicornGraphics<WIDTH, HEIGHT>, ticks: u64) {
+    let brown = Rgb888::CSS_SADDLE_BROWN;
+    let green = Rgb888::GREEN;
+    let gold = Rgb888::CSS_GOLD;
+    let red = Rgb888::RED;
+
+    let math = ticks - tree.last_twinkle;
+
+    let twinkle_colour = if math > 500000 { red } else { gold };
+
+    if math > 1000000 {
+        tree.twinkle(ticks);
+    }
+
+    gu.set_pixel(Point::new(tree.x, 10), brown);
+
+    for x in -4..5 {
+        let point = Point::new(tree.x + x, 9);
+        gu.set_pixel(point, green);
+
+        if x == 3 {
+            gu.set_pixel(point, twinkle_colour);
+        }
+    }
+
+    for x in -3..4 {
+        let point = Point::new(tree.x + x, 8);
+        gu.set_pixel(point, green);
+
+        if x == -2 {
+            gu.set_pixel(point, twinkle_colour);
+        }
+    }
+
+    for x in -2..3 {
+        let point = Point::new(tree.x + x, 7);
+        gu.set_pixel(point, green);
+
+        if x == 1 {
+            gu.set_pixel(point, twinkle_colour);
+        }
+    }
+
+    for x in -1..2 {
+        let point = Point::new(tree.x + x, 6);
+        gu.set_pixel(point, green);
+
+        if x == 0 {
+            gu.set_pixel(point, twinkle_colour);
+        }
+    }
+
+    for x in -3..4 {
+        let point = Point::new(tree.x + x, 5);
+        gu.set_pixel(point, green);
+
+        if x == 2 {
+            gu.set_pixel(point, twinkle_colour);
+        }
+    }
+
+    for x in -2..3 {
+        let point = Point::new(tree.x + x, 4);
+        gu.set_pixel(point, green);
+
+        if x == -1 {
+            gu.set_pixel(point, twinkle_colour);
+        }
+    }
+
+    for x in -1..2 {
+        gu.set_pixel(Point::new(tree.x + x, 3), green);
+    }
+
+    gu.set_pixel(Point::new(tree.x, 2), green);
+    gu.set_pixel(Point::new(tree.x, 1), gold);
+}
+
+fn draw_tree_alt(tree: &mut Tree, gu: &mut UnicornGraphics<WIDTH, HEIGHT>, ticks: u64) {
+    let brown = Rgb888::CSS_SADDLE_BROWN;
+    let green = Rgb888::GREEN;
+    let gold = Rgb888::CSS_GOLD;
+    let red = Rgb888::RED;
+
+    let math = ticks - tree.last_twinkle;
+
+    let twinkle_colour = if math > 500000 { red } else { gold };
+
+    if math > 1000000 {
+        tree.twinkle(ticks);
+    }
+
+    gu.set_pixel(Point::new(tree.x, 10), brown);
+    gu.set_pixel(Point::new(tree.x, 9), brown);
+
+    for x in -3..4 {
+        let point = Point::new(tree.x + x, 8);
+        gu.set_pixel(point, green);
+
+        if x == -2 {
+            gu.set_pixel(point, twinkle_colour);
+        }
+    }
+
+    for x in -2..3 {
+        let point = Point::new(tree.x + x, 7);
+        gu.set_pixel(point, green);
+
+        if x == 1 {
+            gu.set_pixel(point, twinkle_colour);
+        }
+    }
+
+    for x in -1..2 {
+        let point = Point::new(tree.x + x, 6);
+        gu.set_pixel(point, green);
+
+        if x == 0 {
+            gu.set_pixel(point, twinkle_colour);
+        }
+    }
+
+    for x in -3..4 {
+        let point = Point::new(tree.x + x, 5);
+        gu.set_pixel(point, green);
+
+        if x == 2 {
+            gu.set_pixel(point, twinkle_colour);
+        }
+    }
+
+    for x in -2..3 {
+        let point = Point::new(tree.x + x, 4);
+        gu.set_pixel(point, green);
+
+        if x == -1 {
+            gu.set_pixel(point, twinkle_colour);
+        }
+    }
+
+    for x in -1..2 {
+        gu.set_pixel(Point::new(tree.x + x, 3), green);
+    }
+
+    gu.set_pixel(Point::new(tree.x, 2), green);
+    gu.set_pixel(Point::new(tree.x, 1), gold);
+    gu.set_pixel(Point::new(tree.x - 2, 10), Rgb888::RED);
+    gu.set_pixel(Point::new(tree.x + 2, 10), Rgb888::RED);
+}
+
+struct Snowflake {
+    pub y: i32,
+    pub x: i32,
+    pub running: bool,
+    pub last_fell: u64,
+}
+
+impl Snowflake {
+    pub fn new() -> Self {
+        Self {
+            y: 0,
+            x: 0,
+            running: false,
+            last_fell: 0,
+        }
+    }
+
+    pub fn start(&mut self, x: i32) {
+        self.x = x;
+        self.y = 0;
+        self.running = true;
+    }
+
+    pub fn fall(&mut self, ticks: u64) {
+        self.y += 1;
+        if self.y > HEIGHT as i32 {
+            self.running = false;
+        }
+
+        self.last_fell = ticks;
+    }
+
+    pub fn point(&self) -> Point {
+        Point::new(self.x, self.y)
+    }
+}
+
+struct Tree {
+    pub x: i32,
+    pub last_twinkle: u64,
+}
+
+impl Tree {
+    pub fn new(x: i32) -> Self {
+        Self { x, last_twinkle: 0 }
+    }
+
+    pub fn twinkle(&mut self, ticks: u64) {
+        self.last_twinkle = ticks;
+    }
+}
@@ -0,0 +1,176 @@
+//! Converts a GIF, or a sequence of PNGs, into the clip format for embedding with `include_bytes!`.
+//!
+//! `cargo run --bin unicorn-clip -- output.clip input.gif`
+//!
+//! `cargo run --bin unicorn-clip -- output.clip frame1.png frame2.png --duration 80`
+
+use std::{env, error::Error, fs, fs::File, io::BufReader, path::Path, process};
+
+use embedded_graphics_core::{pixelcolor::Rgb888, prelude::RgbColor};
+use unicorn_graphics::Clip;
+use unicorn_simulator::{
+    clip::{palette_of, ClipEncoder},
+    Frame,
+};
+
+const USAGE: &str =
+    "usage: unicorn-clip <output> <input.gif | input.png...> [--duration <ms>] [--once] [--rgb]
+
+  --duration <ms>  time to show each PNG, or GIF frames without a delay, defaults to 100
+  --once           play the clip once rather than looping
+  --rgb            store colors as they are, rather than in a palette when there are few enough";
+
+/// Time to show frames for when none is given.
+const DEFAULT_DURATION_MS: u16 = 100;
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("error: {err}");
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let mut paths = Vec::new();
+    let mut duration_ms = DEFAULT_DURATION_MS;
+    let mut looping = true;
+    let mut rgb = false;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--duration" => {
+                let value = args.next().ok_or("--duration needs a value")?;
+                duration_ms = value.parse()?;
+            }
+            "--once" => looping = false,
+            "--rgb" => rgb = true,
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            _ => paths.push(arg),
+        }
+    }
+
+    if paths.len() < 2 {
+        return Err(USAGE.into());
+    }
+    let output = paths.remove(0);
+
+    let mut frames = Vec::new();
+    for path in &paths {
+        let is_gif = Path::new(path)
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("gif"));
+
+        if is_gif {
+            frames.extend(read_gif(path, duration_ms)?);
+        } else {
+            frames.push((read_png(path)?, duration_ms));
+        }
+    }
+
+    let (width, height) = (frames[0].0.width(), frames[0].0.height());
+    let mut encoder = ClipEncoder::new(width.try_into()?, height.try_into()?).with_looping(looping);
+
+    let pixels: Vec<Frame> = frames.iter().map(|(frame, _)| frame.clone()).collect();
+    if let Some(palette) = palette_of(&pixels).filter(|_| !rgb) {
+        encoder = encoder.with_palette(palette)?;
+    }
+
+    for (frame, duration_ms) in &frames {
+        encoder.push_frame(frame, *duration_ms)?;
+    }
+
+    let data = encoder.finish();
+    // check the clip reads back before writing it
+    Clip::new(&data).map_err(|err| format!("encoded clip is not valid: {err}"))?;
+    fs::write(&output, &data)?;
+
+    let raw = frames.len() * width * height * 3;
+    println!(
+        "wrote {} frames of {width}x{height} to {output} in {} bytes, from {raw} bytes of pixels",
+        frames.len(),
+        data.len(),
+    );
+
+    Ok(())
+}
+
+/// Read each frame of the GIF as it is shown, with its delay,
+/// using the given duration for frames without one.
+fn read_gif(path: &str, duration_ms: u16) -> Result<Vec<(Frame, u16)>, Box<dyn Error>> {
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options.read_info(BufReader::new(File::open(path)?))?;
+
+    let (width, height) = (decoder.width() as usize, decoder.height() as usize);
+    let mut canvas = vec![Rgb888::BLACK; width * height];
+    let mut frames = Vec::new();
+
+    while let Some(gif_frame) = decoder.read_next_frame()? {
+        let left = gif_frame.left as usize;
+        let top = gif_frame.top as usize;
+        let frame_width = gif_frame.width as usize;
+        let area = (0..gif_frame.height as usize)
+            .flat_map(|y| (0..frame_width).map(move |x| (x, y)))
+            .filter(|(x, y)| left + x < width && top + y < height);
+
+        let before = canvas.clone();
+        for (x, y) in area.clone() {
+            let rgba = &gif_frame.buffer[(y * frame_width + x) * 4..][..4];
+            if rgba[3] > 0 {
+                canvas[(top + y) * width + left + x] = Rgb888::new(rgba[0], rgba[1], rgba[2]);
+            }
+        }
+
+        let delay_ms = gif_frame.delay.saturating_mul(10);
+        let delay_ms = if delay_ms == 0 { duration_ms } else { delay_ms };
+        frames.push((Frame::from_pixels(width, height, canvas.clone()), delay_ms));
+
+        for (x, y) in area {
+            let index = (top + y) * width + left + x;
+            match gif_frame.dispose {
+                gif::DisposalMethod::Background => canvas[index] = Rgb888::BLACK,
+                gif::DisposalMethod::Previous => canvas[index] = before[index],
+                _ => {}
+            }
+        }
+    }
+
+    if frames.is_empty() {
+        return Err(format!("{path} has no frames").into());
+    }
+
+    Ok(frames)
+}
+
+/// Read the PNG, showing transparent pixels over black.
+fn read_png(path: &str) -> Result<Frame, Box<dyn Error>> {
+    let mut decoder = png::Decoder::new(BufReader::new(File::open(path)?));
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info()?;
+
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer)?;
+    let (width, height) = (info.width as usize, info.height as usize);
+
+    let channels = info.color_type.samples();
+    let pixels: Vec<Rgb888> = buffer[..info.buffer_size()]
+        .chunks(channels)
+        .map(|pixel| {
+            let (rgb, alpha) = match pixel {
+                [gray] => ([*gray; 3], 255),
+                [gray, alpha] => ([*gray; 3], *alpha),
+                [r, g, b] => ([*r, *g, *b], 255),
+                [r, g, b, alpha, ..] => ([*r, *g, *b], *alpha),
+                _ => ([0; 3], 255),
+            };
+            let over_black = |value: u8| (value as u32 * alpha as u32 / 255) as u8;
+            Rgb888::new(over_black(rgb[0]), over_black(rgb[1]), over_black(rgb[2]))
+        })
+        .collect();
+
+    Ok(Frame::from_pixels(width, height, pixels))
+}
@@ -0,0 +1,397 @@
+use std::{collections::BTreeSet, fmt};
+
+use embedded_graphics_core::{pixelcolor::Rgb888, prelude::RgbColor};
+use unicorn_graphics::clip::{
+    CLIP_FLAG_LOOP, CLIP_FLAG_PALETTE, CLIP_FRAME_KEY, CLIP_MAGIC, CLIP_MAX_OP_PIXELS,
+    CLIP_OP_LITERAL, CLIP_OP_RUN, CLIP_OP_SKIP, CLIP_VERSION,
+};
+
+use crate::Frame;
+
+/// Errors that can occur when encoding a clip.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ClipEncodeError {
+    /// The frame is a different size to the clip.
+    SizeMismatch,
+
+    /// The frame has a color that is not in the palette.
+    NotInPalette(Rgb888),
+
+    /// The palette has more than 256 colors.
+    PaletteTooLarge,
+
+    /// The clip already has as many frames as it can hold.
+    TooManyFrames,
+}
+
+impl fmt::Display for ClipEncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClipEncodeError::SizeMismatch => write!(f, "frame size does not match the clip"),
+            ClipEncodeError::NotInPalette(color) => {
+                write!(f, "color {color:?} is not in the palette")
+            }
+            ClipEncodeError::PaletteTooLarge => write!(f, "palette has more than 256 colors"),
+            ClipEncodeError::TooManyFrames => write!(f, "clip has too many frames"),
+        }
+    }
+}
+
+impl std::error::Error for ClipEncodeError {}
+
+/// Get every color used by the frames, providing there are few enough for a palette.
+pub fn palette_of(frames: &[Frame]) -> Option<Vec<Rgb888>> {
+    let colors: BTreeSet<_> = frames
+        .iter()
+        .flat_map(|frame| frame.pixels())
+        .map(|color| (color.r(), color.g(), color.b()))
+        .collect();
+
+    (colors.len() <= 256).then(|| {
+        colors
+            .into_iter()
+            .map(|(r, g, b)| Rgb888::new(r, g, b))
+            .collect()
+    })
+}
+
+/// Encodes frames into the clip format read by `unicorn_graphics::Clip`.
+///
+/// Each frame is stored as the changes from the frame before, or as a key frame
+/// from black when that is smaller, so still backgrounds cost almost nothing.
+pub struct ClipEncoder {
+    width: usize,
+    height: usize,
+    palette: Option<Vec<Rgb888>>,
+    looping: bool,
+    frame_count: u16,
+    frames: Vec<u8>,
+    previous: Vec<Rgb888>,
+}
+
+impl ClipEncoder {
+    /// Create a new encoder of a looping clip of the given size, storing colors as they are.
+    pub fn new(width: u16, height: u16) -> Self {
+        let pixel_count = width as usize * height as usize;
+
+        Self {
+            width: width as usize,
+            height: height as usize,
+            palette: None,
+            looping: true,
+            frame_count: 0,
+            frames: Vec::new(),
+            previous: vec![Rgb888::BLACK; pixel_count],
+        }
+    }
+
+    /// Store colors as an index of the palette, which can hold up to 256 colors.
+    pub fn with_palette(mut self, palette: Vec<Rgb888>) -> Result<Self, ClipEncodeError> {
+        if palette.is_empty() || palette.len() > 256 {
+            return Err(ClipEncodeError::PaletteTooLarge);
+        }
+        self.palette = Some(palette);
+        Ok(self)
+    }
+
+    /// Set whether the clip goes back to the first frame after the last.
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Number of frames added so far.
+    pub fn frame_count(&self) -> u16 {
+        self.frame_count
+    }
+
+    /// Add a frame shown for the given time.
+    pub fn push_frame(&mut self, frame: &Frame, duration_ms: u16) -> Result<(), ClipEncodeError> {
+        if frame.width() != self.width || frame.height() != self.height {
+            return Err(ClipEncodeError::SizeMismatch);
+        }
+        if self.frame_count == u16::MAX {
+            return Err(ClipEncodeError::TooManyFrames);
+        }
+
+        let pixels = frame.pixels();
+        let black = vec![Rgb888::BLACK; pixels.len()];
+
+        let key = self.ops(pixels, &black)?;
+        let (is_key, ops) = if self.frame_count == 0 {
+            (true, key)
+        } else {
+            let delta = self.ops(pixels, &self.previous)?;
+            if key.len() <= delta.len() {
+                (true, key)
+            } else {
+                (false, delta)
+            }
+        };
+
+        self.frames.extend(duration_ms.to_le_bytes());
+        self.frames.push(if is_key { CLIP_FRAME_KEY } else { 0 });
+        self.frames.extend((ops.len() as u32).to_le_bytes());
+        self.frames.extend(ops);
+
+        self.previous.copy_from_slice(pixels);
+        self.frame_count += 1;
+        Ok(())
+    }
+
+    /// Get the encoded clip.
+    pub fn finish(self) -> Vec<u8> {
+        let mut flags = 0;
+        if self.palette.is_some() {
+            flags |= CLIP_FLAG_PALETTE;
+        }
+        if self.looping {
+            flags |= CLIP_FLAG_LOOP;
+        }
+
+        let mut data = Vec::new();
+        data.extend(CLIP_MAGIC);
+        data.push(CLIP_VERSION);
+        data.push(flags);
+        data.extend((self.width as u16).to_le_bytes());
+        data.extend((self.height as u16).to_le_bytes());
+        data.extend(self.frame_count.to_le_bytes());
+
+        if let Some(palette) = &self.palette {
+            data.extend((palette.len() as u16).to_le_bytes());
+            for color in palette {
+                data.extend([color.r(), color.g(), color.b()]);
+            }
+        }
+
+        data.extend(self.frames);
+        data
+    }
+
+    /// Encode the ops that turn the previous pixels into the new pixels.
+    fn ops(&self, pixels: &[Rgb888], previous: &[Rgb888]) -> Result<Vec<u8>, ClipEncodeError> {
+        let mut ops = Vec::new();
+        let unchanged = |index: usize| pixels[index] == previous[index];
+        let repeats = |index: usize| {
+            pixels[index..]
+                .iter()
+                .take(CLIP_MAX_OP_PIXELS)
+                .take_while(|color| **color == pixels[index])
+                .count()
+        };
+
+        let mut index = 0;
+        while index < pixels.len() {
+            if unchanged(index) {
+                let count = (index..pixels.len())
+                    .take(CLIP_MAX_OP_PIXELS)
+                    .take_while(|index| unchanged(*index))
+                    .count();
+
+                // skips at the end are left out, as the pixels are left as they are anyway
+                if index + count < pixels.len() {
+                    ops.push(op(CLIP_OP_SKIP, count));
+                }
+                index += count;
+                continue;
+            }
+
+            let run = repeats(index);
+            if run >= 2 {
+                ops.push(op(CLIP_OP_RUN, run));
+                self.push_color(&mut ops, pixels[index])?;
+                index += run;
+                continue;
+            }
+
+            // take changed pixels up to the next run or unchanged pixels worth their own op
+            let mut count = 1;
+            while index + count < pixels.len()
+                && count < CLIP_MAX_OP_PIXELS
+                && !unchanged(index + count)
+                && repeats(index + count) < 3
+            {
+                count += 1;
+            }
+
+            ops.push(op(CLIP_OP_LITERAL, count));
+            for color in &pixels[index..index + count] {
+                self.push_color(&mut ops, *color)?;
+            }
+            index += count;
+        }
+
+        Ok(ops)
+    }
+
+    /// Add the color to the ops as a palette index, or as it is without a palette.
+    fn push_color(&self, ops: &mut Vec<u8>, color: Rgb888) -> Result<(), ClipEncodeError> {
+        match &self.palette {
+            Some(palette) => {
+                let index = palette
+                    .iter()
+                    .position(|entry| *entry == color)
+                    .ok_or(ClipEncodeError::NotInPalette(color))?;
+                ops.push(index as u8);
+            }
+            None => ops.extend([color.r(), color.g(), color.b()]),
+        }
+        Ok(())
+    }
+}
+
+/// Get the op byte of the kind changing the number of pixels.
+fn op(kind: u8, count: usize) -> u8 {
+    (kind << 6) | (count - 1) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use unicorn_graphics::{
+        clip::CLIP_FRAME_HEADER_LEN, Clip, ClipFrame, ClipPlayer, UnicornGraphics,
+    };
+
+    use super::*;
+
+    const WIDTH: usize = 20;
+    const HEIGHT: usize = 5;
+
+    /// Frames exercising every op: literals, runs longer than a single op, skips and key frames.
+    fn frames() -> Vec<Frame> {
+        let literal: Vec<_> = (0..WIDTH * HEIGHT)
+            .map(|index| Rgb888::new(index as u8 % 7 * 30, index as u8 % 3 * 80, 0))
+            .collect();
+
+        let mut delta = literal.clone();
+        delta[3] = Rgb888::WHITE;
+        delta[50..54].fill(Rgb888::BLUE);
+
+        let run = vec![Rgb888::RED; WIDTH * HEIGHT];
+
+        let mut sparse = vec![Rgb888::BLACK; WIDTH * HEIGHT];
+        sparse[WIDTH * HEIGHT - 1] = Rgb888::GREEN;
+
+        [literal, delta, run, sparse]
+            .into_iter()
+            .map(|pixels| Frame::from_pixels(WIDTH, HEIGHT, pixels))
+            .collect()
+    }
+
+    fn encode(encoder: ClipEncoder, frames: &[Frame]) -> Vec<u8> {
+        let mut encoder = encoder;
+        for (index, frame) in frames.iter().enumerate() {
+            encoder.push_frame(frame, 10 * index as u16 + 5).unwrap();
+        }
+        encoder.finish()
+    }
+
+    fn assert_plays_back(data: &[u8], frames: &[Frame]) {
+        let clip = Clip::new(data).unwrap();
+        assert_eq!(clip.width() as usize, WIDTH);
+        assert_eq!(clip.height() as usize, HEIGHT);
+        assert_eq!(clip.frame_count() as usize, frames.len());
+
+        let mut player = ClipPlayer::new(clip);
+        let mut graphics = UnicornGraphics::<WIDTH, HEIGHT>::new();
+
+        for (index, frame) in frames.iter().enumerate() {
+            let drawn = player.next_frame(&mut graphics).unwrap();
+            let expected = ClipFrame {
+                index: index as u16,
+                duration_ms: 10 * index as u16 + 5,
+            };
+
+            assert_eq!(drawn, expected);
+            assert_eq!(Frame::from_graphics(&graphics).pixels(), frame.pixels());
+        }
+    }
+
+    #[test]
+    fn round_trip_with_colors() {
+        let frames = frames();
+        let data = encode(ClipEncoder::new(WIDTH as u16, HEIGHT as u16), &frames);
+
+        assert_plays_back(&data, &frames);
+    }
+
+    #[test]
+    fn round_trip_with_palette() {
+        let frames = frames();
+        let palette = palette_of(&frames).unwrap();
+        let encoder = ClipEncoder::new(WIDTH as u16, HEIGHT as u16)
+            .with_palette(palette)
+            .unwrap();
+        let data = encode(encoder, &frames);
+
+        assert_plays_back(&data, &frames);
+    }
+
+    #[test]
+    fn looping_clip_starts_again() {
+        let frames = frames();
+        let data = encode(ClipEncoder::new(WIDTH as u16, HEIGHT as u16), &frames);
+
+        let mut player = ClipPlayer::new(Clip::new(&data).unwrap());
+        let mut graphics = UnicornGraphics::<WIDTH, HEIGHT>::new();
+        for _ in 0..frames.len() {
+            player.next_frame(&mut graphics).unwrap();
+        }
+
+        assert_eq!(player.next_frame(&mut graphics).unwrap().index, 0);
+        assert_eq!(Frame::from_graphics(&graphics).pixels(), frames[0].pixels());
+    }
+
+    #[test]
+    fn clip_played_once_finishes() {
+        let frames = frames();
+        let encoder = ClipEncoder::new(WIDTH as u16, HEIGHT as u16).with_looping(false);
+        let data = encode(encoder, &frames);
+
+        let mut player = ClipPlayer::new(Clip::new(&data).unwrap());
+        let mut graphics = UnicornGraphics::<WIDTH, HEIGHT>::new();
+        for _ in 0..frames.len() {
+            player.next_frame(&mut graphics).unwrap();
+        }
+
+        assert_eq!(player.next_frame(&mut graphics), None);
+    }
+
+    #[test]
+    fn unchanged_frame_is_only_skipped() {
+        let frame = &frames()[0];
+        let mut encoder = ClipEncoder::new(WIDTH as u16, HEIGHT as u16);
+        encoder.push_frame(frame, 10).unwrap();
+        let first = encoder.finish().len();
+
+        let mut encoder = ClipEncoder::new(WIDTH as u16, HEIGHT as u16);
+        encoder.push_frame(frame, 10).unwrap();
+        encoder.push_frame(frame, 10).unwrap();
+        let second = encoder.finish().len();
+
+        // a skip of the first 64 pixels, with the skip of the rest left out
+        assert_eq!(second - first, CLIP_FRAME_HEADER_LEN + 1);
+    }
+
+    #[test]
+    fn invalid_frames_are_rejected() {
+        let mut encoder = ClipEncoder::new(WIDTH as u16, HEIGHT as u16)
+            .with_palette(vec![Rgb888::BLACK])
+            .unwrap();
+
+        assert_eq!(
+            encoder.push_frame(&Frame::new(WIDTH, HEIGHT + 1), 10),
+            Err(ClipEncodeError::SizeMismatch)
+        );
+        assert_eq!(
+            encoder.push_frame(&frames()[2], 10),
+            Err(ClipEncodeError::NotInPalette(Rgb888::RED))
+        );
+        assert_eq!(encoder.frame_count(), 0);
+
+        let palette = vec![Rgb888::BLACK; 257];
+        assert!(matches!(
+            ClipEncoder::new(1, 1).with_palette(palette),
+            Err(ClipEncodeError::PaletteTooLarge)
+        ));
+    }
+}
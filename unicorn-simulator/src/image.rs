@@ -0,0 +1,166 @@
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufWriter},
+    path::Path,
+};
+
+use embedded_graphics_core::prelude::RgbColor;
+
+use crate::Frame;
+
+/// Color of the space between LEDs.
+const BACKGROUND: [u8; 3] = [12, 12, 12];
+
+/// Errors that can occur when writing images.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Png(png::EncodingError),
+    Gif(gif::EncodingError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "io error: {err}"),
+            Error::Png(err) => write!(f, "png error: {err}"),
+            Error::Gif(err) => write!(f, "gif error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Error::Io(value)
+    }
+}
+
+impl From<png::EncodingError> for Error {
+    fn from(value: png::EncodingError) -> Self {
+        Error::Png(value)
+    }
+}
+
+impl From<gif::EncodingError> for Error {
+    fn from(value: gif::EncodingError) -> Self {
+        Error::Gif(value)
+    }
+}
+
+/// How each pixel of a frame is drawn in an image.
+#[derive(Copy, Clone, Debug)]
+pub struct RenderOptions {
+    /// Size in image pixels of each LED.
+    pub scale: u32,
+
+    /// Size in image pixels of the space between LEDs.
+    pub gap: u32,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self { scale: 8, gap: 2 }
+    }
+}
+
+impl RenderOptions {
+    /// Size in image pixels of a frame rendered with these options.
+    pub fn image_size(&self, frame: &Frame) -> (u32, u32) {
+        let cell = self.scale + self.gap;
+        (
+            frame.width() as u32 * cell + self.gap,
+            frame.height() as u32 * cell + self.gap,
+        )
+    }
+
+    /// Render the frame into an RGB image buffer.
+    pub fn render(&self, frame: &Frame) -> Vec<u8> {
+        let (width, height) = self.image_size(frame);
+        let cell = self.scale + self.gap;
+
+        let mut data = Vec::with_capacity((width * height * 3) as usize);
+        for py in 0..height {
+            for px in 0..width {
+                let in_led = px % cell >= self.gap && py % cell >= self.gap;
+                let x = (px / cell) as usize;
+                let y = (py / cell) as usize;
+
+                if in_led && x < frame.width() && y < frame.height() {
+                    let color = frame.pixels()[y * frame.width() + x];
+                    data.extend_from_slice(&[color.r(), color.g(), color.b()]);
+                } else {
+                    data.extend_from_slice(&BACKGROUND);
+                }
+            }
+        }
+
+        data
+    }
+}
+
+/// Save the frame as a PNG file.
+pub fn save_png<P: AsRef<Path>>(
+    path: P,
+    frame: &Frame,
+    options: RenderOptions,
+) -> Result<(), Error> {
+    let (width, height) = options.image_size(frame);
+    let file = BufWriter::new(File::create(path)?);
+
+    let mut encoder = png::Encoder::new(file, width, height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&options.render(frame))?;
+
+    Ok(())
+}
+
+/// Records frames into an animated GIF file, looping forever.
+pub struct GifRecorder {
+    encoder: gif::Encoder<BufWriter<File>>,
+    options: RenderOptions,
+    size: (u32, u32),
+}
+
+impl GifRecorder {
+    /// Create a new recorder writing to the given path.
+    /// All frames added must be the same size as the given frame size.
+    pub fn new<P: AsRef<Path>>(
+        path: P,
+        width: usize,
+        height: usize,
+        options: RenderOptions,
+    ) -> Result<Self, Error> {
+        let size = options.image_size(&Frame::new(width, height));
+        let file = BufWriter::new(File::create(path)?);
+
+        let mut encoder = gif::Encoder::new(file, size.0 as u16, size.1 as u16, &[])?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+
+        Ok(Self {
+            encoder,
+            options,
+            size,
+        })
+    }
+
+    /// Add a frame to the animation, shown for the given number of milliseconds.
+    /// GIF delays have a resolution of 10ms.
+    pub fn add_frame(&mut self, frame: &Frame, delay_ms: u32) -> Result<(), Error> {
+        debug_assert_eq!(self.options.image_size(frame), self.size);
+
+        let data = self.options.render(frame);
+        let mut gif_frame =
+            gif::Frame::from_rgb_speed(self.size.0 as u16, self.size.1 as u16, &data, 10);
+        gif_frame.delay = (delay_ms / 10) as u16;
+
+        self.encoder.write_frame(&gif_frame)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,289 @@
+use std::fmt;
+
+use embedded_graphics_core::{
+    pixelcolor::Rgb888,
+    prelude::{Point, RgbColor},
+};
+use unicorn_bitstream::{
+    scan_position, BitstreamConfig, BitstreamEncoder, BCD_FRAME_BYTES, BCD_FRAME_COUNT, HEIGHT,
+    MIN_BCD_FRAME_COUNT, PIXEL_OFFSET, ROW_COUNT, WIDTH,
+};
+use unicorn_graphics::UnicornGraphics;
+
+pub mod clip;
+pub mod image;
+pub mod terminal;
+
+/// Maximum value of a decoded BCD channel.
+const BCD_MAX: f32 = ((1 << BCD_FRAME_COUNT) - 1) as f32;
+
+/// Errors that can occur when decoding a bitstream.
+#[derive(Debug, PartialEq, Eq)]
+pub enum BitstreamError {
+    /// The length of the bitstream is not a whole number of BCD frames for every row.
+    InvalidLength(usize),
+
+    /// The bitstream has a number of BCD frames per row the encoder never produces.
+    UnsupportedColorDepth(usize),
+}
+
+impl fmt::Display for BitstreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitstreamError::InvalidLength(len) => {
+                write!(
+                    f,
+                    "bitstream length {len} is not whole BCD frames for every row"
+                )
+            }
+            BitstreamError::UnsupportedColorDepth(count) => {
+                write!(f, "bitstream has {count} BCD frames per row")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BitstreamError {}
+
+/// A single frame of pixels as it would be seen on the display.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb888>,
+}
+
+impl Frame {
+    /// Create a new frame of the given size.
+    /// Defaults to `embedded_graphics_core::pixelcolor::Rgb888::BLACK` for all pixels.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![Rgb888::BLACK; width * height],
+        }
+    }
+
+    /// Create a frame of the given size from its pixels, row by row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of pixels does not match the size.
+    pub fn from_pixels(width: usize, height: usize, pixels: Vec<Rgb888>) -> Self {
+        assert_eq!(
+            pixels.len(),
+            width * height,
+            "pixels must match the frame size"
+        );
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Create a frame from the pixels held in the unicorn graphics instance, as is.
+    pub fn from_graphics<const W: usize, const H: usize>(graphics: &UnicornGraphics<W, H>) -> Self {
+        let mut frame = Self::new(W, H);
+
+        for (y, row) in graphics.get_pixels().iter().enumerate() {
+            for (x, color) in row.iter().enumerate() {
+                frame.pixels[y * W + x] = *color;
+            }
+        }
+
+        frame
+    }
+
+    /// Create a frame from the unicorn graphics instance as the display would show it,
+    /// by encoding it at the given brightness and decoding the resulting bitstream.
+    pub fn from_graphics_encoded(
+        graphics: &UnicornGraphics<WIDTH, HEIGHT>,
+        brightness: u8,
+    ) -> Self {
+        Self::from_graphics_encoded_with_config(graphics, brightness, BitstreamConfig::default())
+    }
+
+    /// Create a frame from the unicorn graphics instance as the display would show it
+    /// with the given color depth, by encoding it at the given brightness and decoding the resulting bitstream.
+    pub fn from_graphics_encoded_with_config(
+        graphics: &UnicornGraphics<WIDTH, HEIGHT>,
+        brightness: u8,
+        config: BitstreamConfig,
+    ) -> Self {
+        let mut encoder = BitstreamEncoder::with_config(config);
+        encoder.set_pixels(graphics, brightness);
+        Self::from_bitstream(encoder.as_bytes()).expect("the encoder produces a valid bitstream")
+    }
+
+    /// Create a frame by decoding the bitstream sent to the display.
+    /// The color depth is worked out from the length of the bitstream.
+    ///
+    /// The BCD frames of each pixel are summed to get the time the LED is lit for, which is then
+    /// converted from linear light to sRGB so it looks the same on a monitor as it does on the panel.
+    ///
+    /// Returns an error if the bitstream is not whole BCD frames for every row,
+    /// at a color depth the encoder supports.
+    pub fn from_bitstream(bitstream: &[u8]) -> Result<Self, BitstreamError> {
+        let mut frame = Self::new(WIDTH, HEIGHT);
+
+        if !bitstream.len().is_multiple_of(ROW_COUNT * BCD_FRAME_BYTES) {
+            return Err(BitstreamError::InvalidLength(bitstream.len()));
+        }
+
+        let row_bytes = bitstream.len() / ROW_COUNT;
+        let bcd_frame_count = row_bytes / BCD_FRAME_BYTES;
+        if !(MIN_BCD_FRAME_COUNT..=BCD_FRAME_COUNT).contains(&bcd_frame_count) {
+            return Err(BitstreamError::UnsupportedColorDepth(bcd_frame_count));
+        }
+
+        // scale lower color depths up to the maximum, as the encoder drops the least significant bits
+        let shift = BCD_FRAME_COUNT - bcd_frame_count;
+
+        for y in 0..HEIGHT {
+            for x in 0..WIDTH {
+                let (mut r, mut g, mut b) = (0_u16, 0_u16, 0_u16);
+
+                // The encoder flips both axes and may fold rows together, so undo that here
+                let (column, row) = scan_position(x, y);
+
+                for bcd_frame in 0..bcd_frame_count {
+                    let offset =
+                        row * row_bytes + (BCD_FRAME_BYTES * bcd_frame) + PIXEL_OFFSET + column;
+                    let bits = bitstream[offset] as u16;
+
+                    b |= (bits & 0b1) << (bcd_frame + shift);
+                    g |= ((bits >> 1) & 0b1) << (bcd_frame + shift);
+                    r |= ((bits >> 2) & 0b1) << (bcd_frame + shift);
+                }
+
+                frame.pixels[y * WIDTH + x] = Rgb888::new(to_srgb(r), to_srgb(g), to_srgb(b));
+            }
+        }
+
+        Ok(frame)
+    }
+
+    /// Width of the frame in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height of the frame in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Gets the pixel at the given point, providing the point is within the width and height.
+    pub fn get_item(&self, coord: Point) -> Option<Rgb888> {
+        let x = coord.x as usize;
+        let y = coord.y as usize;
+
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        Some(self.pixels[y * self.width + x])
+    }
+
+    /// Get all pixels of the frame, row by row.
+    pub fn pixels(&self) -> &[Rgb888] {
+        &self.pixels
+    }
+}
+
+impl<const W: usize, const H: usize> From<&UnicornGraphics<W, H>> for Frame {
+    fn from(value: &UnicornGraphics<W, H>) -> Self {
+        Self::from_graphics(value)
+    }
+}
+
+/// Convert a decoded BCD channel value in linear light to an 8 bit sRGB value.
+fn to_srgb(value: u16) -> u8 {
+    let linear = value as f32 / BCD_MAX;
+
+    let srgb = if linear <= 0.003_130_8 {
+        linear * 12.92
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    };
+
+    (srgb * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: Option<Rgb888>, expected: Rgb888) {
+        let actual = actual.unwrap();
+        let channels = [
+            (actual.r(), expected.r()),
+            (actual.g(), expected.g()),
+            (actual.b(), expected.b()),
+        ];
+        for (actual_channel, expected_channel) in channels {
+            assert!(
+                actual_channel.abs_diff(expected_channel) <= 2,
+                "{actual:?} is not close to {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn encoded_graphics_round_trip() {
+        let bottom_right = Point::new(WIDTH as i32 - 1, HEIGHT as i32 - 1);
+        let mut graphics = UnicornGraphics::<WIDTH, HEIGHT>::new();
+        graphics.set_pixel(Point::new(0, 0), Rgb888::WHITE);
+        graphics.set_pixel(bottom_right, Rgb888::RED);
+
+        for config in [
+            BitstreamConfig::default(),
+            BitstreamConfig::new(MIN_BCD_FRAME_COUNT, 1),
+        ] {
+            let frame = Frame::from_graphics_encoded_with_config(&graphics, 255, config);
+
+            assert_close(frame.get_item(Point::new(0, 0)), Rgb888::WHITE);
+            assert_close(frame.get_item(bottom_right), Rgb888::RED);
+            assert_eq!(frame.get_item(Point::new(1, 0)), Some(Rgb888::BLACK));
+        }
+    }
+
+    #[test]
+    fn bitstream_of_every_color_depth() {
+        for bcd_frame_count in MIN_BCD_FRAME_COUNT..=BCD_FRAME_COUNT {
+            let encoder = BitstreamEncoder::with_config(BitstreamConfig::new(bcd_frame_count, 1));
+            let frame = Frame::from_bitstream(encoder.as_bytes()).unwrap();
+
+            assert_eq!(frame.pixels().len(), WIDTH * HEIGHT);
+        }
+    }
+
+    #[test]
+    fn bitstream_of_invalid_length() {
+        let encoder = BitstreamEncoder::new();
+        let bytes = encoder.as_bytes();
+
+        for len in [bytes.len() - 1, bytes.len() + 1, BCD_FRAME_BYTES] {
+            let bitstream = vec![0; len];
+            assert_eq!(
+                Frame::from_bitstream(&bitstream),
+                Err(BitstreamError::InvalidLength(len))
+            );
+        }
+    }
+
+    #[test]
+    fn bitstream_of_unsupported_color_depth() {
+        let frame_bytes = ROW_COUNT * BCD_FRAME_BYTES;
+
+        for count in [0, MIN_BCD_FRAME_COUNT - 1, BCD_FRAME_COUNT + 1] {
+            let bitstream = vec![0; count * frame_bytes];
+            assert_eq!(
+                Frame::from_bitstream(&bitstream),
+                Err(BitstreamError::UnsupportedColorDepth(count))
+            );
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::{
+    fmt::Write as _,
+    io::{self, Write},
+};
+
+use embedded_graphics_core::{
+    pixelcolor::Rgb888,
+    prelude::{Point, RgbColor},
+};
+
+use crate::Frame;
+
+/// Render the frame as truecolor ANSI text.
+///
+/// Each character cell holds two pixels stacked vertically, using the upper half block
+/// with the foreground as the top pixel and the background as the bottom pixel.
+pub fn to_ansi(frame: &Frame) -> String {
+    let mut out = String::new();
+
+    for y in (0..frame.height()).step_by(2) {
+        for x in 0..frame.width() {
+            let top = frame
+                .get_item(Point::new(x as i32, y as i32))
+                .unwrap_or(Rgb888::BLACK);
+            let bottom = frame
+                .get_item(Point::new(x as i32, y as i32 + 1))
+                .unwrap_or(Rgb888::BLACK);
+
+            write!(
+                out,
+                "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m\u{2580}",
+                top.r(),
+                top.g(),
+                top.b(),
+                bottom.r(),
+                bottom.g(),
+                bottom.b()
+            )
+            .unwrap();
+        }
+        out.push_str("\x1b[0m\n");
+    }
+
+    out
+}
+
+/// Draws frames to a terminal, redrawing in place rather than scrolling.
+#[derive(Default)]
+pub struct TerminalRenderer {
+    /// Number of lines written by the last draw.
+    lines: usize,
+}
+
+impl TerminalRenderer {
+    /// Create a new terminal renderer.
+    pub fn new() -> Self {
+        Self { lines: 0 }
+    }
+
+    /// Draw the frame to stdout, replacing the previously drawn frame.
+    pub fn draw(&mut self, frame: &Frame) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+
+        if self.lines > 0 {
+            write!(stdout, "\x1b[{}A", self.lines)?;
+        }
+
+        stdout.write_all(to_ansi(frame).as_bytes())?;
+        stdout.flush()?;
+
+        self.lines = frame.height().div_ceil(2);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,216 @@
+use core::ops::{BitOr, BitOrAssign};
+
+/// Maximum value of the ADSR envelope, held as a 24 bit value.
+const ADSR_MAX: u32 = 0xff_ffff;
+
+/// Waveforms a channel can output.
+/// Waveforms can be combined, in which case they are mixed evenly.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct Waveform(u8);
+
+impl Waveform {
+    /// No waveform, the channel is silent.
+    pub const NONE: Waveform = Waveform(0);
+
+    /// Random noise, new values are generated each waveform cycle.
+    pub const NOISE: Waveform = Waveform(128);
+
+    /// Square wave, with the duty cycle set by the channel pulse width.
+    pub const SQUARE: Waveform = Waveform(64);
+
+    /// Saw tooth wave.
+    pub const SAW: Waveform = Waveform(32);
+
+    /// Triangle wave.
+    pub const TRIANGLE: Waveform = Waveform(16);
+
+    /// Sine wave.
+    pub const SINE: Waveform = Waveform(8);
+
+    /// Check if all waveforms in `other` are set.
+    pub fn contains(&self, other: Waveform) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Check if no waveforms are set.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Number of waveforms set.
+    pub fn count(&self) -> u32 {
+        self.0.count_ones()
+    }
+}
+
+impl BitOr for Waveform {
+    type Output = Waveform;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Waveform(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Waveform {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Phase of the attack, decay, sustain, release envelope.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum AdsrPhase {
+    Attack,
+    Decay,
+    Sustain,
+    Release,
+    Off,
+}
+
+/// A single voice of the synthesizer.
+#[derive(Copy, Clone, Debug)]
+pub struct AudioChannel {
+    /// Waveforms output by this channel.
+    pub waveforms: Waveform,
+
+    /// Frequency of the waveform in hertz.
+    pub frequency: u16,
+
+    /// Volume of this channel, where `0xffff` is full volume.
+    pub volume: u16,
+
+    /// Time in milliseconds to rise from silence to full volume.
+    pub attack_ms: u16,
+
+    /// Time in milliseconds to fall from full volume to the sustain level.
+    pub decay_ms: u16,
+
+    /// Level held until the note is released, where `0xffff` is full volume.
+    pub sustain: u16,
+
+    /// Time in milliseconds to fall from the sustain level to silence.
+    pub release_ms: u16,
+
+    /// Point in the waveform cycle at which a square wave goes low, where `0x7fff` is half way.
+    pub pulse_width: u16,
+
+    /// Position through the current waveform cycle, where `0x10000` is a full cycle.
+    pub(crate) waveform_offset: u32,
+
+    /// Current noise value, used by the noise waveform.
+    pub(crate) noise: i32,
+
+    adsr_phase: AdsrPhase,
+    adsr_frame: u32,
+    adsr_end_frame: u32,
+    adsr: u32,
+    adsr_step: i32,
+}
+
+impl AudioChannel {
+    /// Create a new silent channel.
+    pub const fn new() -> Self {
+        Self {
+            waveforms: Waveform::NONE,
+            frequency: 660,
+            volume: 0xffff,
+            attack_ms: 2,
+            decay_ms: 6,
+            sustain: 0xffff,
+            release_ms: 1,
+            pulse_width: 0x7fff,
+            waveform_offset: 0,
+            noise: 0,
+            adsr_phase: AdsrPhase::Off,
+            adsr_frame: 0,
+            adsr_end_frame: 0,
+            adsr: 0,
+            adsr_step: 0,
+        }
+    }
+
+    /// Start playing the channel from the attack phase.
+    pub fn trigger_attack(&mut self, sample_rate: u32) {
+        let end_frame = Self::ms_to_frames(self.attack_ms, sample_rate);
+        self.start_phase(AdsrPhase::Attack, end_frame, ADSR_MAX);
+    }
+
+    /// Move the channel into the decay phase.
+    pub fn trigger_decay(&mut self, sample_rate: u32) {
+        let end_frame = Self::ms_to_frames(self.decay_ms, sample_rate);
+        self.start_phase(AdsrPhase::Decay, end_frame, (self.sustain as u32) << 8);
+    }
+
+    /// Move the channel into the sustain phase, holding the current level.
+    pub fn trigger_sustain(&mut self) {
+        self.adsr_frame = 0;
+        self.adsr_phase = AdsrPhase::Sustain;
+        self.adsr_end_frame = 0;
+        self.adsr_step = 0;
+    }
+
+    /// Release the channel, fading out to silence.
+    pub fn trigger_release(&mut self, sample_rate: u32) {
+        let end_frame = Self::ms_to_frames(self.release_ms, sample_rate);
+        self.start_phase(AdsrPhase::Release, end_frame, 0);
+    }
+
+    /// Stop the channel immediately.
+    pub fn off(&mut self) {
+        self.adsr_frame = 0;
+        self.adsr_phase = AdsrPhase::Off;
+        self.adsr_step = 0;
+        self.adsr = 0;
+    }
+
+    /// Get the current phase of the envelope.
+    pub fn adsr_phase(&self) -> AdsrPhase {
+        self.adsr_phase
+    }
+
+    /// Get the current level of the envelope, where `0xffff` is full volume.
+    pub fn adsr_level(&self) -> u16 {
+        (self.adsr >> 8) as u16
+    }
+
+    /// Check if the channel is producing sound.
+    pub fn is_playing(&self) -> bool {
+        self.adsr_phase != AdsrPhase::Off
+    }
+
+    /// Advance the envelope by one frame, moving onto the next phase when the current one ends.
+    pub(crate) fn advance_adsr(&mut self, sample_rate: u32) {
+        if self.adsr_frame >= self.adsr_end_frame && self.adsr_phase != AdsrPhase::Sustain {
+            match self.adsr_phase {
+                AdsrPhase::Attack => self.trigger_decay(sample_rate),
+                AdsrPhase::Decay => self.trigger_sustain(),
+                AdsrPhase::Release => self.off(),
+                _ => {}
+            }
+        }
+
+        self.adsr = self
+            .adsr
+            .saturating_add_signed(self.adsr_step)
+            .min(ADSR_MAX);
+        self.adsr_frame = self.adsr_frame.saturating_add(1);
+    }
+
+    fn start_phase(&mut self, phase: AdsrPhase, end_frame: u32, target: u32) {
+        self.adsr_frame = 0;
+        self.adsr_phase = phase;
+        self.adsr_end_frame = end_frame;
+        self.adsr_step = (target as i32 - self.adsr as i32) / end_frame as i32;
+    }
+
+    /// Number of frames for the given time, at least one so every phase has an end.
+    fn ms_to_frames(ms: u16, sample_rate: u32) -> u32 {
+        ((ms as u32 * sample_rate) / 1000).max(1)
+    }
+}
+
+impl Default for AudioChannel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
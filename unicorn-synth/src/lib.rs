@@ -0,0 +1,296 @@
+#![no_std]
+
+pub use channel::{AdsrPhase, AudioChannel, Waveform};
+
+mod channel;
+
+/// Number of channels available on the default synthesizer.
+pub const CHANNEL_COUNT: usize = 8;
+
+/// Seed for the noise generator.
+const NOISE_SEED: u32 = 0x32b7_1700;
+
+/// A multi voice synthesizer, mixing all channels into signed 16 bit mono samples.
+#[derive(Clone, Debug)]
+pub struct Synth<const N: usize = CHANNEL_COUNT> {
+    /// The voices of the synthesizer.
+    pub channels: [AudioChannel; N],
+
+    /// Volume applied to the final mix, where `0xffff` is full volume.
+    pub volume: u16,
+
+    sample_rate: u32,
+    noise_state: u32,
+}
+
+impl<const N: usize> Synth<N> {
+    /// Create a new synthesizer generating samples at the given sample rate, which is at least 1.
+    pub const fn new(sample_rate: u32) -> Self {
+        let sample_rate = if sample_rate == 0 { 1 } else { sample_rate };
+
+        Self {
+            channels: [AudioChannel::new(); N],
+            volume: 0x7fff,
+            sample_rate,
+            noise_state: NOISE_SEED,
+        }
+    }
+
+    /// Get the sample rate samples are generated at.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Start playing the given channel from the attack phase.
+    pub fn trigger_attack(&mut self, channel: usize) {
+        if let Some(channel) = self.channels.get_mut(channel) {
+            channel.trigger_attack(self.sample_rate);
+        }
+    }
+
+    /// Release the given channel, fading out to silence.
+    pub fn trigger_release(&mut self, channel: usize) {
+        if let Some(channel) = self.channels.get_mut(channel) {
+            channel.trigger_release(self.sample_rate);
+        }
+    }
+
+    /// Stop all channels immediately.
+    pub fn stop_all(&mut self) {
+        for channel in self.channels.iter_mut() {
+            channel.off();
+        }
+    }
+
+    /// Check if any channel is producing sound.
+    pub fn is_playing(&self) -> bool {
+        self.channels.iter().any(|channel| channel.is_playing())
+    }
+
+    /// Fill the buffer with the next samples.
+    pub fn fill(&mut self, buffer: &mut [i16]) {
+        for sample in buffer.iter_mut() {
+            *sample = self.next_sample();
+        }
+    }
+
+    /// Generate the next sample, mixing all channels together.
+    pub fn next_sample(&mut self) -> i16 {
+        let mut sample: i32 = 0;
+
+        for index in 0..N {
+            let channel = &mut self.channels[index];
+
+            if !channel.is_playing() {
+                continue;
+            }
+
+            // Q16 fixed point value representing how far through the current waveform we are
+            channel.waveform_offset += ((channel.frequency as u32 * 256) << 8) / self.sample_rate;
+
+            channel.advance_adsr(self.sample_rate);
+
+            // Generate a new noise value each time the waveform cycle completes
+            if channel.waveform_offset & 0x10000 != 0 {
+                channel.noise = Self::next_noise(&mut self.noise_state);
+            }
+
+            channel.waveform_offset &= 0xffff;
+
+            if channel.waveforms.is_empty() {
+                continue;
+            }
+
+            let offset = channel.waveform_offset as i32;
+            let mut channel_sample: i32 = 0;
+
+            if channel.waveforms.contains(Waveform::NOISE) {
+                channel_sample += channel.noise;
+            }
+
+            if channel.waveforms.contains(Waveform::SAW) {
+                channel_sample += offset - 0x7fff;
+            }
+
+            if channel.waveforms.contains(Waveform::TRIANGLE) {
+                if offset < 0x7fff {
+                    channel_sample += offset * 2 - 0x7fff;
+                } else {
+                    channel_sample += 0x7fff - (offset - 0x7fff) * 2;
+                }
+            }
+
+            if channel.waveforms.contains(Waveform::SQUARE) {
+                channel_sample += if offset < channel.pulse_width as i32 {
+                    0x7fff
+                } else {
+                    -0x7fff
+                };
+            }
+
+            if channel.waveforms.contains(Waveform::SINE) {
+                channel_sample += SINE_WAVEFORM[(offset >> 8) as usize & 0xff] as i32;
+            }
+
+            channel_sample /= channel.waveforms.count() as i32;
+
+            // Apply the envelope and then the channel volume
+            channel_sample = ((channel_sample as i64 * channel.adsr_level() as i64) >> 16) as i32;
+            channel_sample = ((channel_sample as i64 * channel.volume as i64) >> 16) as i32;
+
+            sample += channel_sample;
+        }
+
+        sample = ((sample as i64 * self.volume as i64) >> 16) as i32;
+
+        sample.clamp(i16::MIN as i32, i16::MAX as i32) as i16
+    }
+
+    /// Rough approximation of normally distributed noise, from a xorshift generator.
+    fn next_noise(state: &mut u32) -> i32 {
+        let mut next = || {
+            let mut x = *state;
+            x ^= x << 13;
+            x ^= x >> 17;
+            x ^= x << 5;
+            *state = x;
+            x
+        };
+
+        let r0 = next();
+        let r1 = next();
+        let n = ((r0 & 0xffff) + (r1 & 0xffff) + (r0 >> 16) + (r1 >> 16)) / 2;
+
+        n as i32 - 0xffff
+    }
+}
+
+/// A single cycle of a sine wave.
+static SINE_WAVEFORM: [i16; 256] = [
+    0, 804, 1608, 2410, 3212, 4011, 4808, 5602, 6393, 7179, 7962, 8739, 9512, 10278, 11039, 11793,
+    12539, 13279, 14010, 14732, 15446, 16151, 16846, 17530, 18204, 18868, 19519, 20159, 20787,
+    21403, 22005, 22594, 23170, 23731, 24279, 24811, 25329, 25832, 26319, 26790, 27245, 27683,
+    28105, 28510, 28898, 29268, 29621, 29956, 30273, 30571, 30852, 31113, 31356, 31580, 31785,
+    31971, 32137, 32285, 32412, 32521, 32609, 32678, 32728, 32757, 32767, 32757, 32728, 32678,
+    32609, 32521, 32412, 32285, 32137, 31971, 31785, 31580, 31356, 31113, 30852, 30571, 30273,
+    29956, 29621, 29268, 28898, 28510, 28105, 27683, 27245, 26790, 26319, 25832, 25329, 24811,
+    24279, 23731, 23170, 22594, 22005, 21403, 20787, 20159, 19519, 18868, 18204, 17530, 16846,
+    16151, 15446, 14732, 14010, 13279, 12539, 11793, 11039, 10278, 9512, 8739, 7962, 7179, 6393,
+    5602, 4808, 4011, 3212, 2410, 1608, 804, 0, -804, -1608, -2410, -3212, -4011, -4808, -5602,
+    -6393, -7179, -7962, -8739, -9512, -10278, -11039, -11793, -12539, -13279, -14010, -14732,
+    -15446, -16151, -16846, -17530, -18204, -18868, -19519, -20159, -20787, -21403, -22005, -22594,
+    -23170, -23731, -24279, -24811, -25329, -25832, -26319, -26790, -27245, -27683, -28105, -28510,
+    -28898, -29268, -29621, -29956, -30273, -30571, -30852, -31113, -31356, -31580, -31785, -31971,
+    -32137, -32285, -32412, -32521, -32609, -32678, -32728, -32757, -32767, -32757, -32728, -32678,
+    -32609, -32521, -32412, -32285, -32137, -31971, -31785, -31580, -31356, -31113, -30852, -30571,
+    -30273, -29956, -29621, -29268, -28898, -28510, -28105, -27683, -27245, -26790, -26319, -25832,
+    -25329, -24811, -24279, -23731, -23170, -22594, -22005, -21403, -20787, -20159, -19519, -18868,
+    -18204, -17530, -16846, -16151, -15446, -14732, -14010, -13279, -12539, -11793, -11039, -10278,
+    -9512, -8739, -7962, -7179, -6393, -5602, -4808, -4011, -3212, -2410, -1608, -804,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Synth at full volume with a single channel playing the waveform,
+    /// at a frequency where each cycle is exactly 8 samples.
+    fn synth_with(waveform: Waveform) -> Synth<1> {
+        let mut synth = Synth::<1>::new(8000);
+        synth.volume = 0xffff;
+
+        let channel = &mut synth.channels[0];
+        channel.waveforms = waveform;
+        channel.frequency = 1000;
+        channel.attack_ms = 0;
+        channel.decay_ms = 0;
+
+        synth.trigger_attack(0);
+        synth
+    }
+
+    #[test]
+    fn square_wave_samples() {
+        let mut synth = synth_with(Waveform::SQUARE);
+        let mut buffer = [0; 16];
+        synth.fill(&mut buffer);
+
+        let (high, low) = (32764, -32767);
+        assert_eq!(
+            buffer,
+            [
+                high, high, high, low, low, low, low, high, high, high, high, low, low, low, low,
+                high
+            ]
+        );
+    }
+
+    #[test]
+    fn saw_wave_samples() {
+        let mut synth = synth_with(Waveform::SAW);
+        let mut buffer = [0; 8];
+        synth.fill(&mut buffer);
+
+        assert_eq!(
+            buffer,
+            [-24575, -16383, -8191, 0, 8190, 16382, 24574, -32767]
+        );
+    }
+
+    #[test]
+    fn same_samples_from_fill_and_next_sample() {
+        let mut filled = synth_with(Waveform::NOISE | Waveform::TRIANGLE);
+        let mut stepped = filled.clone();
+
+        let mut buffer = [0; 64];
+        filled.fill(&mut buffer);
+
+        for sample in buffer {
+            assert_eq!(sample, stepped.next_sample());
+        }
+    }
+
+    #[test]
+    fn silent_after_release() {
+        let mut synth = synth_with(Waveform::SINE);
+        synth.channels[0].release_ms = 1;
+        synth.trigger_release(0);
+
+        let mut buffer = [0; 16];
+        synth.fill(&mut buffer);
+
+        assert!(!synth.is_playing());
+        assert_eq!(buffer[12..], [0; 4]);
+    }
+
+    #[test]
+    fn idle_channel_runs_without_overflow() {
+        let mut synth = Synth::<1>::new(22050);
+        synth.channels[0].waveforms = Waveform::SQUARE;
+        synth.channels[0].frequency = 660;
+
+        for _ in 0..(1 << 22) + 1 {
+            assert_eq!(synth.next_sample(), 0);
+        }
+    }
+
+    #[test]
+    fn sustained_channel_runs_without_overflow() {
+        let mut synth = synth_with(Waveform::SQUARE);
+
+        for _ in 0..(1 << 22) + 1 {
+            synth.next_sample();
+        }
+        assert_eq!(synth.channels[0].adsr_phase(), AdsrPhase::Sustain);
+    }
+
+    #[test]
+    fn zero_sample_rate_is_clamped() {
+        let mut synth = Synth::<1>::new(0);
+        assert_eq!(synth.sample_rate(), 1);
+
+        synth.channels[0].waveforms = Waveform::SINE;
+        synth.trigger_attack(0);
+        synth.next_sample();
+    }
+}